@@ -12,4 +12,18 @@ pub struct EnumCaseAttribute {
 
     #[has_attribute]
     pub default: bool,
+
+    /// Extra spellings accepted on the way IN (never written). Several are comma-separated:
+    /// `alias = "canceled,cancelled"`.
+    pub alias: Option<String>,
+
+    /// Match the value and the aliases ignoring ASCII case. Opt-in per case: an exact match is
+    /// always tried first, so a case that is not marked keeps its exact-only behaviour.
+    #[has_attribute]
+    pub case_insensitive: bool,
+
+    /// The catch-all: a `Variant(String)` that keeps any value no other case matched, verbatim,
+    /// so it writes back out exactly as it came in.
+    #[has_attribute]
+    pub other: bool,
 }
@@ -6,6 +6,10 @@ pub struct HttpBodyAttribute<'s> {
     pub name: Option<&'s str>,
     pub description: &'s str,
 
+    /// A dot-separated member path (`data.attributes.email`) for a field that sits inside nested
+    /// envelope objects. Replaces `name`: the last segment is the member's own key.
+    pub path: Option<&'s str>,
+
     #[allow_ident]
     pub validator: Option<&'s str>,
 
@@ -20,6 +24,10 @@ pub struct HttpBodyAttribute<'s> {
 
     pub default: Option<types_reader::AnyValue<'s>>,
 
+    pub format: Option<&'s str>,
+
+    pub parse_profile: Option<&'s str>,
+
     #[has_attribute]
     pub print_request_to_console: bool,
 }
@@ -0,0 +1,13 @@
+use types_reader::macros::*;
+
+/// `#[http_body_array]` — the whole body is a JSON array, read into this one `Vec<T>` field
+/// (`T` a `MyHttpInputObjectStructure`).
+///
+/// Only `name` / `description`, like `#[http_body_as_stream]`: the value is a list of objects, not
+/// a string, so the outgoing-value directives have nothing to act on.
+#[attribute_name("http_body_array")]
+#[derive(MacrosParameters, Clone)]
+pub struct HttpBodyArrayAttribute<'s> {
+    pub name: Option<&'s str>,
+    pub description: &'s str,
+}
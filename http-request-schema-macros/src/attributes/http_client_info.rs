@@ -0,0 +1,7 @@
+use types_reader::macros::*;
+
+/// `#[http_client_info]` — a `ClientInfo` field, read out of the peer address and the forwarding
+/// headers of trusted proxies. Not an input of the endpoint: it stays out of the docs, and the
+/// client does not send it.
+#[attribute_name("http_client_info")]
+pub struct HttpClientInfoAttribute {}
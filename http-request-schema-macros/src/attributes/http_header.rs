@@ -20,6 +20,10 @@ pub struct HttpHeaderAttribute<'s> {
 
     pub default: Option<types_reader::AnyValue<'s>>,
 
+    pub format: Option<&'s str>,
+
+    pub parse_profile: Option<&'s str>,
+
     #[has_attribute]
     pub print_request_to_console: bool,
 }
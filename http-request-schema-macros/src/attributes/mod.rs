@@ -12,6 +12,10 @@ mod http_body_raw;
 pub use http_body_raw::*;
 mod http_body_as_stream;
 pub use http_body_as_stream::*;
+mod http_body_array;
+pub use http_body_array::*;
+mod http_client_info;
+pub use http_client_info::*;
 mod ignore;
 pub use ignore::*;
 mod enum_case;
@@ -0,0 +1,224 @@
+//! The wire format of a date-time field — `format = "…"` on an input field, `#[json_format = "…"]`
+//! on an object member — checked at compile time and turned into the
+//! `my_http_utils::DateTimeFormat` every generated half names.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use types_reader::PropertyType;
+
+/// Where a format is used. The query string repeats a key for each element of a `Vec`, so it
+/// takes `Vec<DateTimeAsMicroseconds>` too; every other source holds one value.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DateTimeFormatSource {
+    Query,
+    Value,
+}
+
+/// `my_http_utils::DateTimeFormat::…` for `format`, after checking it and the field's type.
+pub fn compile_date_time_format(
+    spanned: &impl ToTokens,
+    format: &str,
+    ty: &PropertyType,
+    source: DateTimeFormatSource,
+) -> Result<TokenStream, syn::Error> {
+    let element = match ty {
+        PropertyType::OptionOf(inner) => inner.as_ref(),
+        PropertyType::VecOf(inner) if source == DateTimeFormatSource::Query => inner.as_ref(),
+        other => other,
+    };
+
+    if !matches!(element, PropertyType::DateTime) {
+        let expected = match source {
+            DateTimeFormatSource::Query => {
+                "DateTimeAsMicroseconds, Option<DateTimeAsMicroseconds> or Vec<DateTimeAsMicroseconds>"
+            }
+            DateTimeFormatSource::Value => {
+                "DateTimeAsMicroseconds or Option<DateTimeAsMicroseconds>"
+            }
+        };
+        return Err(syn::Error::new_spanned(
+            spanned,
+            format!("a date-time format needs a field of type {}", expected),
+        ));
+    }
+
+    let result = match format {
+        "unix_s" => quote!(my_http_utils::DateTimeFormat::UnixSeconds),
+        "unix_ms" => quote!(my_http_utils::DateTimeFormat::UnixMilliseconds),
+        "unix_us" => quote!(my_http_utils::DateTimeFormat::UnixMicroseconds),
+        "rfc3339" => quote!(my_http_utils::DateTimeFormat::Rfc3339),
+        "date" => quote!(my_http_utils::DateTimeFormat::Date),
+        pattern => {
+            check_pattern(pattern).map_err(|message| syn::Error::new_spanned(spanned, message))?;
+            quote!(my_http_utils::DateTimeFormat::Pattern(#pattern))
+        }
+    };
+
+    Ok(result)
+}
+
+/// A pattern may only use the specifiers `DateTimeFormat` reads back, and has to use at least one
+/// — a pattern of literals alone would read every value as the epoch.
+fn check_pattern(pattern: &str) -> Result<(), String> {
+    let mut specifiers = 0;
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+
+        match chars.next() {
+            Some('Y' | 'm' | 'd' | 'H' | 'M' | 'S') => specifiers += 1,
+            Some('3' | '6') if chars.next() == Some('f') => specifiers += 1,
+            Some('%') => {}
+            Some(other) => {
+                return Err(format!(
+                    "unknown date-time format '{}': '%{}' is not a specifier. Use unix_s, unix_ms, \
+                     unix_us, rfc3339, date, or a pattern of %Y %m %d %H %M %S %3f %6f %%",
+                    pattern, other
+                ))
+            }
+            None => {
+                return Err(format!(
+                    "date-time format '{}' ends with a bare '%'",
+                    pattern
+                ))
+            }
+        }
+    }
+
+    if specifiers == 0 {
+        return Err(format!(
+            "unknown date-time format '{}'. Use unix_s, unix_ms, unix_us, rfc3339, date, or a \
+             pattern of %Y %m %d %H %M %S %3f %6f %%",
+            pattern
+        ));
+    }
+
+    Ok(())
+}
+
+/// A `default = "…"` has to be a date in the field's `format`, or every request that leaves the
+/// field out would fail to parse it. Mirrors `DateTimeFormat::parse` in `my_http_utils`, which a
+/// proc-macro can not call.
+pub fn check_date_time_default(
+    spanned: &impl ToTokens,
+    format: &str,
+    value: &str,
+) -> Result<(), syn::Error> {
+    let valid = match format {
+        "unix_s" => {
+            read_unix(value).is_some_and(|seconds| seconds.checked_mul(1_000_000).is_some())
+        }
+        "unix_ms" => read_unix(value).is_some_and(|millis| millis.checked_mul(1_000).is_some()),
+        "unix_us" => read_unix(value).is_some(),
+        "rfc3339" => is_rfc3339(value),
+        "date" => read_pattern("%Y-%m-%d", value).is_some_and(str::is_empty),
+        pattern => read_pattern(pattern, value).is_some_and(str::is_empty),
+    };
+
+    if valid {
+        return Ok(());
+    }
+
+    Err(syn::Error::new_spanned(
+        spanned,
+        format!(
+            "default '{}' is not a date in the format '{}'",
+            value, format
+        ),
+    ))
+}
+
+fn read_unix(src: &str) -> Option<i64> {
+    let digits = src.strip_prefix('-').unwrap_or(src);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    src.parse().ok()
+}
+
+/// `2024-02-29T13:45:07Z`, with an optional fraction of a second and a `Z` or `±HH:MM` offset.
+fn is_rfc3339(src: &str) -> bool {
+    let Some(rest) = read_pattern("%Y-%m-%dT%H:%M:%S", src) else {
+        return false;
+    };
+
+    let rest = match rest.strip_prefix('.') {
+        Some(fraction) => {
+            let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+            if digits == 0 {
+                return false;
+            }
+            &fraction[digits..]
+        }
+        None => rest,
+    };
+
+    match rest {
+        "Z" => true,
+        offset => match offset.strip_prefix(['+', '-']) {
+            Some(offset) => read_pattern("%H:%M", offset).is_some_and(str::is_empty),
+            None => false,
+        },
+    }
+}
+
+/// What is left of `src` after `pattern`, when the part it covers is a valid date and time.
+fn read_pattern<'s>(pattern: &str, src: &'s str) -> Option<&'s str> {
+    let (mut year, mut month, mut day) = (1970, 1, 1);
+    let (mut hour, mut minute, mut second) = (0, 0, 0);
+
+    let mut src = src;
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            src = src.strip_prefix(c)?;
+            continue;
+        }
+
+        match chars.next()? {
+            'Y' => year = take_digits(&mut src, 4)?,
+            'm' => month = take_digits(&mut src, 2)?,
+            'd' => day = take_digits(&mut src, 2)?,
+            'H' => hour = take_digits(&mut src, 2)?,
+            'M' => minute = take_digits(&mut src, 2)?,
+            'S' => second = take_digits(&mut src, 2)?,
+            '3' if chars.next() == Some('f') => {
+                take_digits(&mut src, 3)?;
+            }
+            '6' if chars.next() == Some('f') => {
+                take_digits(&mut src, 6)?;
+            }
+            '%' => src = src.strip_prefix('%')?,
+            _ => return None,
+        }
+    }
+
+    let days_in_month = match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+
+    let valid = (1..=12).contains(&month)
+        && day >= 1
+        && day <= days_in_month
+        && hour < 24
+        && minute < 60
+        && second < 60;
+
+    valid.then_some(src)
+}
+
+/// Exactly `count` ASCII digits.
+fn take_digits(src: &mut &str, count: usize) -> Option<i64> {
+    let digits = src.get(..count)?;
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    *src = &src[count..];
+    digits.parse().ok()
+}
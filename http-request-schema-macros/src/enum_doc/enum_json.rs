@@ -28,7 +28,7 @@ impl<'s> EnumJson<'s> {
         })
     }
 
-    pub fn get_id(&self) -> Result<isize, syn::Error> {
+    pub fn get_id(&self) -> Result<i64, syn::Error> {
         if let Some(id) = self.attr.id.as_ref() {
             match id.parse() {
                 Ok(id) => return Ok(id),
@@ -58,4 +58,40 @@ impl<'s> EnumJson<'s> {
     pub fn description(&self) -> &str {
         self.attr.description.as_str()
     }
+
+    pub fn is_other(&self) -> bool {
+        self.attr.other
+    }
+
+    /// Every spelling this case is READ from: the value, the id and the aliases, deduplicated so
+    /// the generated `match` has no unreachable pattern of its own making.
+    pub fn get_read_patterns(&self) -> Result<Vec<String>, syn::Error> {
+        let mut result = vec![self.get_enum_case_str_value()?];
+
+        if let Some(id) = self.attr.id.as_ref() {
+            result.push(id.to_string());
+        }
+
+        if let Some(aliases) = self.attr.alias.as_ref() {
+            for alias in aliases.split(',') {
+                let alias = alias.trim();
+                if alias.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        self.src.get_name_ident(),
+                        "[alias] must not be empty",
+                    ));
+                }
+                result.push(alias.to_string());
+            }
+        }
+
+        let mut deduped: Vec<String> = Vec::with_capacity(result.len());
+        for pattern in result {
+            if !deduped.contains(&pattern) {
+                deduped.push(pattern);
+            }
+        }
+
+        Ok(deduped)
+    }
 }
@@ -28,6 +28,8 @@ pub fn generate(ast: &syn::DeriveInput, as_integer: bool) -> Result<TokenStream,
         fields.push(enum_json);
     }
 
+    let other_case = read_other_case(ast, &fields, as_integer)?;
+
     // Default trait, from the case marked `default` (if any) — pure, no transport.
     let default_trait = if let Some(default_case) = &default_case_value {
         let default_case = proc_macro2::TokenStream::from_str(default_case).unwrap();
@@ -57,10 +59,20 @@ pub fn generate(ast: &syn::DeriveInput, as_integer: bool) -> Result<TokenStream,
 
     let enum_as_str = generate_enum_as_str(&fields)?;
 
+    // The catch-all hands out the text it holds, so `as_str()` can only promise `&'static str`
+    // while there is none — an enum without one keeps the signature it always had.
+    let as_str_return_type = if other_case.is_some() {
+        quote::quote!(&str)
+    } else {
+        quote::quote!(&'static str)
+    };
+
+    let from_case_str_fn = generate_from_case_str_fn(&fields, other_case)?;
+
     // Server-side conversion from a parsed request value into the enum — only emitted with the
     // `server` feature (it references `my_http_utils::http_input`, which is server-gated).
     let try_from_input = if cfg!(feature = "server") {
-        generate_try_from_input(struct_name, &fields)?
+        generate_try_from_input(struct_name)?
     } else {
         quote::quote!()
     };
@@ -102,13 +114,15 @@ pub fn generate(ast: &syn::DeriveInput, as_integer: bool) -> Result<TokenStream,
         // value -> string. The client request builder uses `as_str()` to serialize an
         // enum-typed field into a request.
         impl #struct_name{
-            pub fn as_str(&self) -> &'static str{
+            pub fn as_str(&self) -> #as_str_return_type{
                 match self{
                     #(#enum_as_str)*
                 }
             }
 
             #default_as_str_fn
+
+            #from_case_str_fn
         }
 
         // value -> JSON string. Always emitted so the client request builder can serialise an
@@ -145,34 +159,23 @@ pub fn generate(ast: &syn::DeriveInput, as_integer: bool) -> Result<TokenStream,
 /// Deriving serde on such an enum is now a `conflicting implementations` error: that is deliberate,
 /// and the fix is to drop the `Serialize`/`Deserialize` from the derive list.
 ///
-/// `Deserialize` accepts the `value` **or** the numeric `id`, matching `TryFrom<HttpInputValue>`
-/// (which takes both) — one behaviour, not two. It reads through `deserialize_any` so an id that
-/// arrives as a JSON number (`5`) is accepted alongside the string form (`"5"`).
+/// `Deserialize` accepts the `value`, the numeric `id` or an alias through the same
+/// `__from_case_str` lookup `TryFrom<HttpInputValue>` uses — one behaviour, not two. It reads
+/// through `deserialize_any` so an id that arrives as a JSON number (`5`) is accepted alongside the
+/// string form (`"5"`).
 fn generate_serde_impls(
     struct_name: &syn::Ident,
     cases: &[EnumJson],
 ) -> Result<proc_macro2::TokenStream, syn::Error> {
     let struct_name_as_str = struct_name.to_string();
 
-    let mut arms = Vec::with_capacity(cases.len());
     let mut expected = Vec::with_capacity(cases.len());
 
     for case in cases {
-        let variant = case.src.get_name_ident();
-        let str_value = case.get_enum_case_str_value()?;
-
-        let mut patterns = vec![str_value.clone()];
-        if let Some(id) = case.attr.id.as_ref() {
-            if id != &str_value {
-                patterns.push(id.clone());
-            }
+        if case.is_other() {
+            continue;
         }
-        expected.push(format!("`{}`", str_value));
-
-        let patterns = patterns.iter().map(|p| quote::quote!(#p));
-        arms.push(quote::quote! {
-            #(#patterns)|* => Ok(#struct_name::#variant),
-        });
+        expected.push(format!("`{}`", case.get_enum_case_str_value()?));
     }
 
     let expecting = format!("one of {} (or a case id)", expected.join(", "));
@@ -210,12 +213,12 @@ fn generate_serde_impls(
                     }
                 };
 
-                match __s {
-                    #(#arms)*
-                    __other => Err(my_http_utils::my_json::json_reader::JsonParseError::new(
+                match #struct_name::__from_case_str(__s) {
+                    Some(__case) => Ok(__case),
+                    None => Err(my_http_utils::my_json::json_reader::JsonParseError::new(
                         format!(
                             "unknown value `{}` for {}, expected {}",
-                            __other, #struct_name_as_str, #expecting
+                            __s, #struct_name_as_str, #expecting
                         ),
                     )),
                 }
@@ -248,11 +251,11 @@ fn generate_serde_impls(
                         self,
                         __v: &str,
                     ) -> Result<Self::Value, __E> {
-                        match __v {
-                            #(#arms)*
-                            __other => Err(__E::custom(format!(
+                        match #struct_name::__from_case_str(__v) {
+                            Some(__case) => Ok(__case),
+                            None => Err(__E::custom(format!(
                                 "unknown value `{}` for {}, expected {}",
-                                __other, #struct_name_as_str, #expecting
+                                __v, #struct_name_as_str, #expecting
                             ))),
                         }
                     }
@@ -279,33 +282,13 @@ fn generate_serde_impls(
     })
 }
 
-/// Emits `TryFrom<HttpInputValue>`: reads the value as a string and matches it against each
-/// case's string form (the same string `as_str` emits, so client→server round-trips) plus its
-/// numeric `id`. Only *emitted* with the `server` feature (the fn itself just builds tokens, so
+/// Emits `TryFrom<HttpInputValue>`: reads the value as a string and looks it up with
+/// `__from_case_str` — the case's string form (the same string `as_str` emits, so client→server
+/// round-trips), its numeric `id` and its aliases. Only *emitted* with the `server` feature (the fn itself just builds tokens, so
 /// it always compiles).
 fn generate_try_from_input(
     struct_name: &syn::Ident,
-    cases: &[EnumJson],
 ) -> Result<proc_macro2::TokenStream, syn::Error> {
-    let mut arms = Vec::with_capacity(cases.len());
-
-    for case in cases {
-        let variant = case.src.get_name_ident();
-        let str_value = case.get_enum_case_str_value()?;
-
-        let mut patterns = vec![str_value.clone()];
-        if let Some(id) = case.attr.id.as_ref() {
-            if id != &str_value {
-                patterns.push(id.clone());
-            }
-        }
-        let patterns = patterns.iter().map(|p| quote::quote!(#p));
-
-        arms.push(quote::quote! {
-            #(#patterns)|* => Ok(Self::#variant),
-        });
-    }
-
     Ok(quote::quote! {
         impl<'s> std::convert::TryFrom<my_http_utils::http_input::HttpInputValue<'s>> for #struct_name {
             type Error = my_http_utils::http_input::HttpParseError;
@@ -313,9 +296,9 @@ fn generate_try_from_input(
                 __value: my_http_utils::http_input::HttpInputValue<'s>,
             ) -> Result<Self, Self::Error> {
                 let __s = __value.as_string()?;
-                match __s.as_str() {
-                    #(#arms)*
-                    _ => Err(my_http_utils::http_input::HttpParseError::CanNotParseValue {
+                match Self::__from_case_str(__s.as_str()) {
+                    Some(__case) => Ok(__case),
+                    None => Err(my_http_utils::http_input::HttpParseError::CanNotParseValue {
                         name: __value.get_name().to_string(),
                         src: __value.get_src(),
                         value: __s,
@@ -326,10 +309,133 @@ fn generate_try_from_input(
     })
 }
 
+/// Emits the one string -> case lookup every reader goes through — serde, my-json's
+/// `JsonValueReader` and `TryFrom<HttpInputValue>` — so the three can not drift apart on what they
+/// accept. Exact spellings (value, id, aliases) are tried first, then the `case_insensitive` cases,
+/// and only then the `other` catch-all, which keeps the text as it arrived. Without a catch-all an
+/// unmatched value is `None`, and each caller reports it in its own error type.
+fn generate_from_case_str_fn(
+    cases: &[EnumJson],
+    other_case: Option<&syn::Ident>,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let mut exact_arms = Vec::with_capacity(cases.len());
+    let mut case_insensitive_checks = Vec::new();
+
+    for case in cases {
+        if case.is_other() {
+            continue;
+        }
+
+        let variant = case.src.get_name_ident();
+        let patterns = case.get_read_patterns()?;
+
+        if case.attr.case_insensitive {
+            let checks = patterns
+                .iter()
+                .map(|p| quote::quote!(__s.eq_ignore_ascii_case(#p)));
+            case_insensitive_checks.push(quote::quote! {
+                if #(#checks)||* {
+                    return Some(Self::#variant);
+                }
+            });
+        }
+
+        let patterns = patterns.iter().map(|p| quote::quote!(#p));
+        exact_arms.push(quote::quote! {
+            #(#patterns)|* => return Some(Self::#variant),
+        });
+    }
+
+    let fallback = match other_case {
+        Some(other_case) => quote::quote!(Some(Self::#other_case(::std::string::String::from(__s)))),
+        None => quote::quote!(None),
+    };
+
+    Ok(quote::quote! {
+        fn __from_case_str(__s: &str) -> Option<Self> {
+            match __s {
+                #(#exact_arms)*
+                _ => {}
+            }
+
+            #(#case_insensitive_checks)*
+
+            #fallback
+        }
+    })
+}
+
+/// Finds the `#[http_enum_case(other)]` variant, if any, and checks it can hold what it catches:
+/// exactly one, a `Variant(String)`, not the `default` (there is no text to default to), and only
+/// on a string enum — an integer enum's wire value is its id, which has nowhere to go.
+fn read_other_case<'s>(
+    ast: &syn::DeriveInput,
+    cases: &'s [EnumJson],
+    as_integer: bool,
+) -> Result<Option<&'s syn::Ident>, syn::Error> {
+    let mut result: Option<&syn::Ident> = None;
+
+    for case in cases {
+        let ident = case.src.get_name_ident();
+
+        if !case.is_other() {
+            continue;
+        }
+
+        if as_integer {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "[other] is only supported on MyHttpStringEnum",
+            ));
+        }
+
+        if result.is_some() {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "Only one enum case can be marked [other]",
+            ));
+        }
+
+        if case.attr.default {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "The [other] case can not be the [default] one",
+            ));
+        }
+
+        let carries_one_value = match &ast.data {
+            syn::Data::Enum(data) => data.variants.iter().any(|variant| {
+                &variant.ident == ident
+                    && matches!(&variant.fields, syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1)
+            }),
+            _ => false,
+        };
+
+        if !carries_one_value {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "The [other] case must carry the unknown value: `Variant(String)`",
+            ));
+        }
+
+        result = Some(ident);
+    }
+
+    Ok(result)
+}
+
 fn generate_enum_as_str(cases: &[EnumJson]) -> Result<Vec<proc_macro2::TokenStream>, syn::Error> {
     let mut result = Vec::with_capacity(cases.len());
     for case in cases {
         let case_ident = case.src.get_name_ident();
+
+        if case.is_other() {
+            result.push(quote::quote! {
+                Self::#case_ident(__value) => __value.as_str(),
+            });
+            continue;
+        }
+
         let str_value = case.get_enum_case_str_value()?;
 
         result.push(quote::quote! {
@@ -343,7 +449,12 @@ fn generate_enum_as_str(cases: &[EnumJson]) -> Result<Vec<proc_macro2::TokenStre
 fn generate_enum_cases(cases: &[EnumJson]) -> Result<Vec<proc_macro2::TokenStream>, syn::Error> {
     let mut result = Vec::with_capacity(cases.len());
     for case in cases {
-        let id = proc_macro2::Literal::isize_unsuffixed(case.get_id()?);
+        // The catch-all has no fixed value to list; the schema describes the known ones.
+        if case.is_other() {
+            continue;
+        }
+
+        let id = proc_macro2::Literal::i64_unsuffixed(case.get_id()?);
         let value = case.get_enum_case_value();
         let description = case.description();
 
@@ -0,0 +1,109 @@
+use proc_macro::TokenStream;
+use types_reader::EnumCase;
+
+use crate::enum_doc::enum_json::EnumJson;
+
+/// `#[derive(MyHttpFlagsEnum)]`: the enum names the individual bits, and the value a model field
+/// carries is `my_http_utils::http_input::HttpFlags<Enum>` — any number of them ORed together.
+/// Everything about reading and writing the set lives in `HttpFlags`; the derive only hands it the
+/// `(bit, name)` table through `HttpFlagsEnum`, plus the schema (server only).
+///
+/// Each case's `id` is its bit and must be a single one (a power of two); its `value` (the variant
+/// name when absent) is the name the set is read from.
+pub fn generate_flags(ast: &syn::DeriveInput) -> Result<TokenStream, syn::Error> {
+    let struct_name = &ast.ident;
+    let struct_name_as_str = struct_name.to_string();
+
+    let src_fields = EnumCase::read(ast)?;
+
+    let mut flag_cases = Vec::new();
+    let mut bit_arms = Vec::new();
+    let mut schema_cases = Vec::new();
+
+    for src_field in src_fields {
+        let case = EnumJson::new(src_field)?;
+        let ident = case.src.get_name_ident();
+
+        if case.is_other() || case.attr.default || case.attr.alias.is_some() {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "[other], [default] and [alias] are not supported on a flags enum",
+            ));
+        }
+
+        let bit = case.get_id()?;
+        if bit <= 0 || bit & (bit - 1) != 0 {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "The id of a flag must be a single bit: 1, 2, 4, 8, …",
+            ));
+        }
+
+        let bit = proc_macro2::Literal::i64_unsuffixed(bit);
+        let name = case.get_enum_case_str_value()?;
+        let description = case.description();
+
+        flag_cases.push(quote::quote!((#bit, #name)));
+        bit_arms.push(quote::quote!(Self::#ident => #bit,));
+        schema_cases.push(quote::quote! {
+            __es.cases.push(data_types::HttpEnumCase{
+                id: #bit,
+                value: #name,
+                description: #description
+            });
+        });
+    }
+
+    let use_documentation = crate::consts::get_use_documentation();
+
+    // Schema: the enum's case list, as `EnumType::Flags` so a renderer documents every bit rather
+    // than presenting the field as one-of. `HttpFlags<Enum>` forwards its own schema here.
+    let data_type_provider = if cfg!(feature = "server") {
+        quote::quote! {
+            impl my_http_utils::schema::data_types::DataTypeProvider for #struct_name {
+                fn get_data_type() -> my_http_utils::schema::data_types::HttpDataType {
+                    #use_documentation;
+
+                    let mut __es = data_types::HttpEnumStructure{
+                        struct_id: #struct_name_as_str,
+                        enum_type: EnumType::Flags,
+                        cases: vec![],
+                    };
+
+                    #(#schema_cases)*
+
+                    __es.into_http_data_type_object()
+                }
+            }
+        }
+    } else {
+        quote::quote!()
+    };
+
+    let result = quote::quote! {
+        impl my_http_utils::http_input::HttpFlagsEnum for #struct_name {
+            fn get_flag_cases() -> &'static [(i64, &'static str)] {
+                &[#(#flag_cases),*]
+            }
+
+            fn get_bit(&self) -> i64 {
+                match self {
+                    #(#bit_arms)*
+                }
+            }
+        }
+
+        // `Read | Write` builds the set straight from two cases.
+        impl std::ops::BitOr for #struct_name {
+            type Output = my_http_utils::http_input::HttpFlags<#struct_name>;
+
+            fn bitor(self, __other: Self) -> Self::Output {
+                my_http_utils::http_input::HttpFlags::from(self) | __other
+            }
+        }
+
+        #data_type_provider
+    };
+
+    Ok(result.into())
+}
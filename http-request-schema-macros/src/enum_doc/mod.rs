@@ -1,4 +1,6 @@
 mod enum_json;
 mod generate;
 mod generate_default;
+mod generate_flags;
 pub use generate::*;
+pub use generate_flags::*;
@@ -106,7 +106,7 @@ impl RenameAllRule {
 ///
 /// Raw token trees rather than a typed parse: a parenthesised group is a single `TokenTree`, so
 /// this swallows any value shape serde accepts without having to enumerate them.
-fn skip_param_value(meta: &syn::meta::ParseNestedMeta) -> Result<(), syn::Error> {
+pub(crate) fn skip_param_value(meta: &syn::meta::ParseNestedMeta) -> Result<(), syn::Error> {
     while !meta.input.is_empty() && !meta.input.peek(syn::Token![,]) {
         meta.input.parse::<proc_macro2::TokenTree>()?;
     }
@@ -262,7 +262,8 @@ pub fn read_field_rename(field: &syn::Field) -> Result<Option<String>, syn::Erro
 /// mangle data just as quietly.
 ///
 /// Naming (`rename`, `rename_all`) is not in this list — that is honoured, and is the whole point
-/// of this module.
+/// of this module. Neither are `flatten`, `default` and `skip_serializing_if`: those are honoured
+/// too, see [`crate::json_field_options`].
 const WIRE_SHAPING_SERDE_PARAMS: &[(&str, &str)] = &[
     ("skip", "the field would still be written, and read back, by this path"),
     (
@@ -270,8 +271,6 @@ const WIRE_SHAPING_SERDE_PARAMS: &[(&str, &str)] = &[
         "the field would still be written — a field marked never-to-be-sent would go on the wire",
     ),
     ("skip_deserializing", "the field would still be read by this path"),
-    ("skip_serializing_if", "the key would be written even when the predicate says to omit it"),
-    ("flatten", "the object would be nested, not inlined"),
     ("with", "the codec would not be called"),
     ("serialize_with", "the codec would not be called"),
     ("deserialize_with", "the codec would not be called"),
@@ -36,6 +36,12 @@ pub fn generate(ast: &syn::DeriveInput) -> (proc_macro::TokenStream, bool) {
         Err(err) => return (err.into_compile_error().into(), debug),
     };
 
+    // `#[json_strict]`: the reader rejects members it does not look up, and repeated ones.
+    let strict = match read_json_strict(ast) {
+        Ok(result) => result,
+        Err(err) => return (err.into_compile_error().into(), debug),
+    };
+
     // `JsonValueWriter` — always emitted so the client request builder can serialise this object
     // (whether it is the whole body or nested) with `my_json`, no serde.
     let json_value_writer = match crate::json_value_writer_gen::generate_object_json_value_writer(
@@ -62,10 +68,20 @@ pub fn generate(ast: &syn::DeriveInput) -> (proc_macro::TokenStream, bool) {
                 Err(err) => return (err.into_compile_error().into(), debug),
             };
 
+        let strict_json = match crate::http_object_structure::generate_strict_json(
+            generic_data.as_ref(),
+            &fields,
+            rename_all,
+        ) {
+            Ok(result) => result,
+            Err(err) => return (err.into_compile_error().into(), debug),
+        };
+
         match crate::http_object_structure::generate_data_provider(
             struct_name,
             generic_data.as_ref(),
             get_http_data_structure,
+            strict_json,
         ) {
             Ok(result) => result,
             Err(err) => return (err.into_compile_error().into(), debug),
@@ -82,6 +98,7 @@ pub fn generate(ast: &syn::DeriveInput) -> (proc_macro::TokenStream, bool) {
         generic_data.is_some(),
         &fields,
         rename_all,
+        strict,
     ) {
         Ok(result) => result,
         Err(err) => return (err.into_compile_error().into(), debug),
@@ -122,3 +139,24 @@ pub fn generate(ast: &syn::DeriveInput) -> (proc_macro::TokenStream, bool) {
 
     (result.into(), debug)
 }
+
+/// `#[json_strict]` is a bare marker; anything spelled after it is a mistake worth reporting
+/// rather than ignoring.
+fn read_json_strict(ast: &syn::DeriveInput) -> Result<bool, syn::Error> {
+    let mut result = false;
+    for attr in &ast.attrs {
+        if !attr.path().is_ident("json_strict") {
+            continue;
+        }
+
+        if !matches!(attr.meta, syn::Meta::Path(_)) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`#[json_strict]` takes no parameters",
+            ));
+        }
+
+        result = true;
+    }
+    Ok(result)
+}
@@ -40,10 +40,13 @@ pub fn generate(
             &fields,
             rename_all,
         )?;
+        let strict_json =
+            super::generate_strict_json(generic_data.as_ref(), &fields, rename_all)?;
         crate::http_object_structure::generate_data_provider(
             struct_name,
             generic_data.as_ref(),
             get_http_data_structure,
+            strict_json,
         )?
     } else {
         quote!()
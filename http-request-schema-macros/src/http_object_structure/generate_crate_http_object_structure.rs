@@ -2,6 +2,7 @@ use types_reader::StructProperty;
 
 use crate::generic_utils::GenericData;
 use crate::field_key::RenameAllRule;
+use crate::json_field_options::JsonFieldOptions;
 
 use super::struct_prop_ext::StructPropertyExt;
 
@@ -39,15 +40,73 @@ pub fn generate_get_http_data_structure(
     Ok(result)
 }
 
+/// `DataTypeProvider::STRICT_JSON` — the keys the schema documents, each with the table of its own
+/// type, and the tables of the structures flattened in. A generic structure keeps the default: its
+/// members depend on the type it is used with.
+pub fn generate_strict_json(
+    generic_data: Option<&GenericData>,
+    fields: &[StructProperty],
+    rename_all: Option<RenameAllRule>,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    if generic_data.is_some() {
+        return Ok(quote::quote!());
+    }
+
+    let mut members = Vec::with_capacity(fields.len());
+    let mut flattened = Vec::new();
+    for field in fields {
+        let options = JsonFieldOptions::read(field)?;
+
+        if options.flatten {
+            let ty = field.ty.get_token_stream();
+            flattened.push(quote::quote! {
+                <#ty as my_http_utils::schema::data_types::DataTypeProvider>::STRICT_JSON
+            });
+            continue;
+        }
+
+        let name = field.get_name(rename_all)?;
+        let value = crate::types::compile_strict_json(&field.ty);
+        members.push(quote::quote! {
+            my_http_utils::http_input::core::StrictJsonMember { name: #name, value: #value }
+        });
+    }
+
+    Ok(quote::quote! {
+        const STRICT_JSON: &'static my_http_utils::http_input::core::StrictJson =
+            &my_http_utils::http_input::core::StrictJson::Object {
+                members: &[#(#members),*],
+                flattened: &[#(#flattened),*],
+            };
+    })
+}
+
 fn render_obj_fields(
     fields: &[StructProperty],
     rename_all: Option<RenameAllRule>,
 ) -> Result<Vec<proc_macro2::TokenStream>, syn::Error> {
     let mut result = Vec::with_capacity(fields.len());
     for field in fields {
+        let options = JsonFieldOptions::read(field)?;
+
+        // A flattened structure is documented as its members, inline — as it is written.
+        if options.flatten {
+            let ty = field.ty.get_token_stream();
+            result.push(quote::quote! {
+                __hos.main.fields.extend(
+                    <#ty as my_http_utils::schema::data_types::DataTypeProvider>::get_http_data_structure().main.fields
+                );
+            });
+            continue;
+        }
+
         let name = field.get_name(rename_all)?;
-        let line =
-            crate::types::compile_http_field(name.as_str(), &field.ty, field.ty.is_option())?;
+        let line = crate::types::compile_http_field(
+            name.as_str(),
+            &field.ty,
+            field.ty.is_option() || options.default.is_some(),
+            options.format.as_ref(),
+        )?;
 
         result.push(quote::quote!(__hos.main.fields.push(#line);));
     }
@@ -4,6 +4,7 @@ pub fn generate_data_provider(
     struct_name: &syn::Ident,
     generic_data: Option<&GenericData>,
     get_http_data_structure: proc_macro2::TokenStream,
+    strict_json: proc_macro2::TokenStream,
 ) -> Result<proc_macro2::TokenStream, syn::Error> {
     let (generic, generic_ident, get_generic_type) = if let Some(generic) = generic_data {
         let generic_token_stream = generic.generic.clone();
@@ -28,6 +29,8 @@ pub fn generate_data_provider(
     let result = quote::quote! {
 
         impl #generic my_http_utils::schema::data_types::DataTypeProvider for #struct_name #generic_ident {
+            #strict_json
+
             fn get_data_type() -> my_http_utils::schema::data_types::HttpDataType {
                 Self::get_http_data_structure().into_http_data_type_object()
             }
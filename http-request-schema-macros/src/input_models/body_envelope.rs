@@ -0,0 +1,137 @@
+//! The object tree a model's `#[http_body]` fields imply once some of them carry a
+//! `path = "data.attributes.email"`: each leading segment is an envelope object, the last one the
+//! member's own key. The client writer builds the body from this tree, the schema describes it and
+//! a strict body is checked against it, so all three see the same nesting. The server's reads do
+//! not need it — they take each field by its dotted path (see `NestedBodyReader`).
+
+use super::InputField;
+use crate::property_type_ext::PropertyTypeExt;
+
+pub enum BodyMember<'s> {
+    Field {
+        key: &'s str,
+        field: &'s InputField<'s>,
+    },
+    Envelope(BodyEnvelope<'s>),
+}
+
+pub struct BodyEnvelope<'s> {
+    pub key: &'s str,
+    /// The dotted path of the envelope itself (`data.attributes`).
+    pub path: String,
+    pub members: Vec<BodyMember<'s>>,
+}
+
+impl<'s> BodyMember<'s> {
+    fn key(&self) -> &'s str {
+        match self {
+            Self::Field { key, .. } => *key,
+            Self::Envelope(envelope) => envelope.key,
+        }
+    }
+}
+
+impl<'s> BodyEnvelope<'s> {
+    /// Whether the envelope must be sent: it is as soon as one member inside it is.
+    pub fn is_required(&self) -> bool {
+        self.members.iter().any(|member| match member {
+            BodyMember::Field { field, .. } => {
+                field.property.ty.required() && !field.attr.has_default()
+            }
+            BodyMember::Envelope(envelope) => envelope.is_required(),
+        })
+    }
+}
+
+/// Groups the body fields, in declaration order: an envelope takes the place of the first field
+/// that goes through it. Fails when a key is used both as a member and as an envelope.
+pub fn group_body_fields<'s>(
+    fields: &'s [InputField<'s>],
+) -> Result<Vec<BodyMember<'s>>, syn::Error> {
+    let mut result = Vec::new();
+
+    for field in fields {
+        let path = match field.attr.get_body_path() {
+            Some(path) => path,
+            None => {
+                let key = field.get_input_field_name()?;
+                insert_member(&mut result, "", &[], key, field)?;
+                continue;
+            }
+        };
+
+        let segments: Vec<&str> = path.split('.').collect();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return field.throw_error(
+                "#[http_body] path must be dot-separated member names, e.g. \"data.attributes.email\"",
+            );
+        }
+
+        let (key, envelopes) = segments.split_last().unwrap();
+        insert_member(&mut result, "", envelopes, key, field)?;
+    }
+
+    Ok(result)
+}
+
+fn insert_member<'s>(
+    members: &mut Vec<BodyMember<'s>>,
+    parent_path: &str,
+    envelopes: &[&'s str],
+    key: &'s str,
+    field: &'s InputField<'s>,
+) -> Result<(), syn::Error> {
+    let (envelope_key, rest) = match envelopes.split_first() {
+        Some(split) => split,
+        None => {
+            if members.iter().any(|member| member.key() == key) {
+                return field.throw_error(&format!(
+                    "body member '{}' is already used as an envelope or field",
+                    join_path(parent_path, key)
+                ));
+            }
+
+            members.push(BodyMember::Field { key, field });
+            return Ok(());
+        }
+    };
+
+    let envelope_path = join_path(parent_path, envelope_key);
+
+    let position = members
+        .iter()
+        .position(|member| member.key() == *envelope_key);
+
+    let envelope = match position {
+        Some(index) => match &mut members[index] {
+            BodyMember::Envelope(envelope) => envelope,
+            BodyMember::Field { .. } => {
+                return field.throw_error(&format!(
+                    "body member '{}' is a field, so it can not be an envelope as well",
+                    envelope_path
+                ));
+            }
+        },
+        None => {
+            members.push(BodyMember::Envelope(BodyEnvelope {
+                key: envelope_key,
+                path: envelope_path.clone(),
+                members: Vec::new(),
+            }));
+            match members.last_mut() {
+                Some(BodyMember::Envelope(envelope)) => envelope,
+                _ => unreachable!("an envelope was just pushed"),
+            }
+        }
+    };
+
+    insert_member(&mut envelope.members, &envelope_path, rest, key, field)
+}
+
+fn join_path(parent_path: &str, key: &str) -> String {
+    if parent_path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent_path, key)
+    }
+}
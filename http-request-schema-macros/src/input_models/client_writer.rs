@@ -4,7 +4,9 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use types_reader::PropertyType;
 
+use super::body_envelope::{group_body_fields, BodyMember};
 use super::{http_input_props::HttpInputProperties, InputField};
+use crate::property_type_ext::PropertyTypeExt;
 
 /// Emits `impl my_http_utils::schema::client::THttpRequestBuilder for #struct_name`.
 ///
@@ -142,14 +144,15 @@ fn one_value(
     let upper = field.attr.has_to_uppercase();
     let validator = field.attr.validator();
     let print = field.attr.has_print_request_to_console();
+    let format = field.get_date_time_format()?;
 
     if !trim && !lower && !upper && validator.is_none() && !print {
-        let v = value_ref_expr(place, ty);
+        let v = value_ref_expr(place, ty, format.as_ref());
         return Ok(sink.call(name, &v));
     }
 
     // Transform chain -> owned String in `__v`.
-    let mut chain = value_base_expr(place, ty);
+    let mut chain = value_base_expr(place, ty, format.as_ref());
     if trim {
         chain = quote!(#chain.trim());
     }
@@ -197,35 +200,44 @@ fn one_value(
 /// `to_rfc3339_utc()` (not `to_rfc3339()`) for a date: these sinks are plain `&str` (a path
 /// segment, a query param, a header), so they cannot go through `my-json`'s `JsonValueWriter` the
 /// way a body field does — the spelling has to be named explicitly, and it must be the same one
-/// (`…Z`, fixed-width microseconds) that `my-json` and `rust-extensions`' serde emit.
-fn value_ref_expr(expr: TokenStream, ty: &PropertyType) -> TokenStream {
+/// (`…Z`, fixed-width microseconds) that `my-json` and `rust-extensions`' serde emit. A date with
+/// a `format = "…"` is written in that format instead.
+fn value_ref_expr(
+    expr: TokenStream,
+    ty: &PropertyType,
+    format: Option<&TokenStream>,
+) -> TokenStream {
     match ty {
         PropertyType::String => quote!(#expr.as_str()),
-        PropertyType::Struct(..) => quote!(#expr.as_str()),
+        PropertyType::Struct(..) if !ty.is_wide_integer() => quote!(#expr.as_str()),
+        PropertyType::DateTime if format.is_some() => quote!(&#format.format(&#expr)),
         PropertyType::DateTime => quote!(&#expr.to_rfc3339_utc()),
         _ => quote!(&#expr.to_string()),
     }
 }
 
 /// A `&str`/`String` base expression usable as the start of a transform chain (no leading `&`).
-fn value_base_expr(expr: TokenStream, ty: &PropertyType) -> TokenStream {
+fn value_base_expr(
+    expr: TokenStream,
+    ty: &PropertyType,
+    format: Option<&TokenStream>,
+) -> TokenStream {
     match ty {
         PropertyType::String => quote!(#expr.as_str()),
-        PropertyType::Struct(..) => quote!(#expr.as_str()),
+        PropertyType::Struct(..) if !ty.is_wide_integer() => quote!(#expr.as_str()),
+        PropertyType::DateTime if format.is_some() => quote!(#format.format(&#expr)),
         PropertyType::DateTime => quote!(#expr.to_rfc3339_utc()),
         _ => quote!(#expr.to_string()),
     }
 }
 
-/// Builds the required `get_body` method. Exactly one of body / form-data / body-raw can be
-/// present (the derive's `self_check` forbids mixing); when the model has no body it returns
-/// `HttpRequestBody::Empty`. Always emits a method (the trait has no default).
+/// Builds the required `get_body` method. Exactly one of body / form-data / body-raw /
+/// body-as-stream / body-array can be present (the derive's `self_check` forbids mixing); when the
+/// model has no body it returns `HttpRequestBody::Empty`. Always emits a method (the trait has no
+/// default).
 fn generate_get_body(props: &HttpInputProperties) -> Result<TokenStream, syn::Error> {
     if let Some(fields) = &props.body_fields {
-        let mut writes = Vec::with_capacity(fields.len());
-        for field in fields {
-            writes.push(body_object_write(field)?);
-        }
+        let writes = body_members_write(&group_body_fields(fields)?)?;
         return Ok(quote! {
             #[allow(clippy::extra_unused_type_parameters)]
             fn get_body<__TRnd: my_http_utils::schema::client::RandomStringGenerator>(self) -> Result<my_http_utils::body::HttpRequestBody, my_http_utils::schema::client::HttpRequestBuildError> {
@@ -296,6 +308,19 @@ fn generate_get_body(props: &HttpInputProperties) -> Result<TokenStream, syn::Er
             PropertyType::Struct(name, _) if name.as_str() == "RawData" => quote! {
                 my_http_utils::body::HttpRequestBody::Raw { data: self.#ident.into(), content_type: None }
             },
+            // The patch documents are verbatim bytes too, under their own RFC media types.
+            PropertyType::Struct(name, _) if name.as_str() == "JsonPatch" => quote! {
+                my_http_utils::body::HttpRequestBody::Raw {
+                    data: self.#ident.into(),
+                    content_type: Some(my_http_utils::http_input::JSON_PATCH_CONTENT_TYPE),
+                }
+            },
+            PropertyType::Struct(name, _) if name.as_str() == "JsonMergePatch" => quote! {
+                my_http_utils::body::HttpRequestBody::Raw {
+                    data: self.#ident.into(),
+                    content_type: Some(my_http_utils::http_input::JSON_MERGE_PATCH_CONTENT_TYPE),
+                }
+            },
             _ => quote! {
                 match serde_json::to_vec(&self.#ident) {
                     Ok(__bytes) => my_http_utils::body::HttpRequestBody::Raw {
@@ -314,6 +339,27 @@ fn generate_get_body(props: &HttpInputProperties) -> Result<TokenStream, syn::Er
         });
     }
 
+    // A `#[http_body_array]` body is written element by element into one buffer, each element
+    // through its own generated `JsonValueWriter` (a `JsonObjectWriter`) — the same keys the
+    // server-side reader looks up, `json_name` included.
+    if let Some(field) = &props.body_array_field {
+        let ident = field.property.get_field_name_ident();
+        return Ok(quote! {
+            #[allow(clippy::extra_unused_type_parameters)]
+            fn get_body<__TRnd: my_http_utils::schema::client::RandomStringGenerator>(self) -> Result<my_http_utils::body::HttpRequestBody, my_http_utils::schema::client::HttpRequestBuildError> {
+                let mut __json = String::from("[");
+                for (__index, __item) in self.#ident.iter().enumerate() {
+                    if __index > 0 {
+                        __json.push(',');
+                    }
+                    my_http_utils::my_json::json_writer::JsonValueWriter::write(__item, &mut __json);
+                }
+                __json.push(']');
+                Ok(my_http_utils::body::HttpRequestBody::Json(__json.into_bytes()))
+            }
+        });
+    }
+
     // A `#[http_body_as_stream]` model works in BOTH directions: the same field the server fills
     // from hyper is, on the client, filled by the caller and handed to the transport as a stream.
     // `get_body` consumes `self`, so the stream MOVES out of the model — no clone, and the
@@ -338,17 +384,88 @@ fn generate_get_body(props: &HttpInputProperties) -> Result<TokenStream, syn::Er
     })
 }
 
+/// Emits one `__obj` update per member. An envelope is an object of its own: its members are
+/// written into a fresh `JsonObjectWriter` (shadowing `__obj` in a block), which is then appended
+/// to the outer writer under the envelope's key — the same nesting the server reads by `path`.
+fn body_members_write(members: &[BodyMember]) -> Result<Vec<TokenStream>, syn::Error> {
+    let mut writes = Vec::with_capacity(members.len());
+    for member in members {
+        match member {
+            BodyMember::Field { key, field } => writes.push(body_object_write(key, field)?),
+            BodyMember::Envelope(envelope) => {
+                let key = envelope.key;
+                let inner = body_members_write(&envelope.members)?;
+                let write = quote! {
+                    let __obj = my_http_utils::my_json::json_writer::JsonObjectWriter::new();
+                    #(let __obj = #inner;)*
+                    __outer.write_ref(#key, &my_http_utils::JsonRawObject(__obj.build()))
+                };
+
+                // An envelope none of whose members are written is left out, as an absent
+                // `Option` member is, rather than written as `{}`.
+                match body_members_write_condition(&envelope.members) {
+                    Some(condition) => writes.push(quote! {
+                        {
+                            let __outer = __obj;
+                            let __written = #condition;
+                            if __written {
+                                #write
+                            } else {
+                                __outer
+                            }
+                        }
+                    }),
+                    None => writes.push(quote! {
+                        {
+                            let __outer = __obj;
+                            #write
+                        }
+                    }),
+                }
+            }
+        }
+    }
+    Ok(writes)
+}
+
+/// The condition under which at least one of `members` is written; `None` when one always is.
+fn body_members_write_condition(members: &[BodyMember]) -> Option<TokenStream> {
+    let mut conditions = Vec::with_capacity(members.len());
+    for member in members {
+        let condition = match member {
+            BodyMember::Field { field, .. } => {
+                let ident = field.property.get_field_name_ident();
+                crate::json_value_writer_gen::json_object_field_write_condition(
+                    &quote!(self.#ident),
+                    &field.property.ty,
+                )
+            }
+            BodyMember::Envelope(envelope) => body_members_write_condition(&envelope.members),
+        };
+        conditions.push(condition?);
+    }
+    Some(quote!(#(#conditions)||*))
+}
+
 /// Emits the expression that appends one `#[http_body]` field into `__obj` (a `JsonObjectWriter`)
 /// and returns the updated writer. Body fields carry no client-side directives (matching the
 /// previous serde path). Delegates to the shared object-field codegen, so a `Struct` body field
 /// (object structure / enum / custom field — all now `JsonValueWriter`) is serialised the same way
-/// whether it is a body field here or nested inside another object.
-fn body_object_write(field: &InputField) -> Result<TokenStream, syn::Error> {
-    let name = field.get_input_field_name()?;
+/// whether it is a body field here or nested inside another object. A date with a `format = "…"`
+/// goes through the same formatted-date codegen as a `#[json_format]` member.
+fn body_object_write(key: &str, field: &InputField) -> Result<TokenStream, syn::Error> {
     let ident = field.property.get_field_name_ident();
     let place = quote!(self.#ident);
+    if let Some(format) = field.get_date_time_format()? {
+        return Ok(crate::json_value_writer_gen::json_object_date_time_write(
+            key,
+            &place,
+            &field.property.ty,
+            &format,
+        ));
+    }
     Ok(crate::json_value_writer_gen::json_object_field_write(
-        name,
+        key,
         &place,
         &field.property.ty,
     ))
@@ -2,13 +2,35 @@ use proc_macro2::TokenStream;
 
 use quote::quote;
 
-use super::{http_input_props::HttpInputProperties, InputField};
+use super::body_envelope::{group_body_fields, BodyEnvelope, BodyMember};
+use super::parse_profile::ParseProfile;
+use super::{http_input_props::HttpInputProperties, HttpFieldAttribute, InputField};
 
 pub fn generate_http_input<'s>(
+    struct_name: &syn::Ident,
     input_fields: &'s HttpInputProperties<'s>,
 ) -> Result<TokenStream, syn::Error> {
     let mut doc_fields = Vec::new();
+    let mut body_described = false;
     for struct_property in input_fields.get_all() {
+        // The body members are described as a group, where the first of them is: a `path`
+        // field shows up inside the envelope objects it implies, not as a parameter of its own.
+        if let HttpFieldAttribute::HttpBody(_) = &struct_property.attr {
+            if body_described {
+                continue;
+            }
+            body_described = true;
+
+            if let Some(body_fields) = &input_fields.body_fields {
+                let profile = input_fields.parse_profile;
+                match generate_body_parameters(struct_name, body_fields, profile) {
+                    Ok(fields) => doc_fields.extend(fields),
+                    Err(e) => doc_fields.push(e.to_compile_error()),
+                }
+            }
+            continue;
+        }
+
         match generate_http_input_parameter(struct_property) {
             Ok(field) => doc_fields.push(field),
             Err(e) => doc_fields.push(e.to_compile_error()),
@@ -29,6 +51,7 @@ fn generate_http_input_parameter(input_field: &InputField) -> Result<TokenStream
         input_field.get_input_field_name()?,
         &input_field.property.ty,
         input_field.attr.has_default(),
+        input_field.get_date_time_format()?.as_ref(),
     )?;
 
     let http_input_parameter_type = crate::consts::get_http_input_parameter();
@@ -36,13 +59,138 @@ fn generate_http_input_parameter(input_field: &InputField) -> Result<TokenStream
 
     let source = input_field.attr.to_src_token_stream();
 
+    // Only a raw body IS the value of its type, so only there does the type's media type apply;
+    // an array body is always JSON.
+    let content_type = match &input_field.attr {
+        HttpFieldAttribute::HttpBodyRaw(_) => {
+            crate::types::compile_body_content_type(&input_field.property.ty)
+        }
+        HttpFieldAttribute::HttpBodyArray(_) => quote!(Some("application/json")),
+        _ => quote!(None),
+    };
+
+    let security_scheme = match &input_field.attr {
+        HttpFieldAttribute::HttpHeader(_) => {
+            crate::types::compile_security_scheme(&input_field.property.ty)
+        }
+        _ => quote!(None),
+    };
+
+    let parse_profile = input_field.get_parse_profile()?.get_token_stream();
+
     let result = quote! {
-        #http_input_parameter_type{
-            field: #field,
-            description: #description.to_string(),
-            source: #source
+        {
+            let mut __param = #http_input_parameter_type::new(#field, #description, #source);
+            __param.content_type = #content_type;
+            __param.parse_profile = #parse_profile;
+            __param.with_security_scheme(#security_scheme)
         }
     };
 
     Ok(result)
 }
+
+fn generate_body_parameters<'s>(
+    struct_name: &syn::Ident,
+    body_fields: &'s [InputField<'s>],
+    model_parse_profile: ParseProfile,
+) -> Result<Vec<TokenStream>, syn::Error> {
+    let mut result = Vec::new();
+    for member in group_body_fields(body_fields)? {
+        match member {
+            BodyMember::Field { field, .. } => result.push(generate_http_input_parameter(field)?),
+            BodyMember::Envelope(envelope) => {
+                let http_input_parameter_type = crate::consts::get_http_input_parameter();
+                let http_parameter_input_src = crate::consts::get_http_parameter_input_src();
+                let field = compile_envelope_field(struct_name, &envelope)?;
+                let description = envelope_description(&envelope);
+                // An envelope is an object, not a scalar: it shows the model's profile, which its
+                // members fall back to.
+                let parse_profile = model_parse_profile.get_token_stream();
+                result.push(quote! {
+                    {
+                        let mut __param = #http_input_parameter_type::new(
+                            #field,
+                            #description,
+                            #http_parameter_input_src::BodyModel,
+                        );
+                        __param.parse_profile = #parse_profile;
+                        __param
+                    }
+                });
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// An envelope is an object type of its own, named after the model and the envelope's path
+/// (`WebhookDataAttributes` for `data.attributes` on `Webhook`), so a schema renderer lists it
+/// like any other object.
+fn compile_envelope_field(
+    struct_name: &syn::Ident,
+    envelope: &BodyEnvelope,
+) -> Result<TokenStream, syn::Error> {
+    let mut fields = Vec::with_capacity(envelope.members.len());
+    for member in &envelope.members {
+        match member {
+            BodyMember::Field { key, field } => fields.push(crate::types::compile_http_field(
+                key,
+                &field.property.ty,
+                field.attr.has_default(),
+                field.get_date_time_format()?.as_ref(),
+            )?),
+            BodyMember::Envelope(inner) => fields.push(compile_envelope_field(struct_name, inner)?),
+        }
+    }
+
+    let mut struct_id = struct_name.to_string();
+    for segment in envelope.path.split('.') {
+        let mut chars = segment.chars();
+        if let Some(first) = chars.next() {
+            struct_id.extend(first.to_uppercase());
+            struct_id.push_str(chars.as_str());
+        }
+    }
+
+    let key = envelope.key;
+    let required = envelope.is_required();
+    let http_field_type = crate::consts::get_http_field_type();
+
+    Ok(quote! {
+        #http_field_type::new(
+            #key,
+            data_types::HttpObjectStructure {
+                main: data_types::HttpObjectFields {
+                    struct_id: #struct_id.to_string(),
+                    fields: vec![#(#fields),*],
+                },
+                generic: None,
+            }
+            .into_http_data_type_object(),
+            #required,
+        )
+    })
+}
+
+/// The envelope itself has no markup to take a description from, so it carries those of the
+/// members inside it, each prefixed with the member's path.
+fn envelope_description(envelope: &BodyEnvelope) -> String {
+    let mut result = Vec::new();
+    collect_descriptions(envelope, &mut result);
+    result.join("; ")
+}
+
+fn collect_descriptions(envelope: &BodyEnvelope, dest: &mut Vec<String>) {
+    for member in &envelope.members {
+        match member {
+            BodyMember::Field { key, field } => {
+                let description = field.get_description();
+                if !description.is_empty() {
+                    dest.push(format!("{}.{}: {}", envelope.path, key, description));
+                }
+            }
+            BodyMember::Envelope(inner) => collect_descriptions(inner, dest),
+        }
+    }
+}
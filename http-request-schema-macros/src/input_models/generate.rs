@@ -3,6 +3,8 @@ use proc_macro::TokenStream;
 use quote::quote;
 
 use super::http_input_props::HttpInputProperties;
+use super::parse_profile::ParseProfile;
+use super::security::SecurityRequirements;
 
 pub fn generate(ast: &syn::DeriveInput, debug: &mut bool) -> Result<TokenStream, syn::Error> {
     let struct_name = &ast.ident;
@@ -15,11 +17,23 @@ pub fn generate(ast: &syn::DeriveInput, debug: &mut bool) -> Result<TokenStream,
         }
     }
 
-    let input_fields = HttpInputProperties::new(&fields)?;
+    let options = read_options(ast)?;
+    let strict_body = options.strict_body;
+
+    let input_fields = HttpInputProperties::new(&fields, options.parse_profile)?;
+    let security = SecurityRequirements::read(ast)?;
+
+    if strict_body && input_fields.body_fields.is_none() {
+        return Err(syn::Error::new_spanned(
+            struct_name,
+            "#[http_input(strict_body)] checks the keys of a JSON body, so the model needs \
+             #[http_body] fields",
+        ));
+    }
 
     let http_input_param = crate::consts::get_http_input_parameter_with_ns();
 
-    let http_input = match super::docs::generate_http_input(&input_fields) {
+    let http_input = match super::docs::generate_http_input(struct_name, &input_fields) {
         Ok(result) => result,
         Err(err) => err.to_compile_error(),
     };
@@ -43,14 +57,16 @@ pub fn generate(ast: &syn::DeriveInput, debug: &mut bool) -> Result<TokenStream,
 
     // Server-side sync `parse` + `READS_BODY` — only under the `server` feature (see
     // `generate_parse_impl`); empty otherwise, keeping the client / wasm build lean.
-    let parse_impl = match generate_parse_impl(struct_name, &input_fields) {
+    let parse_impl = match generate_parse_impl(struct_name, &input_fields, strict_body, &security) {
         Ok(result) => result,
         Err(err) => err.to_compile_error(),
     };
 
-    // Schema description (`get_input_params` / `get_model_routes`) is an OpenAPI/Swagger concern
-    // — server only. Browser clients get just the request builder, so their bundles stay small.
+    // Schema description (`get_input_params` / `get_model_routes` / `get_security_requirements`)
+    // is an OpenAPI/Swagger concern — server only. Browser clients get just the request builder,
+    // so their bundles stay small.
     let schema_impl = if cfg!(feature = "server") {
+        let security_requirements = security.get_token_stream();
         quote! {
             impl #struct_name{
                 pub fn get_input_params()->Vec<#http_input_param>{
@@ -60,6 +76,12 @@ pub fn generate(ast: &syn::DeriveInput, debug: &mut bool) -> Result<TokenStream,
                 pub fn get_model_routes()->Option<Vec<&'static str>>{
                     #http_routes
                 }
+
+                /// The ways to authenticate the model's `#[http_security]` attributes accept —
+                /// empty for a model without any.
+                pub fn get_security_requirements()->Vec<my_http_utils::schema::in_parameters::HttpSecurityRequirement>{
+                    #security_requirements
+                }
             }
         }
     } else {
@@ -82,18 +104,54 @@ pub fn generate(ast: &syn::DeriveInput, debug: &mut bool) -> Result<TokenStream,
 fn generate_parse_impl(
     struct_name: &syn::Ident,
     input_fields: &HttpInputProperties,
+    strict_body: bool,
+    security: &SecurityRequirements,
 ) -> Result<proc_macro2::TokenStream, syn::Error> {
-    super::parse::generate_parse(struct_name, input_fields)
+    super::parse::generate_parse(struct_name, input_fields, strict_body, security)
 }
 
 #[cfg(not(feature = "server"))]
 fn generate_parse_impl(
     _struct_name: &syn::Ident,
     _input_fields: &HttpInputProperties,
+    _strict_body: bool,
+    _security: &SecurityRequirements,
 ) -> Result<proc_macro2::TokenStream, syn::Error> {
     Ok(quote!())
 }
 
+/// The model-level `#[http_input(..)]` options.
+#[derive(Default)]
+struct HttpInputOptions {
+    strict_body: bool,
+    parse_profile: ParseProfile,
+}
+
+fn read_options(ast: &syn::DeriveInput) -> Result<HttpInputOptions, syn::Error> {
+    let mut result = HttpInputOptions::default();
+    for attr in &ast.attrs {
+        if !attr.path().is_ident("http_input") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("strict_body") {
+                result.strict_body = true;
+                Ok(())
+            } else if meta.path.is_ident("parse_profile") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                result.parse_profile = ParseProfile::parse(&value, &value.value())?;
+                Ok(())
+            } else {
+                Err(meta.error(
+                    "unknown #[http_input] option, expected `strict_body` or `parse_profile`",
+                ))
+            }
+        })?;
+    }
+    Ok(result)
+}
+
 fn http_routes(props: &HttpInputProperties) -> Result<Vec<proc_macro2::TokenStream>, syn::Error> {
     let mut result = Vec::new();
 
@@ -11,6 +11,8 @@ pub enum HttpFieldAttribute<'s> {
     /// `#[http_body_as_stream]` — carries only `name`/`description`; every outgoing-value
     /// directive below is therefore constant for it (no default, no validator, no transforms).
     HttpBodyAsStream(HttpBodyAsStreamAttribute<'s>),
+    /// `#[http_body_array]` — `name`/`description` only, like the stream attribute.
+    HttpBodyArray(HttpBodyArrayAttribute<'s>),
     HttpPath(HttpPathAttribute<'s>),
 }
 
@@ -25,6 +27,7 @@ impl<'s> HttpFieldAttribute<'s> {
             Self::HttpFormData(a) => a.default.is_some(),
             Self::HttpBodyRaw(a) => a.default.is_some(),
             Self::HttpBodyAsStream(_) => false,
+            Self::HttpBodyArray(_) => false,
             Self::HttpPath(a) => a.default.is_some(),
         }
     }
@@ -39,12 +42,42 @@ impl<'s> HttpFieldAttribute<'s> {
             Self::HttpFormData(a) => a.default.clone(),
             Self::HttpBodyRaw(a) => a.default.clone(),
             Self::HttpBodyAsStream(_) => None,
+            Self::HttpBodyArray(_) => None,
             Self::HttpPath(a) => a.default.clone(),
         };
 
         default_attr.map(super::HttpInputDefaultValue::new)
     }
 
+    /// The `format = "…"` of a date-time field — see [`super::InputField::get_date_time_format`].
+    pub fn get_date_time_format(&self) -> Option<&'s str> {
+        match self {
+            Self::HttpHeader(a) => a.format,
+            Self::HttpQuery(a) => a.format,
+            Self::HttpBody(a) => a.format,
+            Self::HttpFormData(a) => a.format,
+            Self::HttpBodyRaw(_) => None,
+            Self::HttpBodyAsStream(_) => None,
+            Self::HttpBodyArray(_) => None,
+            Self::HttpPath(a) => a.format,
+        }
+    }
+
+    /// The field's own `parse_profile = "…"`, before the model's is taken into account — see
+    /// [`super::InputField::get_parse_profile`].
+    pub fn get_parse_profile(&self) -> Option<&'s str> {
+        match self {
+            Self::HttpHeader(a) => a.parse_profile,
+            Self::HttpQuery(a) => a.parse_profile,
+            Self::HttpBody(a) => a.parse_profile,
+            Self::HttpFormData(a) => a.parse_profile,
+            Self::HttpBodyRaw(_) => None,
+            Self::HttpBodyAsStream(_) => None,
+            Self::HttpBodyArray(_) => None,
+            Self::HttpPath(a) => a.parse_profile,
+        }
+    }
+
     pub fn to_src_token_stream(&self) -> proc_macro2::TokenStream {
         let http_parameter_input_src = crate::consts::get_http_parameter_input_src();
         match self {
@@ -57,6 +90,8 @@ impl<'s> HttpFieldAttribute<'s> {
             // Same source as a raw body from the schema's point of view: the whole body IS this
             // field. No new `HttpParameterInputSource` variant is needed.
             Self::HttpBodyAsStream(_) => quote::quote!(#http_parameter_input_src::BodyRaw),
+            // Likewise: the array is the whole body.
+            Self::HttpBodyArray(_) => quote::quote!(#http_parameter_input_src::BodyRaw),
         }
     }
 
@@ -68,6 +103,7 @@ impl<'s> HttpFieldAttribute<'s> {
             Self::HttpFormData(http_form_data) => http_form_data.description,
             Self::HttpBodyRaw(http_body_raw) => http_body_raw.description,
             Self::HttpBodyAsStream(http_body_as_stream) => http_body_as_stream.description,
+            Self::HttpBodyArray(http_body_array) => http_body_array.description,
             Self::HttpPath(http_path) => http_path.description,
         }
     }
@@ -76,14 +112,24 @@ impl<'s> HttpFieldAttribute<'s> {
         match self {
             Self::HttpHeader(http_header) => http_header.name,
             Self::HttpQuery(http_query) => http_query.name,
-            Self::HttpBody(http_body) => http_body.name,
+            Self::HttpBody(http_body) => http_body.path.or(http_body.name),
             Self::HttpFormData(http_form_data) => http_form_data.name,
             Self::HttpBodyRaw(http_body_raw) => http_body_raw.name,
             Self::HttpBodyAsStream(http_body_as_stream) => http_body_as_stream.name,
+            Self::HttpBodyArray(http_body_array) => http_body_array.name,
             Self::HttpPath(http_path) => http_path.name,
         }
     }
 
+    /// The `path = "a.b.c"` of a `#[http_body]` field that sits inside envelope objects. Its
+    /// [`Self::get_name`] is the same dotted path — the key the server reads and reports.
+    pub fn get_body_path(&self) -> Option<&'s str> {
+        match self {
+            Self::HttpBody(http_body) => http_body.path,
+            _ => None,
+        }
+    }
+
     /// Name of a validator function, applied to the outgoing value before it is sent.
     pub fn validator(&'s self) -> Option<&'s str> {
         match self {
@@ -93,6 +139,7 @@ impl<'s> HttpFieldAttribute<'s> {
             Self::HttpFormData(a) => a.validator,
             Self::HttpBodyRaw(a) => a.validator,
             Self::HttpBodyAsStream(_) => None,
+            Self::HttpBodyArray(_) => None,
             Self::HttpPath(a) => a.validator,
         }
     }
@@ -105,6 +152,7 @@ impl<'s> HttpFieldAttribute<'s> {
             Self::HttpFormData(a) => a.trim,
             Self::HttpBodyRaw(a) => a.trim,
             Self::HttpBodyAsStream(_) => false,
+            Self::HttpBodyArray(_) => false,
             Self::HttpPath(a) => a.trim,
         }
     }
@@ -117,6 +165,7 @@ impl<'s> HttpFieldAttribute<'s> {
             Self::HttpFormData(a) => a.to_lowercase,
             Self::HttpBodyRaw(a) => a.to_lowercase,
             Self::HttpBodyAsStream(_) => false,
+            Self::HttpBodyArray(_) => false,
             Self::HttpPath(a) => a.to_lowercase,
         }
     }
@@ -129,6 +178,7 @@ impl<'s> HttpFieldAttribute<'s> {
             Self::HttpFormData(a) => a.to_uppercase,
             Self::HttpBodyRaw(a) => a.to_uppercase,
             Self::HttpBodyAsStream(_) => false,
+            Self::HttpBodyArray(_) => false,
             Self::HttpPath(a) => a.to_uppercase,
         }
     }
@@ -141,6 +191,7 @@ impl<'s> HttpFieldAttribute<'s> {
             Self::HttpFormData(a) => a.print_request_to_console,
             Self::HttpBodyRaw(a) => a.print_request_to_console,
             Self::HttpBodyAsStream(_) => false,
+            Self::HttpBodyArray(_) => false,
             Self::HttpPath(a) => a.print_request_to_console,
         }
     }
@@ -182,6 +233,12 @@ impl<'s> From<HttpBodyAsStreamAttribute<'s>> for HttpFieldAttribute<'s> {
     }
 }
 
+impl<'s> From<HttpBodyArrayAttribute<'s>> for HttpFieldAttribute<'s> {
+    fn from(value: HttpBodyArrayAttribute<'s>) -> Self {
+        HttpFieldAttribute::HttpBodyArray(value)
+    }
+}
+
 impl<'s> From<HttpPathAttribute<'s>> for HttpFieldAttribute<'s> {
     fn from(value: HttpPathAttribute<'s>) -> Self {
         HttpFieldAttribute::HttpPath(value)
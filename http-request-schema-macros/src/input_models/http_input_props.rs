@@ -1,7 +1,9 @@
-use types_reader::{MacrosAttribute, StructProperty};
+use types_reader::{MacrosAttribute, PropertyType, StructProperty};
 
-use super::InputField;
+use super::parse_profile::ParseProfile;
+use super::{HttpFieldAttribute, InputField};
 use crate::attributes::*;
+use crate::property_type_ext::PropertyTypeExt;
 
 pub struct HttpInputProperties<'s> {
     pub header_fields: Option<Vec<InputField<'s>>>,
@@ -12,11 +14,21 @@ pub struct HttpInputProperties<'s> {
     /// The one `#[http_body_as_stream]` field, if any — the body is handed to the model as a
     /// chunk stream instead of being materialised.
     pub body_as_stream_field: Option<InputField<'s>>,
+    /// The one `#[http_body_array]` field, if any — the body is a JSON array of objects.
+    pub body_array_field: Option<InputField<'s>>,
+    /// The one `#[http_client_info]` field, if any. Not an input of the endpoint, so it is no
+    /// `InputField` and stays out of [`Self::get_all`].
+    pub client_info_field: Option<&'s StructProperty<'s>>,
     pub path_fields: Option<Vec<InputField<'s>>>,
+    /// The model's `#[http_input(parse_profile = "…")]`.
+    pub parse_profile: ParseProfile,
 }
 
 impl<'s> HttpInputProperties<'s> {
-    pub fn new(props: &'s [StructProperty]) -> Result<Self, syn::Error> {
+    pub fn new(
+        props: &'s [StructProperty],
+        parse_profile: ParseProfile,
+    ) -> Result<Self, syn::Error> {
         let mut body_fields = Vec::with_capacity(props.len());
         let mut query_string_fields = Vec::with_capacity(props.len());
         let mut header_fields = Vec::with_capacity(props.len());
@@ -29,43 +41,61 @@ impl<'s> HttpInputProperties<'s> {
 
         let mut body_as_stream_field = None;
 
+        let mut body_array_field = None;
+
+        let mut client_info_field = None;
+
         for struct_property in props {
             if struct_property.attrs.has_attr(IgnoreAttribute::NAME) {
                 continue;
             }
 
+            if struct_property
+                .attrs
+                .has_attr(HttpClientInfoAttribute::NAME)
+            {
+                if client_info_field.is_some() {
+                    struct_property
+                        .throw_error("#[http_client_info] can be used on only one field")?;
+                }
+
+                client_info_field = Some(struct_property);
+
+                continue;
+            }
+
             let attr: Option<HttpQueryAttribute> = struct_property.try_get_attribute()?;
 
             if let Some(attr) = attr {
-                query_string_fields.push(InputField::new(struct_property, attr));
+                query_string_fields.push(InputField::new(struct_property, attr, parse_profile));
                 continue;
             }
 
             let attr: Option<HttpPathAttribute> = struct_property.try_get_attribute()?;
 
             if let Some(attr) = attr {
-                path_fields.push(InputField::new(struct_property, attr));
+                path_fields.push(InputField::new(struct_property, attr, parse_profile));
                 continue;
             }
 
             let attr: Option<HttpHeaderAttribute> = struct_property.try_get_attribute()?;
 
             if let Some(attr) = attr {
-                header_fields.push(InputField::new(struct_property, attr));
+                header_fields.push(InputField::new(struct_property, attr, parse_profile));
                 continue;
             }
 
             let attr: Option<HttpFormDataAttribute> = struct_property.try_get_attribute()?;
 
             if let Some(attr) = attr {
-                form_data_fields.push(InputField::new(struct_property, attr));
+                form_data_fields.push(InputField::new(struct_property, attr, parse_profile));
                 continue;
             }
 
             let attr: Option<HttpBodyAttribute> = struct_property.try_get_attribute()?;
 
             if let Some(attr) = attr {
-                body_fields.push(InputField::new(struct_property, attr));
+                body_fields.push(InputField::new(struct_property, attr, parse_profile));
                 continue;
             }
 
@@ -76,7 +106,7 @@ impl<'s> HttpInputProperties<'s> {
                     struct_property.throw_error("#[http_body_raw] can be used on only one field")?;
                 }
 
-                body_raw_field = Some(InputField::new(struct_property, attr));
+                body_raw_field = Some(InputField::new(struct_property, attr, parse_profile));
 
                 continue;
             }
@@ -89,7 +119,20 @@ impl<'s> HttpInputProperties<'s> {
                         .throw_error("#[http_body_as_stream] can be used on only one field")?;
                 }
 
-                body_as_stream_field = Some(InputField::new(struct_property, attr));
+                body_as_stream_field = Some(InputField::new(struct_property, attr, parse_profile));
+
+                continue;
+            }
+
+            let attr: Option<HttpBodyArrayAttribute> = struct_property.try_get_attribute()?;
+
+            if let Some(attr) = attr {
+                if body_array_field.is_some() {
+                    struct_property
+                        .throw_error("#[http_body_array] can be used on only one field")?;
+                }
+
+                body_array_field = Some(InputField::new(struct_property, attr, parse_profile));
 
                 continue;
             }
@@ -102,7 +145,10 @@ impl<'s> HttpInputProperties<'s> {
             path_fields: into_option(path_fields),
             body_raw_field,
             body_as_stream_field,
+            body_array_field,
+            client_info_field,
             form_data_fields: into_option(form_data_fields),
+            parse_profile,
         };
 
         result.self_check()?;
@@ -111,8 +157,8 @@ impl<'s> HttpInputProperties<'s> {
     }
 
     fn self_check(&self) -> Result<(), syn::Error> {
-        // A model may use only ONE body kind: #[http_body], #[http_form_data], #[http_body_raw]
-        // or #[http_body_as_stream]. Report a single, clear "choose one" error if more than one
+        // A model may use only ONE body kind: #[http_body], #[http_form_data], #[http_body_raw],
+        // #[http_body_as_stream] or #[http_body_array]. Report a single, clear "choose one" error if more than one
         // is used. Streaming is exclusive with the rest for a hard reason: the body cannot be
         // materialised and streamed at the same time.
         let mut body_kinds: Vec<&'static str> = Vec::new();
@@ -128,12 +174,16 @@ impl<'s> HttpInputProperties<'s> {
         if self.body_as_stream_field.is_some() {
             body_kinds.push("#[http_body_as_stream]");
         }
+        if self.body_array_field.is_some() {
+            body_kinds.push("#[http_body_array]");
+        }
         if body_kinds.len() > 1 {
             // Point the error at some body field so the message is actionable.
             let span = self
                 .body_as_stream_field
                 .as_ref()
                 .map(|f| f.property.field)
+                .or_else(|| self.body_array_field.as_ref().map(|f| f.property.field))
                 .or_else(|| self.body_raw_field.as_ref().map(|f| f.property.field))
                 .or_else(|| {
                     self.form_data_fields
@@ -154,7 +204,7 @@ impl<'s> HttpInputProperties<'s> {
                 format!(
                     "a model can use only one body kind, but found {}. \
                      Choose one of: #[http_body], #[http_form_data], #[http_body_raw], \
-                     #[http_body_as_stream]",
+                     #[http_body_as_stream], #[http_body_array]",
                     body_kinds.join(" + ")
                 ),
             ));
@@ -162,6 +212,18 @@ impl<'s> HttpInputProperties<'s> {
 
         if let Some(body_fields) = &self.body_fields {
             check_duplicated(body_fields)?;
+
+            // `path` names the member by itself; a `name` next to it would say it twice.
+            for field in body_fields {
+                if let HttpFieldAttribute::HttpBody(body_attr) = &field.attr {
+                    if body_attr.path.is_some() && body_attr.name.is_some() {
+                        return field
+                            .throw_error("#[http_body] takes either `name` or `path`, not both");
+                    }
+                }
+            }
+
+            super::body_envelope::group_body_fields(body_fields)?;
         }
 
         if let Some(form_data_fields) = &self.form_data_fields {
@@ -188,6 +250,82 @@ impl<'s> HttpInputProperties<'s> {
             }
         }
 
+        // The array body is read element by element through each object's generated reader, so
+        // the element has to be an object structure — a `Vec` of strings or numbers has no such
+        // reader, and an `Option` would have no absent case: an empty body is not an empty array.
+        if let Some(body_array_field) = &self.body_array_field {
+            let is_vec_of_struct = match &body_array_field.property.ty {
+                PropertyType::VecOf(inner) => matches!(inner.as_ref(), PropertyType::Struct(..)),
+                _ => false,
+            };
+
+            if !is_vec_of_struct {
+                return body_array_field.throw_error(
+                    "#[http_body_array] field must be a Vec<T> of a MyHttpInputObjectStructure",
+                );
+            }
+        }
+
+        // Only a JSON body can say `null`, so a tri-state field anywhere else would have a state
+        // its source can never produce. The value directives work on a single `&str`, which a
+        // tri-state value does not have.
+        let groups = [
+            &self.header_fields,
+            &self.query_string_fields,
+            &self.body_fields,
+            &self.form_data_fields,
+            &self.path_fields,
+        ];
+        let singles = [
+            &self.body_raw_field,
+            &self.body_as_stream_field,
+            &self.body_array_field,
+        ];
+
+        let all_fields = groups
+            .into_iter()
+            .flatten()
+            .flatten()
+            .chain(singles.into_iter().flatten());
+
+        for field in all_fields {
+            if !field.property.ty.is_patch() {
+                continue;
+            }
+
+            let body_attr = match &field.attr {
+                HttpFieldAttribute::HttpBody(body_attr) => body_attr,
+                _ => {
+                    return field.throw_error(
+                        "Patch<T> / Option<Option<T>> is only supported on a #[http_body] field",
+                    )
+                }
+            };
+
+            if body_attr.default.is_some()
+                || body_attr.trim
+                || body_attr.to_lowercase
+                || body_attr.to_uppercase
+                || body_attr.validator.is_some()
+            {
+                return field.throw_error(
+                    "a Patch<T> / Option<Option<T>> field does not support default, trim, \
+                     to_lowercase, to_uppercase or validator",
+                );
+            }
+        }
+
+        // A request always has a client, even when nothing about it can be told — that is a
+        // `ClientInfo` with its parts `None`, not an absent field.
+        if let Some(client_info_field) = self.client_info_field {
+            if client_info_field.ty.is_option() {
+                return Err(syn::Error::new_spanned(
+                    client_info_field.field,
+                    "#[http_client_info] field can not be Option",
+                ));
+            }
+        }
+
         if let Some(path_fields) = &self.path_fields {
             for path_field in path_fields {
                 if path_field.property.ty.is_option() {
@@ -230,6 +368,10 @@ impl<'s> HttpInputProperties<'s> {
             result.push(body_as_stream_field);
         }
 
+        if let Some(body_array_field) = &self.body_array_field {
+            result.push(body_array_field);
+        }
+
         if let Some(path_fields) = &self.path_fields {
             result.extend(path_fields);
         }
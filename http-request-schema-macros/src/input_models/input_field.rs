@@ -1,18 +1,28 @@
+use proc_macro2::TokenStream;
 use types_reader::StructProperty;
 
+use super::parse_profile::ParseProfile;
 use super::HttpFieldAttribute;
+use crate::date_time_format::DateTimeFormatSource;
 
 #[derive(Clone)]
 pub struct InputField<'s> {
     pub property: &'s StructProperty<'s>,
     pub attr: HttpFieldAttribute<'s>,
+    /// The model's `#[http_input(parse_profile = "…")]`, which the field's own overrides.
+    pub model_parse_profile: ParseProfile,
 }
 
 impl<'s> InputField<'s> {
-    pub fn new<T: Into<HttpFieldAttribute<'s>>>(property: &'s StructProperty<'s>, attr: T) -> Self {
+    pub fn new<T: Into<HttpFieldAttribute<'s>>>(
+        property: &'s StructProperty<'s>,
+        attr: T,
+        model_parse_profile: ParseProfile,
+    ) -> Self {
         Self {
             property,
             attr: attr.into(),
+            model_parse_profile,
         }
     }
 
@@ -28,6 +38,36 @@ impl<'s> InputField<'s> {
         self.attr.description()
     }
 
+    /// The field's `format = "…"` as a `my_http_utils::DateTimeFormat` expression, or `None` for a
+    /// date (or anything else) without one. Only a query field may be a `Vec` of dates.
+    pub fn get_date_time_format(&self) -> Result<Option<TokenStream>, syn::Error> {
+        let format = match self.attr.get_date_time_format() {
+            Some(format) => format,
+            None => return Ok(None),
+        };
+
+        let source = match &self.attr {
+            HttpFieldAttribute::HttpQuery(_) => DateTimeFormatSource::Query,
+            _ => DateTimeFormatSource::Value,
+        };
+
+        crate::date_time_format::compile_date_time_format(
+            self.property.field,
+            format,
+            &self.property.ty,
+            source,
+        )
+        .map(Some)
+    }
+
+    /// The profile the field's value is read under: its own `parse_profile`, else the model's.
+    pub fn get_parse_profile(&self) -> Result<ParseProfile, syn::Error> {
+        match self.attr.get_parse_profile() {
+            Some(value) => ParseProfile::parse(self.property.field, value),
+            None => Ok(self.model_parse_profile),
+        }
+    }
+
     pub fn throw_error<TResult>(&self, message: &str) -> Result<TResult, syn::Error> {
         let err = syn::Error::new_spanned(self.property.field, message);
         Err(err)
@@ -1,8 +1,11 @@
+mod body_envelope;
 mod client_writer;
 pub mod docs;
 mod generate;
 pub mod http_input_props;
 mod input_field;
+pub mod parse_profile;
+mod security;
 pub use generate::generate;
 pub use input_field::*;
 mod http_field_attr;
@@ -9,17 +9,33 @@ use quote::quote;
 use syn::Ident;
 use types_reader::PropertyType;
 
+use super::body_envelope::{group_body_fields, BodyMember};
 use super::http_input_props::HttpInputProperties;
+use super::parse_profile::ParseProfile;
+use super::security::SecurityRequirements;
 use super::InputField;
+use crate::property_type_ext::PropertyTypeExt;
 
 pub fn generate_parse(
     name: &Ident,
     props: &HttpInputProperties,
+    strict_body: bool,
+    security: &SecurityRequirements,
 ) -> Result<TokenStream, syn::Error> {
     let mut fields_to_return = Vec::new();
     let mut reads = Vec::new();
     let mut validations = Vec::new();
 
+    // ---- `#[http_security]`: a request without the credentials fails before any field is read ----
+    if !security.is_empty() {
+        reads.push(quote! {
+            my_http_utils::http_input::core::check_security(
+                request,
+                &Self::get_security_requirements(),
+            )?;
+        });
+    }
+
     // ---- path (always required; Option is rejected by self_check) ----
     if let Some(path_fields) = &props.path_fields {
         for field in path_fields {
@@ -63,7 +79,8 @@ pub fn generate_parse(
     // STREAMS_BODY = true instead.
     let reads_body = props.body_fields.is_some()
         || props.form_data_fields.is_some()
-        || props.body_raw_field.is_some();
+        || props.body_raw_field.is_some()
+        || props.body_array_field.is_some();
 
     let streams_body = props.body_as_stream_field.is_some();
 
@@ -89,6 +106,21 @@ pub fn generate_parse(
         });
     }
 
+    // `#[http_input(strict_body)]`: the keys are checked against a table built here, from the
+    // same envelope tree the client writes and the schema describes — nothing is built per request.
+    if strict_body {
+        let body_fields = props.body_fields.as_deref().unwrap_or_default();
+        let members = strict_json_members(&group_body_fields(body_fields)?);
+        reads.push(quote! {
+            const __STRICT_BODY: &my_http_utils::http_input::core::StrictJson =
+                &my_http_utils::http_input::core::StrictJson::Object {
+                    members: &[#(#members),*],
+                    flattened: &[],
+                };
+            __body.check_strict(__STRICT_BODY)?;
+        });
+    }
+
     if let Some(body_fields) = &props.body_fields {
         for field in body_fields {
             fields_to_return.push(field.read_value_with_transformation()?);
@@ -115,23 +147,40 @@ pub fn generate_parse(
         fields_to_return.push(read_body_raw(raw_field)?);
     }
 
+    // An array body is read straight off the request bytes, one element at a time — no
+    // `BodyReader` (its eager parse only knows a top-level object or form).
+    if let Some(array_field) = &props.body_array_field {
+        let ident = array_field.property.get_field_name_ident();
+        fields_to_return.push(quote! {
+            #ident: my_http_utils::http_input::core::read_json_array_body(request)?
+        });
+    }
+
     // The stream is created and already being filled by the transport BEFORE `parse` runs, so
     // `parse` only moves the ready `HttpBodyAsStream` into the field — inline into the struct
     // literal, like `read_body_raw`. No `BodyReader` is built for such a model.
     if let Some(stream_field) = &props.body_as_stream_field {
         let ident = stream_field.property.get_field_name_ident();
         fields_to_return.push(quote! {
-            #ident: request.take_body_stream().ok_or_else(||
-                my_http_utils::http_input::HttpParseError::BodyStream(
-                    "Body stream is not available".to_string()))?
+            #ident: request.take_body_stream().ok_or_else(
+                my_http_utils::http_input::HttpParseError::body_stream_not_available)?
+        });
+    }
+
+    // Not a source of the request as such: the client is read out of the peer address and the
+    // forwarding headers, behind the registered trusted proxies.
+    if let Some(client_info_field) = props.client_info_field {
+        let ident = client_info_field.get_field_name_ident();
+        fields_to_return.push(quote! {
+            #ident: my_http_utils::http_input::core::read_client_info(request)
         });
     }
 
     Ok(quote! {
         impl #name {
             /// `true` when this model reads the request body (`http_body` / `http_body_raw` /
-            /// `http_form_data`). The server uses it to avoid reading the body when it is not
-            /// needed before calling [`Self::parse`].
+            /// `http_body_array` / `http_form_data`). The server uses it to avoid reading the body
+            /// when it is not needed before calling [`Self::parse`].
             pub const READS_BODY: bool = #reads_body;
 
             /// `true` when this model takes the body as a stream (`#[http_body_as_stream]`).
@@ -153,23 +202,66 @@ pub fn generate_parse(
     })
 }
 
+/// `value` (an `HttpInputValue`) converted into the field's type: `try_into`, or — for a date with
+/// a `format = "…"` — read in that format.
+fn convert(field: &InputField, value: TokenStream) -> Result<TokenStream, syn::Error> {
+    Ok(match field.get_date_time_format()? {
+        Some(format) => quote!(#value.as_formatted_date_time(#format)?),
+        None => quote!(#value.try_into()?),
+    })
+}
+
+/// An optional read (`Option<HttpInputValue>`) under the field's parse profile. A `Standard` field
+/// reads as it always has.
+fn optional(field: &InputField, read: TokenStream) -> Result<TokenStream, syn::Error> {
+    Ok(match field.get_parse_profile()? {
+        ParseProfile::Standard => read,
+        profile => {
+            let profile = profile.get_token_stream();
+            quote!(#profile.apply(#read))
+        }
+    })
+}
+
+/// A required read (`Result<HttpInputValue, HttpParseError>`, unwrapped with `?`) under the
+/// field's parse profile.
+fn required(field: &InputField, read: TokenStream) -> Result<TokenStream, syn::Error> {
+    Ok(match field.get_parse_profile()? {
+        ParseProfile::Standard => quote!(#read?),
+        profile => {
+            let profile = profile.get_token_stream();
+            quote!(#profile.apply_required(#read)?)
+        }
+    })
+}
+
 fn read_path(field: &InputField) -> Result<TokenStream, syn::Error> {
     let name = field.get_input_field_name()?;
     let let_param = field.get_let_input_param();
+    let read = required(
+        field,
+        quote!(my_http_utils::http_input::core::read_path_value(request, #name)),
+    )?;
+    let value = convert(field, read)?;
     Ok(quote! {
-        let #let_param = my_http_utils::http_input::core::read_path_value(request, #name)?.try_into()?;
+        let #let_param = #value;
     })
 }
 
 fn read_header(field: &InputField) -> Result<TokenStream, syn::Error> {
     let name = field.get_input_field_name()?;
     let let_param = field.get_let_input_param();
+    let value = convert(field, quote!(value))?;
+    let read_optional = optional(
+        field,
+        quote!(my_http_utils::http_input::core::read_header_optional(request, #name)),
+    )?;
 
     if field.property.ty.is_option() {
         let default_value = field.get_default_value_opt_case()?;
         return Ok(quote! {
-            let #let_param = if let Some(value) = my_http_utils::http_input::core::read_header_optional(request, #name) {
-                Some(value.try_into()?)
+            let #let_param = if let Some(value) = #read_optional {
+                Some(#value)
             } else {
                 #default_value
             };
@@ -177,15 +269,20 @@ fn read_header(field: &InputField) -> Result<TokenStream, syn::Error> {
     }
 
     if !field.has_default_value() {
+        let read = required(
+            field,
+            quote!(my_http_utils::http_input::core::read_header_required(request, #name)),
+        )?;
+        let required = convert(field, read)?;
         return Ok(quote! {
-            let #let_param = my_http_utils::http_input::core::read_header_required(request, #name)?.try_into()?;
+            let #let_param = #required;
         });
     }
 
     let default_value = field.get_default_value_non_opt_case()?;
     Ok(quote! {
-        let #let_param = if let Some(value) = my_http_utils::http_input::core::read_header_optional(request, #name) {
-            value.try_into()?
+        let #let_param = if let Some(value) = #read_optional {
+            #value
         } else {
             #default_value
         };
@@ -194,6 +291,8 @@ fn read_header(field: &InputField) -> Result<TokenStream, syn::Error> {
 
 fn read_query(field: &InputField) -> Result<TokenStream, syn::Error> {
     let name = field.get_input_field_name()?;
+    let value = convert(field, quote!(value))?;
+    let read_optional = optional(field, quote!(__query.get_optional(#name)))?;
 
     match &field.property.ty {
         PropertyType::OptionOf(sub_ty) => {
@@ -201,8 +300,8 @@ fn read_query(field: &InputField) -> Result<TokenStream, syn::Error> {
             let default_value = field.get_default_value_opt_case()?;
             let let_param = field.get_let_input_param();
             Ok(quote! {
-                let #let_param = if let Some(value) = __query.get_optional(#name) {
-                    Some(value.try_into()?)
+                let #let_param = if let Some(value) = #read_optional {
+                    Some(#value)
                 } else {
                     #default_value
                 };
@@ -210,12 +309,26 @@ fn read_query(field: &InputField) -> Result<TokenStream, syn::Error> {
         }
         PropertyType::VecOf(_) => {
             let ident = field.property.get_field_name_ident();
+            // A lenient blank element is skipped, like a blank single value is absent.
+            let apply_profile = match field.get_parse_profile()? {
+                ParseProfile::Standard => quote!(),
+                profile => {
+                    let profile = profile.get_token_stream();
+                    quote! {
+                        let value = match #profile.apply(Some(value)) {
+                            Some(value) => value,
+                            None => continue,
+                        };
+                    }
+                }
+            };
             Ok(quote! {
                 let #ident = {
                     let items = __query.get_vec(#name)?;
                     let mut result = Vec::with_capacity(items.len());
                     for value in items {
-                        result.push(value.try_into()?);
+                        #apply_profile
+                        result.push(#value);
                     }
                     result
                 };
@@ -228,8 +341,8 @@ fn read_query(field: &InputField) -> Result<TokenStream, syn::Error> {
                 let default_value = field.get_default_value_non_opt_case()?;
                 let let_param = field.get_let_input_param();
                 return Ok(quote! {
-                    let #let_param = match __query.get_optional(#name) {
-                        Some(value) => value.try_into()?,
+                    let #let_param = match #read_optional {
+                        Some(value) => #value,
                         None => #default_value,
                     };
                 });
@@ -241,6 +354,37 @@ fn read_query(field: &InputField) -> Result<TokenStream, syn::Error> {
 
 fn read_body(field: &InputField) -> Result<TokenStream, syn::Error> {
     let name = field.get_input_field_name()?;
+    let value = convert(field, quote!(value))?;
+
+    // A `path` field is named by its dotted path; the nested view walks the envelopes in it.
+    let data_src = if field.attr.get_body_path().is_some() {
+        quote!(__body.nested())
+    } else {
+        quote!(__body)
+    };
+    let read_optional = optional(field, quote!(#data_src.get_optional(#name)))?;
+
+    // Tri-state: absent and `null` are two different values, so the read cannot go through
+    // `get_optional`, which reports a `null` member as absent.
+    if field.property.ty.is_patch() {
+        let ident = field.property.get_field_name_ident();
+        let ty = field.property.ty.get_token_stream();
+        let into_field = if field.property.ty.is_option() {
+            quote!(.into_option())
+        } else {
+            quote!()
+        };
+        let read = match field.get_parse_profile()? {
+            ParseProfile::Standard => quote!(#data_src.get_patch(#name)),
+            profile => {
+                let profile = profile.get_token_stream();
+                quote!(#profile.apply_patch(#data_src.get_patch(#name)))
+            }
+        };
+        return Ok(quote! {
+            let #ident: #ty = #read.try_map(|__v| __v.try_into())? #into_field;
+        });
+    }
 
     match &field.property.ty {
         PropertyType::OptionOf(sub_ty) => {
@@ -248,27 +392,27 @@ fn read_body(field: &InputField) -> Result<TokenStream, syn::Error> {
             let default_value = field.get_default_value_opt_case()?;
             let let_param = field.get_let_input_param();
             Ok(quote! {
-                let #let_param = if let Some(value) = __body.get_optional(#name) {
-                    Some(value.try_into()?)
+                let #let_param = if let Some(value) = #read_optional {
+                    Some(#value)
                 } else {
                     #default_value
                 };
             })
         }
-        PropertyType::Struct(..) => read_struct_with_optional_default(field, quote!(__body)),
+        PropertyType::Struct(..) => read_struct_with_optional_default(field, data_src),
         _ => {
             verify_default_value(field, &field.property.ty)?;
             if field.has_default_value() {
                 let default_value = field.get_default_value_non_opt_case()?;
                 let let_param = field.get_let_input_param();
                 return Ok(quote! {
-                    let #let_param = match __body.get_optional(#name) {
-                        Some(value) => value.try_into()?,
+                    let #let_param = match #read_optional {
+                        Some(value) => #value,
                         None => #default_value,
                     };
                 });
             }
-            read_required(field, quote!(__body))
+            read_required(field, data_src)
         }
     }
 }
@@ -289,8 +433,9 @@ fn read_body_raw(field: &InputField) -> Result<TokenStream, syn::Error> {
         // the raw `Vec<u8>`; the field type builds itself from those bytes via the crate-local
         // `FromRawBody` — `Vec<u8>` = the bytes as-is, `RawData` / `RawDataTyped` = verbatim (the
         // JSON error, if any, is deferred to `RawDataTyped::deserialize_json`), `String` = a utf-8
-        // check. `FromRawBody` (not `TryFrom<Vec<u8>>`) keeps std's `From` free for the client-side
-        // `From<T>` on `RawDataTyped<T>`. Byte source, so a raw body is never mis-routed via JSON.
+        // check, `JsonPatch` / `JsonMergePatch` = a well-formed patch. `FromRawBody` (not
+        // `TryFrom<Vec<u8>>`) keeps std's `From` free for the client-side `From<T>` on
+        // `RawDataTyped<T>`. Byte source, so a raw body is never mis-routed via JSON.
         Ok(quote!(#ident: my_http_utils::http_input::core::FromRawBody::from_raw_body(
             my_http_utils::http_input::core::read_raw_body(request)
         )?))
@@ -313,8 +458,9 @@ fn read_struct_with_optional_default(
         }
         let default_value = field.get_default_value_opt_case()?;
         let let_param = field.get_let_input_param();
+        let read_optional = optional(field, quote!(#data_src.get_optional(#name)))?;
         return Ok(quote! {
-            let #let_param = match #data_src.get_optional(#name) {
+            let #let_param = match #read_optional {
                 Some(value) => value.try_into()?,
                 None => #default_value,
             };
@@ -328,8 +474,10 @@ fn read_required(field: &InputField, data_src: TokenStream) -> Result<TokenStrea
     let name = field.get_input_field_name()?;
     let ident = field.property.get_field_name_ident();
     let ty = field.property.ty.get_token_stream();
+    let read = required(field, quote!(#data_src.get_required(#name)))?;
+    let value = convert(field, read)?;
     Ok(quote! {
-        let #ident: #ty = #data_src.get_required(#name)?.try_into()?;
+        let #ident: #ty = #value;
     })
 }
 
@@ -358,3 +506,30 @@ fn verify_default_value(field: &InputField, ty: &PropertyType) -> Result<(), syn
         }
     }
 }
+
+/// One `StrictJsonMember` per member of the body tree: a field carries the table of its type, an
+/// envelope a table of its own members.
+fn strict_json_members(members: &[BodyMember]) -> Vec<TokenStream> {
+    members
+        .iter()
+        .map(|member| match member {
+            BodyMember::Field { key, field } => {
+                let value = crate::types::compile_strict_json(&field.property.ty);
+                quote!(my_http_utils::http_input::core::StrictJsonMember { name: #key, value: #value })
+            }
+            BodyMember::Envelope(envelope) => {
+                let key = envelope.key;
+                let members = strict_json_members(&envelope.members);
+                quote! {
+                    my_http_utils::http_input::core::StrictJsonMember {
+                        name: #key,
+                        value: &my_http_utils::http_input::core::StrictJson::Object {
+                            members: &[#(#members),*],
+                            flattened: &[],
+                        },
+                    }
+                }
+            }
+        })
+        .collect()
+}
@@ -169,7 +169,8 @@ impl<'s> InputField<'s> {
                 }
                 PropertyType::DateTime => {
                     let value = default_value.get_value().unwrap_as_string()?.as_str();
-                    return Ok(quote!(Some(DateTimeAsMicroseconds::from_str(#value).unwrap())));
+                    let value = self.date_time_default(value)?;
+                    return Ok(quote!(Some(#value)));
                 }
                 PropertyType::RefTo { ty, .. } => {
                     if ty.as_str().as_str() == "str" {
@@ -255,7 +256,7 @@ impl<'s> InputField<'s> {
             }
             PropertyType::DateTime => {
                 let value = default_value.get_value().unwrap_as_string()?.as_str();
-                Ok(quote!(DateTimeAsMicroseconds::from_str(#value).unwrap()))
+                self.date_time_default(value)
             }
             PropertyType::Struct(..) => {
                 let name = self.property.ty.get_token_stream();
@@ -276,6 +277,25 @@ impl<'s> InputField<'s> {
         }
     }
 
+    /// A date's `default = "…"`, spelled in the field's `format` when it has one — the same spelling
+    /// the request would carry. A default the format does not read is a compile error, so the
+    /// `parse` below can not fail.
+    fn date_time_default(&self, value: &str) -> Result<TokenStream, syn::Error> {
+        Ok(match self.get_date_time_format()? {
+            Some(format) => {
+                if let Some(spelling) = self.attr.get_date_time_format() {
+                    crate::date_time_format::check_date_time_default(
+                        self.property.field,
+                        spelling,
+                        value,
+                    )?;
+                }
+                quote!(#format.parse(#value).unwrap())
+            }
+            None => quote!(DateTimeAsMicroseconds::from_str(#value).unwrap()),
+        })
+    }
+
     /// The validator call, if a `validator = "fn"` was given.
     ///
     /// Contract: `fn(&str) -> Result<(), impl ToString>` — the SAME contract the client request
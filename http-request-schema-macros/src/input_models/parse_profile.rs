@@ -0,0 +1,38 @@
+//! `parse_profile = "…"` — on the model (`#[http_input(parse_profile = "…")]`) or on one field,
+//! which wins. Mirrors `my_http_utils::http_input::ParseProfile`.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseProfile {
+    #[default]
+    Standard,
+    Lenient,
+    Strict,
+}
+
+impl ParseProfile {
+    pub fn parse(spanned: &impl ToTokens, value: &str) -> Result<Self, syn::Error> {
+        match value {
+            "standard" => Ok(Self::Standard),
+            "lenient" => Ok(Self::Lenient),
+            "strict" => Ok(Self::Strict),
+            _ => Err(syn::Error::new_spanned(
+                spanned,
+                format!(
+                    "unknown parse profile '{}', expected standard, lenient or strict",
+                    value
+                ),
+            )),
+        }
+    }
+
+    pub fn get_token_stream(&self) -> TokenStream {
+        match self {
+            Self::Standard => quote!(my_http_utils::http_input::ParseProfile::Standard),
+            Self::Lenient => quote!(my_http_utils::http_input::ParseProfile::Lenient),
+            Self::Strict => quote!(my_http_utils::http_input::ParseProfile::Strict),
+        }
+    }
+}
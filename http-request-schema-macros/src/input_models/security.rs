@@ -0,0 +1,101 @@
+//! `#[http_security(…)]` on a model: one attribute per way to authenticate, every option inside it
+//! needed. Mirrors `my_http_utils::schema::in_parameters::HttpSecurityRequirement`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+enum SecurityScheme {
+    Basic,
+    Bearer,
+    ApiKeyHeader(syn::LitStr),
+    ApiKeyQuery(syn::LitStr),
+}
+
+pub struct SecurityRequirements(Vec<Vec<SecurityScheme>>);
+
+impl SecurityRequirements {
+    pub fn read(ast: &syn::DeriveInput) -> Result<Self, syn::Error> {
+        let mut result = Vec::new();
+        for attr in &ast.attrs {
+            if !attr.path().is_ident("http_security") {
+                continue;
+            }
+
+            let mut schemes = Vec::new();
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("basic") {
+                    schemes.push(SecurityScheme::Basic);
+                } else if meta.path.is_ident("bearer") {
+                    schemes.push(SecurityScheme::Bearer);
+                } else if meta.path.is_ident("api_key_header") {
+                    schemes.push(SecurityScheme::ApiKeyHeader(read_name(&meta)?));
+                } else if meta.path.is_ident("api_key_query") {
+                    schemes.push(SecurityScheme::ApiKeyQuery(read_name(&meta)?));
+                } else {
+                    return Err(meta.error(
+                        "unknown #[http_security] option, expected `basic`, `bearer`, \
+                         `api_key_header = \"…\"` or `api_key_query = \"…\"`",
+                    ));
+                }
+                Ok(())
+            })?;
+
+            if schemes.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "#[http_security] needs at least one scheme",
+                ));
+            }
+
+            result.push(schemes);
+        }
+
+        Ok(Self(result))
+    }
+
+    #[cfg(feature = "server")]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The `Vec<HttpSecurityRequirement>` expression.
+    pub fn get_token_stream(&self) -> TokenStream {
+        let requirements = self.0.iter().map(|schemes| {
+            let schemes = schemes.iter().map(SecurityScheme::get_token_stream);
+            quote! {
+                my_http_utils::schema::in_parameters::HttpSecurityRequirement::new(vec![#(#schemes),*])
+            }
+        });
+
+        quote!(vec![#(#requirements),*])
+    }
+}
+
+impl SecurityScheme {
+    fn get_token_stream(&self) -> TokenStream {
+        let scheme = quote!(my_http_utils::schema::in_parameters::HttpSecurityScheme);
+        let location = quote!(my_http_utils::schema::in_parameters::HttpApiKeyLocation);
+        match self {
+            Self::Basic => quote!(#scheme::Basic),
+            Self::Bearer => quote!(#scheme::Bearer),
+            Self::ApiKeyHeader(name) => quote! {
+                #scheme::ApiKey { location: #location::Header, name: #name }
+            },
+            Self::ApiKeyQuery(name) => quote! {
+                #scheme::ApiKey { location: #location::Query, name: #name }
+            },
+        }
+    }
+}
+
+fn read_name(meta: &syn::meta::ParseNestedMeta) -> Result<syn::LitStr, syn::Error> {
+    let name: syn::LitStr = meta.value()?.parse()?;
+    if name.value().trim().is_empty() {
+        return Err(syn::Error::new_spanned(
+            &name,
+            "the name of an API key can not be empty",
+        ));
+    }
+
+    Ok(name)
+}
@@ -0,0 +1,247 @@
+//! The per-field options that shape an object structure's JSON beyond naming its keys:
+//! `#[json_flatten]`, `#[json_default]` / `#[json_default = "fn"]` and `#[json_skip_if = "fn"]`,
+//! plus their serde spellings — `#[serde(flatten)]`, `#[serde(default)]` /
+//! `#[serde(default = "fn")]` and `#[serde(skip_serializing_if = "fn")]` — and
+//! `#[json_format = "…"]` on a date, which serde has no spelling for.
+//!
+//! Read once per field, like the key in [`crate::field_key`], and honoured by all three halves
+//! generated from it: the writer, the reader and the schema. The serde spelling is honoured for the
+//! same reason `#[serde(rename)]` is — a model that also travels through serde inside a
+//! `RawDataTyped<T>` should not have to say it twice — and a native and a serde spelling that
+//! disagree are a hard error for the same reason too.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use types_reader::{PropertyType, StructProperty};
+
+use crate::date_time_format::DateTimeFormatSource;
+use crate::property_type_ext::PropertyTypeExt;
+
+/// What a missing member is read as.
+#[derive(Clone)]
+pub enum JsonDefault {
+    /// `#[json_default]` — `Default::default()`.
+    Default,
+    /// `#[json_default = "fn"]` — the named function, called with no arguments.
+    Fn(syn::Path),
+}
+
+#[derive(Default)]
+pub struct JsonFieldOptions {
+    pub flatten: bool,
+    pub default: Option<JsonDefault>,
+    pub skip_if: Option<syn::Path>,
+    /// `#[json_format = "…"]`, as a `my_http_utils::DateTimeFormat` expression.
+    pub format: Option<TokenStream>,
+}
+
+impl JsonFieldOptions {
+    pub fn read(property: &StructProperty) -> Result<Self, syn::Error> {
+        let field = property.field;
+
+        let mut native = Self::default();
+        let mut serde = Self::default();
+
+        for attr in &field.attrs {
+            if attr.path().is_ident("json_flatten") {
+                require_bare(attr, "`#[json_flatten]` takes no value")?;
+                native.flatten = true;
+            } else if attr.path().is_ident("json_default") {
+                native.default = Some(match &attr.meta {
+                    syn::Meta::Path(_) => JsonDefault::Default,
+                    syn::Meta::NameValue(name_value) => {
+                        JsonDefault::Fn(parse_path_literal(&name_value.value)?)
+                    }
+                    syn::Meta::List(_) => {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "use `#[json_default]` or `#[json_default = \"fn\"]`",
+                        ))
+                    }
+                });
+            } else if attr.path().is_ident("json_skip_if") {
+                match &attr.meta {
+                    syn::Meta::NameValue(name_value) => {
+                        native.skip_if = Some(parse_path_literal(&name_value.value)?)
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "use `#[json_skip_if = \"fn\"]`, with `fn(&T) -> bool`",
+                        ))
+                    }
+                }
+            } else if attr.path().is_ident("json_format") {
+                let format = match &attr.meta {
+                    syn::Meta::NameValue(syn::MetaNameValue {
+                        value:
+                            syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Str(literal),
+                                ..
+                            }),
+                        ..
+                    }) => literal.value(),
+                    _ => return Err(syn::Error::new_spanned(
+                        attr,
+                        "use `#[json_format = \"unix_ms\"]` (unix_s, unix_ms, unix_us, rfc3339, \
+                             date or a pattern)",
+                    )),
+                };
+                native.format = Some(crate::date_time_format::compile_date_time_format(
+                    attr,
+                    &format,
+                    &property.ty,
+                    DateTimeFormatSource::Value,
+                )?);
+            } else if attr.path().is_ident("serde") && matches!(attr.meta, syn::Meta::List(_)) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("flatten") {
+                        serde.flatten = true;
+                    } else if meta.path.is_ident("default") {
+                        serde.default = Some(if meta.input.peek(syn::Token![=]) {
+                            let literal: syn::LitStr = meta.value()?.parse()?;
+                            JsonDefault::Fn(literal.parse()?)
+                        } else {
+                            JsonDefault::Default
+                        });
+                    } else if meta.path.is_ident("skip_serializing_if") {
+                        let literal: syn::LitStr = meta.value()?.parse()?;
+                        serde.skip_if = Some(literal.parse()?);
+                    } else {
+                        crate::field_key::skip_param_value(&meta)?;
+                    }
+                    Ok(())
+                })?;
+            }
+        }
+
+        let result = Self {
+            flatten: native.flatten || serde.flatten,
+            default: merge(
+                field,
+                "default",
+                native.default,
+                serde.default,
+                same_default,
+            )?,
+            skip_if: merge(field, "skip_if", native.skip_if, serde.skip_if, |a, b| {
+                path_text(a) == path_text(b)
+            })?,
+            format: native.format,
+        };
+
+        result.check(property)?;
+        Ok(result)
+    }
+
+    fn check(&self, property: &StructProperty) -> Result<(), syn::Error> {
+        if property.ty.is_patch() && (self.default.is_some() || self.skip_if.is_some()) {
+            return Err(syn::Error::new_spanned(
+                property.field,
+                "a Patch<T> / Option<Option<T>> member already tells absent from null; it takes \
+                 no json_default / json_skip_if",
+            ));
+        }
+
+        if !self.flatten {
+            return Ok(());
+        }
+
+        if self.default.is_some() || self.skip_if.is_some() {
+            return Err(syn::Error::new_spanned(
+                property.field,
+                "a flattened member has no key of its own, so it takes no json_default / \
+                 json_skip_if — put them on the members of the flattened structure",
+            ));
+        }
+
+        let is_object =
+            matches!(property.ty, PropertyType::Struct(..)) && !property.ty.is_wide_integer();
+        if !is_object {
+            return Err(syn::Error::new_spanned(
+                property.field,
+                "#[json_flatten] needs a field whose type is an object structure \
+                 (not an Option, a Vec or a simple type)",
+            ));
+        }
+
+        let has_name = property
+            .field
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("json_name"))
+            || crate::field_key::read_field_rename(property.field)?.is_some();
+        if has_name {
+            return Err(syn::Error::new_spanned(
+                property.field,
+                "a flattened member has no key of its own, so it can not be renamed",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The expression a missing member is read as.
+    pub fn default_fn(&self) -> Option<TokenStream> {
+        match &self.default {
+            Some(JsonDefault::Default) => Some(quote!(::std::default::Default::default)),
+            Some(JsonDefault::Fn(path)) => Some(quote!(#path)),
+            None => None,
+        }
+    }
+}
+
+fn require_bare(attr: &syn::Attribute, message: &str) -> Result<(), syn::Error> {
+    if matches!(attr.meta, syn::Meta::Path(_)) {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(attr, message))
+    }
+}
+
+fn parse_path_literal(value: &syn::Expr) -> Result<syn::Path, syn::Error> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(literal),
+            ..
+        }) => literal.parse(),
+        other => Err(syn::Error::new_spanned(
+            other,
+            "expected a function path as a string, e.g. \"default_currency\"",
+        )),
+    }
+}
+
+fn merge<T>(
+    field: &syn::Field,
+    option: &str,
+    native: Option<T>,
+    serde: Option<T>,
+    same: impl Fn(&T, &T) -> bool,
+) -> Result<Option<T>, syn::Error> {
+    match (native, serde) {
+        (Some(native), Some(serde)) if !same(&native, &serde) => Err(syn::Error::new_spanned(
+            field,
+            format!(
+                "`#[json_{}]` and its `#[serde(..)]` spelling disagree on this field. serde still \
+                 reads this model if it travels inside a `RawDataTyped<T>`, so they would be two \
+                 wire formats. Keep one, or make them agree.",
+                option
+            ),
+        )),
+        (Some(native), _) => Ok(Some(native)),
+        (None, serde) => Ok(serde),
+    }
+}
+
+fn same_default(a: &JsonDefault, b: &JsonDefault) -> bool {
+    match (a, b) {
+        (JsonDefault::Default, JsonDefault::Default) => true,
+        (JsonDefault::Fn(a), JsonDefault::Fn(b)) => path_text(a) == path_text(b),
+        _ => false,
+    }
+}
+
+fn path_text(path: &syn::Path) -> String {
+    quote!(#path).to_string()
+}
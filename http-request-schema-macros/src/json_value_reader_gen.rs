@@ -10,12 +10,21 @@
 
 use proc_macro2::TokenStream;
 use quote::quote;
-use types_reader::StructProperty;
+use types_reader::{PropertyType, StructProperty};
 
 use crate::http_object_structure::struct_prop_ext::StructPropertyExt;
 use crate::field_key::RenameAllRule;
+use crate::json_field_options::JsonFieldOptions;
+use crate::property_type_ext::PropertyTypeExt;
 
-/// Emits `impl JsonValueReader<'s> for #struct_name`.
+/// Emits `impl FromJsonObject<'s>` and `impl JsonValueReader<'s>` for `#struct_name`.
+///
+/// `strict` (`#[json_strict]`) makes the reader check the object's member names first — the keys
+/// it is about to look up, each at most once — via `my_http_utils::check_json_object_keys`.
+///
+/// A `#[json_flatten]` field is read out of this same object, through its type's
+/// `from_json_members`; a `#[json_default]` field falls back to its default when the member is
+/// absent or `null`; a `#[json_format = "…"]` date is read in that format.
 ///
 /// Generic object structures emit nothing, matching
 /// [`crate::json_value_writer_gen::generate_object_json_value_writer`]: that one bails on generics,
@@ -26,32 +35,147 @@ pub fn generate_object_json_value_reader(
     is_generic: bool,
     fields: &[StructProperty],
     rename_all: Option<RenameAllRule>,
+    strict: bool,
 ) -> Result<TokenStream, syn::Error> {
     if is_generic {
         return Ok(quote!());
     }
 
     let mut reads = Vec::with_capacity(fields.len());
+    let mut keys = Vec::with_capacity(fields.len());
+    let mut flattened = Vec::new();
     for field in fields {
-        let key = field.get_name(rename_all)?;
+        let options = JsonFieldOptions::read(field)?;
         let ident = field.get_field_name_ident();
 
-        // The key is resolved exactly as the writer resolves it — one `get_name`, two halves.
+        // A flattened structure reads its members out of this object's own bytes.
+        if options.flatten {
+            let ty = field.ty.get_token_stream();
+            reads.push(quote! {
+                #ident: <#ty as my_http_utils::FromJsonObject<'s>>::from_json_members(__raw)?
+            });
+            flattened.push(ty);
+            continue;
+        }
+
+        let key = field.get_name(rename_all)?;
+        keys.push(key.clone());
+
+        if let Some(format) = &options.format {
+            let read = formatted_date_time_read(&field.ty, &key, format, options.default_fn());
+            reads.push(quote! {
+                #ident: #read
+            });
+            continue;
+        }
+
+        // The key is resolved exactly as the writer resolves it — one `get_name`, two halves. A
+        // 128-bit integer goes through the same `JsonWideInteger` carrier the writer uses.
+        let read = match (&field.ty, options.default_fn()) {
+            // A tri-state member keeps absent and `null` apart; `Option<Option<T>>` is the same
+            // read, handed back in its own spelling.
+            (PropertyType::OptionOf(_), _) if field.ty.is_patch() => quote!(
+                my_http_utils::read_json_object_patch_field(__raw, #key)?.into_option()
+            ),
+            (ty, _) if ty.is_patch() => quote!(my_http_utils::read_json_object_patch_field(__raw, #key)?),
+            // A member with a default is read as optional, and the default stands in for absent
+            // and `null` alike — the same two cases `read_json_object_field` already folds.
+            (PropertyType::OptionOf(inner), Some(default_fn)) if inner.is_wide_integer() => {
+                let inner = inner.get_token_stream();
+                quote!(my_http_utils::read_json_object_field::<Option<my_http_utils::JsonWideInteger<#inner>>>(__raw, #key)?.map(|__v| __v.0).or_else(#default_fn))
+            }
+            (ty @ PropertyType::OptionOf(_), Some(default_fn)) => {
+                let ty = ty.get_token_stream();
+                quote!(my_http_utils::read_json_object_field::<#ty>(__raw, #key)?.or_else(#default_fn))
+            }
+            (ty, Some(default_fn)) if ty.is_wide_integer() => {
+                let ty = ty.get_token_stream();
+                quote!(my_http_utils::read_json_object_field::<Option<my_http_utils::JsonWideInteger<#ty>>>(__raw, #key)?.map(|__v| __v.0).unwrap_or_else(#default_fn))
+            }
+            (ty, Some(default_fn)) => {
+                let ty = ty.get_token_stream();
+                quote!(my_http_utils::read_json_object_field::<Option<#ty>>(__raw, #key)?.unwrap_or_else(#default_fn))
+            }
+            (PropertyType::OptionOf(inner), None) if inner.is_wide_integer() => {
+                let inner = inner.get_token_stream();
+                quote!(my_http_utils::read_json_object_field::<Option<my_http_utils::JsonWideInteger<#inner>>>(__raw, #key)?.map(|__v| __v.0))
+            }
+            (ty, None) if ty.is_wide_integer() => {
+                let ty = ty.get_token_stream();
+                quote!(my_http_utils::read_json_object_field::<my_http_utils::JsonWideInteger<#ty>>(__raw, #key)?.0)
+            }
+            (_, None) => quote!(my_http_utils::read_json_object_field(__raw, #key)?),
+        };
+
         reads.push(quote! {
-            #ident: my_http_utils::read_json_object_field(__raw, #key)?
+            #ident: #read
         });
     }
 
+    // The key set is collected at run time, since a flattened structure lends its own keys.
+    let check_keys = if strict {
+        quote! {
+            let mut __names = Vec::new();
+            <Self as my_http_utils::FromJsonObject<'s>>::json_member_names(&mut __names);
+            my_http_utils::check_json_object_keys(__raw, &__names)?;
+        }
+    } else {
+        quote!()
+    };
+
+    let keys = keys.iter().map(|key| key.as_str());
+
+    // The object is read out of its own source bytes (`FromJsonObject`); the `JsonValueReader`
+    // impl only hands it those bytes. The split lets an array body feed each element's slice
+    // straight in, without a `JsonValueRef` for it.
     Ok(quote! {
+        impl<'s> my_http_utils::FromJsonObject<'s> for #struct_name {
+            fn from_json_object(
+                __raw: &'s [u8],
+            ) -> Result<Self, my_http_utils::my_json::json_reader::JsonParseError> {
+                #check_keys
+                <Self as my_http_utils::FromJsonObject<'s>>::from_json_members(__raw)
+            }
+
+            fn from_json_members(
+                __raw: &'s [u8],
+            ) -> Result<Self, my_http_utils::my_json::json_reader::JsonParseError> {
+                Ok(Self { #(#reads),* })
+            }
+
+            fn json_member_names(__names: &mut Vec<&'static str>) {
+                __names.extend_from_slice(&[#(#keys),*]);
+                #(<#flattened as my_http_utils::FromJsonObject<'s>>::json_member_names(__names);)*
+            }
+        }
+
         impl<'s> my_http_utils::my_json::json_reader::JsonValueReader<'s> for #struct_name {
             fn from_json_value(
                 __value: &my_http_utils::my_json::json_reader::JsonValueRef<'s>,
             ) -> Result<Self, my_http_utils::my_json::json_reader::JsonParseError> {
                 // The member's verbatim source slice, borrowed for the whole `'s` — that is what
                 // lets a nested object recurse into this same impl.
-                let __raw = __value.as_slice();
-                Ok(Self { #(#reads),* })
+                <Self as my_http_utils::FromJsonObject<'s>>::from_json_object(__value.as_slice())
             }
         }
     })
 }
+
+/// The read of a `#[json_format]` date, with the same absent / `null` / default rules as
+/// [`generate_object_json_value_reader`]'s other members.
+fn formatted_date_time_read(
+    ty: &PropertyType,
+    key: &str,
+    format: &TokenStream,
+    default_fn: Option<TokenStream>,
+) -> TokenStream {
+    let optional = quote!(my_http_utils::read_json_object_optional_date_time(__raw, #key, #format)?);
+    match (ty, default_fn) {
+        (PropertyType::OptionOf(_), Some(default_fn)) => quote!(#optional.or_else(#default_fn)),
+        (PropertyType::OptionOf(_), None) => optional,
+        (_, Some(default_fn)) => quote!(#optional.unwrap_or_else(#default_fn)),
+        (_, None) => {
+            quote!(my_http_utils::read_json_object_date_time(__raw, #key, #format)?)
+        }
+    }
+}
@@ -9,6 +9,8 @@ use types_reader::{PropertyType, StructProperty};
 
 use crate::http_object_structure::struct_prop_ext::StructPropertyExt;
 use crate::field_key::RenameAllRule;
+use crate::json_field_options::JsonFieldOptions;
+use crate::property_type_ext::PropertyTypeExt;
 
 /// Emits the `__obj.write_*(key, …)` expression appending one field into `__obj` (a
 /// `JsonObjectWriter`) and returning the updated writer. `place` is the field access expression
@@ -18,7 +20,11 @@ use crate::field_key::RenameAllRule;
 /// * `Option` → `write_if_some_ref` (a `None` omits the key);
 /// * `Vec` → an array (`Vec<T>` is itself a `JsonValueWriter`);
 /// * everything else (scalars, `String`, `DateTimeAsMicroseconds`, and any `Struct` that
-///   implements `JsonValueWriter` — object structures, enums, custom fields) → `write_ref`.
+///   implements `JsonValueWriter` — object structures, enums, custom fields) → `write_ref`;
+/// * `i128` / `u128` (bare or in an `Option`) → through `my_http_utils::JsonWideInteger`, which
+///   writes every digit as a JSON number;
+/// * tri-state `Patch<T>` / `Option<Option<T>>` → the key is omitted only when absent; `null` is
+///   written out explicitly, since it means "clear" and not "leave unchanged".
 ///
 /// `DateTimeAsMicroseconds` deliberately has **no** special case here. `my-json` implements
 /// `JsonValueWriter` for it (it depends on `rust-extensions`), so it goes the common path and the
@@ -27,12 +33,71 @@ use crate::field_key::RenameAllRule;
 /// `rust-extensions`' own serde emitted `…Z` — two spellings of one type on the wire.
 pub fn json_object_field_write(key: &str, place: &TokenStream, ty: &PropertyType) -> TokenStream {
     match ty {
+        PropertyType::OptionOf(inner) if ty.is_patch() => {
+            let value_ty = match inner.as_ref() {
+                PropertyType::OptionOf(value_ty) => value_ty.get_token_stream(),
+                _ => unreachable!("is_patch() checked the nesting"),
+            };
+            quote! {
+                match &#place {
+                    None => __obj,
+                    Some(None) => __obj.write_ref(#key, &my_http_utils::http_input::Patch::<#value_ty>::Null),
+                    Some(Some(__value)) => __obj.write_ref(#key, __value),
+                }
+            }
+        }
+        _ if ty.is_patch() => quote! {
+            if my_http_utils::http_input::Patch::is_absent(&#place) {
+                __obj
+            } else {
+                __obj.write_ref(#key, &#place)
+            }
+        },
+        PropertyType::OptionOf(inner) if inner.is_wide_integer() => quote!(
+            __obj.write_if_some_ref(#key, &#place.map(my_http_utils::JsonWideInteger))
+        ),
+        _ if ty.is_wide_integer() => {
+            quote!(__obj.write_ref(#key, &my_http_utils::JsonWideInteger(#place)))
+        }
         PropertyType::OptionOf(_) => quote!(__obj.write_if_some_ref(#key, &#place)),
         PropertyType::VecOf(_) => quote!(__obj.write_ref(#key, &#place)),
         _ => quote!(__obj.write_ref(#key, &#place)),
     }
 }
 
+/// The condition under which [`json_object_field_write`] (or [`json_object_date_time_write`])
+/// writes the field's key: `place` is `Some` for an `Option`, and not absent for a tri-state.
+/// `None` when the key is always written.
+pub fn json_object_field_write_condition(
+    place: &TokenStream,
+    ty: &PropertyType,
+) -> Option<TokenStream> {
+    match ty {
+        PropertyType::OptionOf(_) => Some(quote!(#place.is_some())),
+        _ if ty.is_patch() => Some(quote!(!my_http_utils::http_input::Patch::is_absent(&#place))),
+        _ => None,
+    }
+}
+
+/// [`json_object_field_write`] for a date with an explicit wire format (`format` is a
+/// `my_http_utils::DateTimeFormat` expression): the date goes through
+/// `my_http_utils::JsonFormattedDateTime`, which writes a unix format as a number.
+pub fn json_object_date_time_write(
+    key: &str,
+    place: &TokenStream,
+    ty: &PropertyType,
+    format: &TokenStream,
+) -> TokenStream {
+    match ty {
+        PropertyType::OptionOf(_) => quote!(
+            __obj.write_if_some_ref(#key, &#place.map(|__v| my_http_utils::JsonFormattedDateTime(__v, #format)))
+        ),
+        _ => quote!(
+            __obj.write_ref(#key, &my_http_utils::JsonFormattedDateTime(#place, #format))
+        ),
+    }
+}
+
 /// Emits `impl JsonValueWriter for #struct_name`, serialising the struct as a JSON object whose
 /// keys are the fields' `#[serde(rename)]`/Rust names. Emitted unconditionally (the client needs it
 /// to serialise a nested object), so it does not reference the server-gated schema layer.
@@ -44,6 +109,10 @@ pub fn json_object_field_write(key: &str, place: &TokenStream, ty: &PropertyType
 /// `rename_all` is the container's `#[serde(rename_all = "..")]`. The keys written here have to be
 /// the ones serde would look for: the server reads an object structure back out of the body with
 /// serde, so a key this writer invents on its own would simply not be found.
+///
+/// A `#[json_flatten]` field appends its own members in place of a key; a `#[json_skip_if = "f"]`
+/// field is left out whenever `f(&value)` says so; a `#[json_format = "…"]` date is written in
+/// that format.
 pub fn generate_object_json_value_writer(
     struct_name: &syn::Ident,
     is_generic: bool,
@@ -56,18 +125,51 @@ pub fn generate_object_json_value_writer(
 
     let mut writes = Vec::with_capacity(fields.len());
     for field in fields {
-        let key = field.get_name(rename_all)?;
+        let options = JsonFieldOptions::read(field)?;
         let ident = field.get_field_name_ident();
         let place = quote!(self.#ident);
-        writes.push(json_object_field_write(key.as_str(), &place, &field.ty));
+
+        if options.flatten {
+            writes.push(quote!(my_http_utils::JsonObjectMembers::write_json_members(&#place, __obj)));
+            continue;
+        }
+
+        let key = field.get_name(rename_all)?;
+        let write = match &options.format {
+            Some(format) => json_object_date_time_write(key.as_str(), &place, &field.ty, format),
+            None => json_object_field_write(key.as_str(), &place, &field.ty),
+        };
+
+        writes.push(match &options.skip_if {
+            Some(skip_if) => quote! {
+                if #skip_if(&#place) {
+                    __obj
+                } else {
+                    #write
+                }
+            },
+            None => write,
+        });
     }
 
+    // The members on their own (`JsonObjectMembers`) are what a `#[json_flatten]` parent appends;
+    // the value is those members in an object of their own.
     Ok(quote! {
+        impl my_http_utils::JsonObjectMembers for #struct_name {
+            fn write_json_members(
+                &self,
+                __obj: my_http_utils::my_json::json_writer::JsonObjectWriter,
+            ) -> my_http_utils::my_json::json_writer::JsonObjectWriter {
+                #(let __obj = #writes;)*
+                __obj
+            }
+        }
+
         impl my_http_utils::my_json::json_writer::JsonValueWriter for #struct_name {
             const IS_ARRAY: bool = false;
             fn write(&self, __dest: &mut String) {
                 let __obj = my_http_utils::my_json::json_writer::JsonObjectWriter::new();
-                #(let __obj = #writes;)*
+                let __obj = my_http_utils::JsonObjectMembers::write_json_members(self, __obj);
                 __obj.build_into(__dest);
             }
         }
@@ -6,6 +6,7 @@ use proc_macro::TokenStream;
 // "server": server-only macros (e.g. `http_route`) and request parsing live in my-http-server.
 mod attributes;
 mod consts;
+mod date_time_format;
 mod enum_doc;
 mod generic_utils;
 mod http_input_field;
@@ -16,6 +17,7 @@ mod json_value_reader_gen;
 mod json_value_writer_gen;
 mod property_type_ext;
 mod field_key;
+mod json_field_options;
 mod types;
 
 #[proc_macro_derive(
@@ -28,6 +30,10 @@ mod types;
         http_body,
         http_body_raw,
         http_body_as_stream,
+        http_body_array,
+        http_client_info,
+        http_input,
+        http_security,
         debug,
     )
 )]
@@ -58,7 +64,27 @@ pub fn my_http_input_doc_derive(input: TokenStream) -> TokenStream {
 //
 // `attributes(json_name)`: this crate's own `#[json_name("cardNumber")]`, which says the same thing
 // without making a serde-free model derive serde just to register the attribute.
-#[proc_macro_derive(MyHttpInputObjectStructure, attributes(debug, serde, json_name))]
+//
+// `attributes(json_strict)`: a container marker — the generated reader rejects unknown and
+// repeated members.
+//
+// `attributes(json_flatten, json_default, json_skip_if)`: the field options read by
+// `json_field_options`, native spellings of `#[serde(flatten / default / skip_serializing_if)]`.
+//
+// `attributes(json_format)`: the wire format of a date member, read there as well.
+#[proc_macro_derive(
+    MyHttpInputObjectStructure,
+    attributes(
+        debug,
+        serde,
+        json_name,
+        json_strict,
+        json_flatten,
+        json_default,
+        json_skip_if,
+        json_format
+    )
+)]
 pub fn my_http_input_object_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
     let (result, debug) = crate::http_input_object_structure::generate(&ast);
@@ -70,7 +96,18 @@ pub fn my_http_input_object_derive(input: TokenStream) -> TokenStream {
     result
 }
 
-#[proc_macro_derive(MyHttpObjectStructure, attributes(debug, serde, json_name))]
+#[proc_macro_derive(
+    MyHttpObjectStructure,
+    attributes(
+        debug,
+        serde,
+        json_name,
+        json_flatten,
+        json_default,
+        json_skip_if,
+        json_format
+    )
+)]
 pub fn my_http_output_object_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
     let mut debug = false;
@@ -103,6 +140,16 @@ pub fn my_http_integer_enum_derive(input: TokenStream) -> TokenStream {
     }
 }
 
+// The enum names the bits; a field is typed `HttpFlags<Enum>`, which does the reading and writing.
+#[proc_macro_derive(MyHttpFlagsEnum, attributes(http_enum_case))]
+pub fn my_http_flags_enum_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    match crate::enum_doc::generate_flags(&ast) {
+        Ok(result) => result,
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 #[proc_macro_attribute]
 pub fn http_input_field(input: TokenStream, item: TokenStream) -> TokenStream {
     match crate::http_input_field::generate(input, item) {
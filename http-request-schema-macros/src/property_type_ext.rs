@@ -3,6 +3,8 @@ use types_reader::PropertyType;
 pub trait PropertyTypeExt {
     //fn is_file_content(&self) -> bool;
     fn required(&self) -> bool;
+    fn is_wide_integer(&self) -> bool;
+    fn is_patch(&self) -> bool;
 }
 
 impl<'s> PropertyTypeExt for PropertyType<'s> {
@@ -14,6 +16,28 @@ impl<'s> PropertyTypeExt for PropertyType<'s> {
     // }
 
     fn required(&self) -> bool {
-        !matches!(self, PropertyType::VecOf(_) | PropertyType::OptionOf(_))
+        !matches!(self, PropertyType::VecOf(_) | PropertyType::OptionOf(_)) && !self.is_patch()
+    }
+
+    /// `i128` / `u128`. `my-json` has no writer / reader for them, so the JSON codegen routes them
+    /// through `my_http_utils::JsonWideInteger`; and they have no `as_str()`, so the client writer
+    /// must not take the `Struct` path for them either.
+    fn is_wide_integer(&self) -> bool {
+        matches!(self.as_str().as_str(), "i128" | "u128")
+    }
+
+    /// A tri-state body member: `Patch<T>`, or `Option<Option<T>>` spelling the same thing. Absent
+    /// and `null` are two different values for it, so the JSON codegen and the body read must not
+    /// fold them together the way they do for a plain `Option`.
+    ///
+    /// A macro sees no imports, so `Patch` is recognised by name, however its path is spelled.
+    /// The code generated for it names `my_http_utils::http_input::Patch` in full, so a type of
+    /// the caller's own called `Patch` fails to compile instead of being read as a tri-state.
+    fn is_patch(&self) -> bool {
+        match self {
+            PropertyType::OptionOf(inner) => matches!(inner.as_ref(), PropertyType::OptionOf(_)),
+            PropertyType::Struct(name, _) => name.as_str() == "Patch",
+            _ => false,
+        }
     }
 }
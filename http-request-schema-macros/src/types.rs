@@ -4,12 +4,18 @@ use types_reader::PropertyType;
 
 use crate::property_type_ext::PropertyTypeExt;
 
+/// `date_time_format` is a date's `format` (a `my_http_utils::DateTimeFormat` expression), which
+/// decides its schema type in place of `DateTimeAsMicroseconds`' own.
 pub fn compile_http_field(
     name: &str,
     pt: &PropertyType,
     has_defaults_value: bool,
+    date_time_format: Option<&TokenStream>,
 ) -> Result<TokenStream, syn::Error> {
-    let data_type = compile_data_type(pt);
+    let data_type = match date_time_format {
+        Some(format) => compile_date_time_data_type(pt, format),
+        None => compile_data_type(pt),
+    };
     let mut required = pt.required();
 
     if has_defaults_value {
@@ -18,6 +24,13 @@ pub fn compile_http_field(
 
     let http_field_type = crate::consts::get_http_field_type();
 
+    // A tri-state member may be omitted and may be `null` — both are meaningful.
+    if pt.is_patch() {
+        return Ok(quote! {
+            #http_field_type::new_nullable(#name, #data_type)
+        });
+    }
+
     let result = quote! {
         #http_field_type::new(#name, #data_type, #required)
     };
@@ -25,8 +38,48 @@ pub fn compile_http_field(
     Ok(result)
 }
 
+/// The media type of a raw body: the type's own, through the same `Option` unwrapping as the data
+/// type.
+pub fn compile_body_content_type(pt: &PropertyType) -> TokenStream {
+    let type_token = value_type_token(pt);
+    quote!(#type_token::get_body_content_type())
+}
+
+/// The security scheme of a header, when its type is a credential (`BearerToken`, …).
+pub fn compile_security_scheme(pt: &PropertyType) -> TokenStream {
+    let type_token = value_type_token(pt);
+    quote!(#type_token::get_security_scheme())
+}
+
+/// The `DataTypeProvider::STRICT_JSON` table of a member's value type, through the same `Option`
+/// and tri-state unwrapping as its data type.
+pub fn compile_strict_json(pt: &PropertyType) -> TokenStream {
+    let type_token = match pt {
+        PropertyType::OptionOf(generic_type) => match generic_type.as_ref() {
+            PropertyType::OptionOf(value_type) => value_type.get_token_stream_with_generics(),
+            _ => generic_type.get_token_stream_with_generics(),
+        },
+        _ => pt.get_token_stream_with_generics(),
+    };
+
+    quote!(<#type_token as my_http_utils::schema::data_types::DataTypeProvider>::STRICT_JSON)
+}
+
+fn value_type_token(pt: &PropertyType) -> TokenStream {
+    match pt {
+        PropertyType::OptionOf(generic_type) => generic_type.get_token_stream_with_generics(),
+        _ => pt.get_token_stream_with_generics(),
+    }
+}
+
 fn compile_data_type(pt: &PropertyType) -> TokenStream {
     if let PropertyType::OptionOf(generic_type) = pt {
+        // `Option<Option<T>>`: the type is `T`'s; the tri-state is recorded on the field.
+        if let PropertyType::OptionOf(value_type) = generic_type.as_ref() {
+            let type_token = value_type.get_token_stream_with_generics();
+            return quote!(#type_token::get_data_type());
+        }
+
         let type_token = generic_type.get_token_stream_with_generics();
 
         return quote!(#type_token::get_data_type());
@@ -36,3 +89,15 @@ fn compile_data_type(pt: &PropertyType) -> TokenStream {
 
     quote!(#type_token::get_data_type())
 }
+
+fn compile_date_time_data_type(pt: &PropertyType, format: &TokenStream) -> TokenStream {
+    let simple_type = quote!(#format.get_simple_type());
+    match pt {
+        PropertyType::VecOf(_) => quote!(
+            my_http_utils::schema::data_types::HttpDataType::ArrayOf(
+                my_http_utils::schema::data_types::ArrayElement::SimpleType(#simple_type)
+            )
+        ),
+        _ => quote!(my_http_utils::schema::data_types::HttpDataType::SimpleType(#simple_type)),
+    }
+}
@@ -0,0 +1,156 @@
+use crate::http_input::{ByteRange, ContentRange};
+
+/// A `multipart/byteranges` body — the `206 Partial Content` answer to a `Range` with several
+/// ranges (RFC 9110 §14.6): one part per range, each with its `Content-Type` and
+/// `Content-Range`.
+///
+/// Ranges too large to hold in memory are streamed instead: the body is
+/// [`get_part_header`](Self::get_part_header) and the bytes of each range followed by `\r\n`,
+/// then [`get_end_delimiter`](Self::get_end_delimiter), and
+/// [`get_content_length`](Self::get_content_length) gives its length up front.
+pub struct ByteRangesBody {
+    boundary: String,
+    content_type: String,
+    complete_length: Option<u64>,
+    buffer: Vec<u8>,
+}
+
+impl ByteRangesBody {
+    /// `content_type` is the type of the whole representation, repeated in every part;
+    /// `complete_length` is its length, `None` when it is not known.
+    pub fn new(rnd_string: &str, content_type: &str, complete_length: Option<u64>) -> Self {
+        Self {
+            boundary: format!("ByteRangesBoundary{}", rnd_string),
+            content_type: content_type.to_string(),
+            complete_length,
+            buffer: vec![],
+        }
+    }
+
+    /// `content` is the bytes of `range`, and nothing else. Fails when its length is not the
+    /// length of `range`, or when `range` runs past the complete length.
+    pub fn append_range(mut self, range: ByteRange, content: &[u8]) -> Result<Self, String> {
+        if range.get_length() != content.len() as u64 {
+            return Err(format!(
+                "Byte range {}-{} is {} bytes long, the content is {}",
+                range.get_start(),
+                range.get_end(),
+                range.get_length(),
+                content.len()
+            ));
+        }
+
+        let header = self.get_part_header(range)?;
+        self.buffer.extend_from_slice(header.as_bytes());
+        self.buffer.extend_from_slice(content);
+        self.buffer.extend_from_slice(b"\r\n");
+        Ok(self)
+    }
+
+    /// What goes before the bytes of `range`: the boundary and the headers of its part. Fails
+    /// when `range` runs past the complete length.
+    pub fn get_part_header(&self, range: ByteRange) -> Result<String, String> {
+        let content_range = ContentRange::new(range, self.complete_length)?;
+
+        Ok(format!(
+            "--{}\r\nContent-Type: {}\r\nContent-Range: {}\r\n\r\n",
+            self.boundary,
+            self.content_type,
+            content_range.as_str()
+        ))
+    }
+
+    /// What closes the body, after the last part.
+    pub fn get_end_delimiter(&self) -> String {
+        format!("--{}--", self.boundary)
+    }
+
+    /// The length of the body that serves `ranges`. Fails when one of them runs past the
+    /// complete length.
+    pub fn get_content_length(&self, ranges: &[ByteRange]) -> Result<u64, String> {
+        let mut result = self.get_end_delimiter().len() as u64;
+
+        for range in ranges {
+            let part_header = self.get_part_header(*range)?;
+            result += part_header.len() as u64 + range.get_length() + "\r\n".len() as u64;
+        }
+
+        Ok(result)
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        let mut result = self.buffer;
+
+        result.extend_from_slice(b"--");
+        result.extend_from_slice(self.boundary.as_bytes());
+        result.extend_from_slice(b"--");
+
+        result
+    }
+
+    pub fn get_content_type(&self) -> String {
+        format!("multipart/byteranges; boundary={}", self.boundary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::ByteRangesBody;
+    use crate::http_input::ByteRange;
+
+    #[test]
+    fn test_byte_ranges_body() {
+        let content = b"0123456789abcdefghij";
+        let ranges = [
+            ByteRange::new(0, 3).unwrap(),
+            ByteRange::new(16, 19).unwrap(),
+        ];
+
+        let mut body = ByteRangesBody::new("1234567890123456", "text/plain", Some(20));
+        let expected_length = body.get_content_length(&ranges).unwrap();
+        assert_eq!(
+            body.get_content_type(),
+            "multipart/byteranges; boundary=ByteRangesBoundary1234567890123456"
+        );
+
+        for range in ranges {
+            let (start, end) = (range.get_start() as usize, range.get_end() as usize);
+            body = body.append_range(range, &content[start..=end]).unwrap();
+        }
+
+        let result = body.into_bytes();
+        assert_eq!(result.len() as u64, expected_length);
+        assert_eq!(
+            std::str::from_utf8(result.as_slice()).unwrap(),
+            "--ByteRangesBoundary1234567890123456\r\n\
+             Content-Type: text/plain\r\n\
+             Content-Range: bytes 0-3/20\r\n\r\n\
+             0123\r\n\
+             --ByteRangesBoundary1234567890123456\r\n\
+             Content-Type: text/plain\r\n\
+             Content-Range: bytes 16-19/20\r\n\r\n\
+             ghij\r\n\
+             --ByteRangesBoundary1234567890123456--"
+        );
+    }
+
+    #[test]
+    fn test_byte_ranges_body_of_unknown_length() {
+        let body = ByteRangesBody::new("b", "application/octet-stream", None);
+        let header = body.get_part_header(ByteRange::new(5, 9).unwrap()).unwrap();
+
+        assert!(header.contains("Content-Range: bytes 5-9/*\r\n"));
+    }
+
+    #[test]
+    fn test_byte_ranges_body_refuses_what_does_not_fit() {
+        let range = ByteRange::new(16, 20).unwrap();
+        let body = ByteRangesBody::new("b", "text/plain", Some(20));
+
+        assert!(body.get_part_header(range).is_err());
+        assert!(body.get_content_length(&[range]).is_err());
+
+        let range = ByteRange::new(0, 3).unwrap();
+        assert!(body.append_range(range, b"01234").is_err());
+    }
+}
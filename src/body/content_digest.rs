@@ -0,0 +1,336 @@
+//! Integrity digests of a body (RFC 9530): `Content-Digest: sha-256=:…:`.
+//!
+//! A body in memory is hashed at once ([`HttpRequestBody::get_content_digest`]). A streamed one is
+//! hashed chunk by chunk as its reader hands the chunks out: the client asks
+//! [`HttpBodyAsStream::with_content_digest`] for a digest that is there once the body has been
+//! read in full, and the server has the reader of an incoming stream check the digest it was sent
+//! ([`HttpBodyAsStream::verify_content_digest`]) — the last read fails instead of ending the body.
+//!
+//! Nothing here knows about content codings, so the digest of a body sent as it is also serves as
+//! its `Repr-Digest`.
+//!
+//! [`HttpBodyAsStream::with_content_digest`]: crate::http_input::HttpBodyAsStream::with_content_digest
+//! [`HttpBodyAsStream::verify_content_digest`]: crate::http_input::HttpBodyAsStream::verify_content_digest
+
+use std::sync::{Arc, Mutex};
+
+use sha2::Digest;
+
+use crate::body::HttpRequestBody;
+use crate::crypto::{digest, Sha256, Sha512};
+use crate::http_input::{Base64Bytes, HttpParseError};
+use crate::schema::client::HeaderBuilder;
+
+pub const CONTENT_DIGEST_HEADER: &str = "Content-Digest";
+pub const REPR_DIGEST_HEADER: &str = "Repr-Digest";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DigestAlgorithm {
+    Sha256,
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    /// The key of the algorithm in a digest header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha-256",
+            Self::Sha512 => "sha-512",
+        }
+    }
+
+    pub fn compute(&self, data: &[u8]) -> Vec<u8> {
+        self.compute_parts(&[data])
+    }
+
+    fn compute_parts(&self, parts: &[&[u8]]) -> Vec<u8> {
+        match self {
+            Self::Sha256 => digest::<Sha256>(parts),
+            Self::Sha512 => digest::<Sha512>(parts),
+        }
+    }
+}
+
+impl std::str::FromStr for DigestAlgorithm {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        match src {
+            "sha-256" => Ok(Self::Sha256),
+            "sha-512" => Ok(Self::Sha512),
+            _ => Err(format!("Digest algorithm '{}' is not supported", src)),
+        }
+    }
+}
+
+/// One member of a digest header: the algorithm and the digest it gives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentDigest {
+    pub algorithm: DigestAlgorithm,
+    pub value: Vec<u8>,
+}
+
+impl ContentDigest {
+    pub fn compute(algorithm: DigestAlgorithm, data: &[u8]) -> Self {
+        Self {
+            algorithm,
+            value: algorithm.compute(data),
+        }
+    }
+
+    /// `sha-256=:…:` — the header value.
+    pub fn to_header_value(&self) -> String {
+        format!(
+            "{}=:{}:",
+            self.algorithm.as_str(),
+            Base64Bytes::new(self.value.as_slice()).as_str()
+        )
+    }
+
+    /// The strongest digest of a header value. Members of algorithms not supported here (`md5`,
+    /// `sha` and the like, which RFC 9530 deprecates) are skipped; a header with none left is an
+    /// error.
+    pub fn parse_header(src: &str) -> Result<Self, String> {
+        let mut result: Option<Self> = None;
+        for member in src.split(',') {
+            let Some((key, value)) = member.split_once('=') else {
+                return Err(format!("'{}' is not a digest", member.trim()));
+            };
+            let Ok(algorithm) = key.trim().parse::<DigestAlgorithm>() else {
+                continue;
+            };
+
+            let value = value
+                .trim()
+                .strip_prefix(':')
+                .and_then(|value| value.strip_suffix(':'))
+                .and_then(|value| value.parse::<Base64Bytes>().ok())
+                .ok_or_else(|| format!("'{}' is not a digest", member.trim()))?;
+
+            let stronger = match &result {
+                Some(result) => result.algorithm < algorithm,
+                None => true,
+            };
+            if stronger {
+                result = Some(Self {
+                    algorithm,
+                    value: value.into_bytes(),
+                });
+            }
+        }
+
+        result.ok_or_else(|| format!("'{}' has no supported digest", src))
+    }
+
+    pub fn matches(&self, data: &[u8]) -> bool {
+        self.algorithm.compute(data) == self.value
+    }
+
+    /// Pushes the digest as `Content-Digest`.
+    pub fn fill_headers(&self, headers: &mut impl HeaderBuilder) {
+        headers.add_header(CONTENT_DIGEST_HEADER, &self.to_header_value());
+    }
+}
+
+impl HttpRequestBody {
+    /// The digest of the bytes the body is sent as. `None` for [`Self::Stream`], which has no bytes
+    /// yet — see [`crate::http_input::HttpBodyAsStream::with_content_digest`].
+    pub fn get_content_digest(&self, algorithm: DigestAlgorithm) -> Option<ContentDigest> {
+        let value = match self {
+            Self::Json(data) | Self::Raw { data, .. } => algorithm.compute(data),
+            Self::UrlEncoded(body) => algorithm.compute(body.data.as_bytes()),
+            Self::FormData(body) => algorithm.compute_parts(&body.get_parts()),
+            Self::Empty => algorithm.compute(&[]),
+            Self::Stream(_) => return None,
+        };
+
+        Some(ContentDigest { algorithm, value })
+    }
+}
+
+/// The digest of a streamed body, filled in by its reader. [`Self::get`] is `None` until the
+/// reader has handed out the whole body.
+#[derive(Clone)]
+pub struct StreamContentDigest(Arc<BodyStreamDigest>);
+
+impl StreamContentDigest {
+    pub fn get(&self) -> Option<ContentDigest> {
+        match &*self.0.lock() {
+            BodyStreamDigestState::Done(digest) => Some(digest.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Debug for StreamContentDigest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("StreamContentDigest")
+            .field(&self.get())
+            .finish()
+    }
+}
+
+/// What a body reader hashes its chunks into: the running hash, and the digest the body has to
+/// come out with when there is one to check.
+pub(crate) struct BodyStreamDigest {
+    state: Mutex<BodyStreamDigestState>,
+    expected: Option<ContentDigest>,
+}
+
+enum BodyStreamDigestState {
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Done(ContentDigest),
+}
+
+impl BodyStreamDigest {
+    pub(crate) fn calculate(algorithm: DigestAlgorithm) -> (Arc<Self>, StreamContentDigest) {
+        let result = Arc::new(Self::new(algorithm, None));
+        (result.clone(), StreamContentDigest(result))
+    }
+
+    pub(crate) fn verify(expected: ContentDigest) -> Arc<Self> {
+        Arc::new(Self::new(expected.algorithm, Some(expected)))
+    }
+
+    fn new(algorithm: DigestAlgorithm, expected: Option<ContentDigest>) -> Self {
+        let state = match algorithm {
+            DigestAlgorithm::Sha256 => BodyStreamDigestState::Sha256(Sha256::new()),
+            DigestAlgorithm::Sha512 => BodyStreamDigestState::Sha512(Sha512::new()),
+        };
+
+        Self {
+            state: Mutex::new(state),
+            expected,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BodyStreamDigestState> {
+        // Nothing under the lock panics; recover rather than propagate a panic into a body read.
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    pub(crate) fn update(&self, chunk: &[u8]) {
+        match &mut *self.lock() {
+            BodyStreamDigestState::Sha256(hasher) => hasher.update(chunk),
+            BodyStreamDigestState::Sha512(hasher) => hasher.update(chunk),
+            // A chunk after the end of the body — the channel does not deliver one.
+            BodyStreamDigestState::Done(_) => {}
+        }
+    }
+
+    /// The body has ended in full: completes the digest and checks it against the expected one.
+    /// Ending it again checks the same digest again.
+    pub(crate) fn finish(&self) -> Result<(), HttpParseError> {
+        let mut state = self.lock();
+        let digest = match &mut *state {
+            BodyStreamDigestState::Sha256(hasher) => ContentDigest {
+                algorithm: DigestAlgorithm::Sha256,
+                value: hasher.finalize_reset().to_vec(),
+            },
+            BodyStreamDigestState::Sha512(hasher) => ContentDigest {
+                algorithm: DigestAlgorithm::Sha512,
+                value: hasher.finalize_reset().to_vec(),
+            },
+            BodyStreamDigestState::Done(digest) => digest.clone(),
+        };
+
+        let matches = match &self.expected {
+            Some(expected) => *expected == digest,
+            None => true,
+        };
+        *state = BodyStreamDigestState::Done(digest);
+
+        if matches {
+            Ok(())
+        } else {
+            Err(HttpParseError::body_digest_mismatch())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContentDigest, DigestAlgorithm};
+    use crate::body::HttpRequestBody;
+    use crate::http_input::{HttpBodyAsStream, HttpParseError};
+
+    // RFC 9530 Appendix B.1: the digests of `{"hello": "world"}`.
+    const HELLO: &[u8] = br#"{"hello": "world"}"#;
+    const HELLO_SHA_256: &str = "sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:";
+    const HELLO_SHA_512: &str = "sha-512=:WZDPaVn/7XgHaAy8pmojAkGWoRx2UFChF41A2svX+TaPm+AbwAgBWnrIiYllu7BNNyealdVLvRwEmTHWXvJwew==:";
+
+    #[test]
+    fn digests_of_a_body() {
+        let body = HttpRequestBody::Json(HELLO.to_vec());
+        assert_eq!(
+            body.get_content_digest(DigestAlgorithm::Sha256)
+                .unwrap()
+                .to_header_value(),
+            HELLO_SHA_256
+        );
+        assert_eq!(
+            body.get_content_digest(DigestAlgorithm::Sha512)
+                .unwrap()
+                .to_header_value(),
+            HELLO_SHA_512
+        );
+        assert!(HttpRequestBody::Stream(HttpBodyAsStream::empty())
+            .get_content_digest(DigestAlgorithm::Sha256)
+            .is_none());
+    }
+
+    #[test]
+    fn the_strongest_supported_digest_of_a_header() {
+        let header = format!("md5=:AAAA:, {}, {}", HELLO_SHA_256, HELLO_SHA_512);
+        let digest = ContentDigest::parse_header(&header).unwrap();
+        assert_eq!(digest.algorithm, DigestAlgorithm::Sha512);
+        assert!(digest.matches(HELLO));
+        assert!(!digest.matches(b"{}"));
+
+        assert!(ContentDigest::parse_header(HELLO_SHA_256)
+            .unwrap()
+            .matches(HELLO));
+        assert!(ContentDigest::parse_header("md5=:AAAA:").is_err());
+        assert!(ContentDigest::parse_header("sha-256=AAAA").is_err());
+    }
+
+    #[tokio::test]
+    async fn a_stream_is_digested_as_it_is_read() {
+        let (sender, stream) = HttpBodyAsStream::create(4, None);
+        let (stream, digest) = stream.with_content_digest(DigestAlgorithm::Sha256);
+        let reader = stream.get_body_reader().unwrap();
+
+        tokio::spawn(async move {
+            for chunk in HELLO.chunks(5) {
+                assert!(sender.send_chunk(chunk.to_vec()).await);
+            }
+            sender.finish();
+        });
+
+        assert!(reader.get_next_chunk().await.unwrap().is_some());
+        assert_eq!(digest.get(), None);
+        assert_eq!(reader.read_to_end(None).await.unwrap(), HELLO[5..].to_vec());
+        assert_eq!(digest.get().unwrap().to_header_value(), HELLO_SHA_256);
+    }
+
+    #[tokio::test]
+    async fn a_stream_that_does_not_match_its_digest_fails_at_the_end() {
+        let expected = ContentDigest::parse_header(HELLO_SHA_256).unwrap();
+        let (sender, stream) = HttpBodyAsStream::create(4, None);
+        let reader = stream
+            .verify_content_digest(expected)
+            .get_body_reader()
+            .unwrap();
+
+        tokio::spawn(async move {
+            assert!(sender.send_chunk(br#"{"hello": "there"}"#.to_vec()).await);
+            sender.finish();
+        });
+
+        assert!(reader.get_next_chunk().await.unwrap().is_some());
+        let err = reader.get_next_chunk().await.unwrap_err();
+        assert_eq!(err, HttpParseError::body_digest_mismatch());
+        assert_eq!(err.code(), "E_BODY_DIGEST_MISMATCH");
+    }
+}
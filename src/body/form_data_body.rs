@@ -64,6 +64,16 @@ impl FormDataBody {
         self
     }
 
+    /// The pieces [`Self::into_bytes`] joins, for what hashes the body without taking it.
+    pub(crate) fn get_parts(&self) -> [&[u8]; 4] {
+        [
+            self.buffer.as_slice(),
+            b"--",
+            self.boundary.as_bytes(),
+            b"--",
+        ]
+    }
+
     pub fn into_bytes(self) -> Vec<u8> {
         let mut result = self.buffer;
 
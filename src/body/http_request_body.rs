@@ -37,6 +37,16 @@ impl HttpRequestBody {
         Ok(Self::Json(payload))
     }
 
+    /// The same body with a [`Self::Json`] payload in its RFC 8785 canonical form (see
+    /// [`crate::to_canonical_json`]) — the bytes to sign or hash, and to send, when the other side
+    /// checks a digest of them. Every other variant is returned as it is.
+    pub fn into_canonical_json(self) -> Result<Self, crate::CanonicalJsonError> {
+        match self {
+            Self::Json(data) => Ok(Self::Json(crate::to_canonical_json(&data)?)),
+            other => Ok(other),
+        }
+    }
+
     pub fn get_content_type(&self) -> Option<StrOrString<'static>> {
         match self {
             Self::Json(_) => Some("application/json".into()),
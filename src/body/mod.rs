@@ -4,3 +4,7 @@ mod url_encoded_body;
 pub use url_encoded_body::*;
 mod form_data_body;
 pub use form_data_body::*;
+mod content_digest;
+pub use content_digest::*;
+mod byte_ranges_body;
+pub use byte_ranges_body::*;
@@ -0,0 +1,298 @@
+//! Canonical JSON (RFC 8785, the JSON Canonicalization Scheme): one byte sequence per JSON value,
+//! for signing and hashing a body.
+//!
+//! The bytes a `JsonObjectWriter` produces follow the model's field order and the writer's number
+//! spelling, and another writer — the other side's — is free to differ in both. Canonicalizing
+//! settles all of it:
+//!
+//! * object members sorted by their names' UTF-16 code units, a repeated name being an error;
+//! * numbers as an IEEE 754 double, written the way ECMAScript writes one (`1e+30`, `0.002`,
+//!   `4.5`, `-0` as `0`);
+//! * strings with only `"`, `\` and the C0 controls escaped, everything else as UTF-8;
+//! * no whitespace.
+//!
+//! Canonicalizing is a pass over bytes already written: the document is parsed again and
+//! rewritten, whoever wrote it — the derive-generated writer has no canonical mode of its own.
+//!
+//! Note what the double costs: an integer the double can not hold exactly (past 2^53, say, from an
+//! `i64` / `u128` member) has no canonical form, and is an error rather than rounded to a
+//! different value — I-JSON (RFC 7493) keeps integers within what a double holds. Sign or hash the
+//! canonical bytes and send those same bytes, so the other side reads the values the digest
+//! covers.
+
+use my_json::json_writer::JsonValueWriter;
+
+use crate::json_node::{self, JsonNode, JsonSyntaxError};
+
+/// Why a document has no canonical form: it is not JSON, it repeats a member name, a number is
+/// out of the range of a double, or an integer is one a double does not hold exactly. `pointer` is the RFC 6901 JSON pointer of the offending value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalJsonError {
+    pointer: String,
+    message: String,
+}
+
+impl CanonicalJsonError {
+    pub fn new(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            pointer: pointer.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn get_pointer(&self) -> &str {
+        self.pointer.as_str()
+    }
+
+    pub fn get_message(&self) -> &str {
+        self.message.as_str()
+    }
+}
+
+impl From<JsonSyntaxError> for CanonicalJsonError {
+    fn from(err: JsonSyntaxError) -> Self {
+        Self::new(err.pointer, err.message)
+    }
+}
+
+impl std::fmt::Display for CanonicalJsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at '{}'", self.message, self.pointer)
+    }
+}
+
+impl std::error::Error for CanonicalJsonError {}
+
+/// The canonical form of a JSON document — a received body, say, before its digest is checked.
+pub fn to_canonical_json(src: &[u8]) -> Result<Vec<u8>, CanonicalJsonError> {
+    let document = json_node::parse(src)?;
+
+    let mut result = String::with_capacity(src.len());
+    write_canonical(&document, &mut Vec::new(), &mut result)?;
+    Ok(result.into_bytes())
+}
+
+/// The canonical form of a value with the my-json writer — every `MyHttpObjectStructure` and
+/// `MyHttpInputObjectStructure`.
+pub fn write_canonical_json(value: &impl JsonValueWriter) -> Result<Vec<u8>, CanonicalJsonError> {
+    let mut json = String::new();
+    value.write(&mut json);
+    to_canonical_json(json.as_bytes())
+}
+
+fn write_canonical(
+    node: &JsonNode,
+    path: &mut Vec<String>,
+    dest: &mut String,
+) -> Result<(), CanonicalJsonError> {
+    match node {
+        JsonNode::Number(number) => write_number(number, dest)
+            .map_err(|message| CanonicalJsonError::new(json_node::to_pointer(path), message)),
+        JsonNode::Array(items) => {
+            dest.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    dest.push(',');
+                }
+                path.push(index.to_string());
+                write_canonical(item, path, dest)?;
+                path.pop();
+            }
+            dest.push(']');
+            Ok(())
+        }
+        JsonNode::Object(members) => {
+            let mut sorted: Vec<_> = members.iter().collect();
+            sorted.sort_by(|(left, _), (right, _)| left.encode_utf16().cmp(right.encode_utf16()));
+
+            dest.push('{');
+            for (index, (key, value)) in sorted.iter().enumerate() {
+                path.push(key.to_string());
+                if index > 0 {
+                    if sorted[index - 1].0 == *key {
+                        return Err(CanonicalJsonError::new(
+                            json_node::to_pointer(path),
+                            "repeated member",
+                        ));
+                    }
+                    dest.push(',');
+                }
+                json_node::write_string(key, dest);
+                dest.push(':');
+                write_canonical(value, path, dest)?;
+                path.pop();
+            }
+            dest.push('}');
+            Ok(())
+        }
+        // `null`, booleans and strings: `JsonNode::write` already escapes exactly what JCS does.
+        _ => {
+            node.write(dest);
+            Ok(())
+        }
+    }
+}
+
+/// A JSON number as ECMAScript's `Number.prototype.toString` writes the double it rounds to.
+///
+/// Rust's `{:e}` gives the shortest digits that round-trip — the digits ECMAScript picks too — so
+/// only the placement of the decimal point and the exponent is left to do here.
+fn write_number(text: &str, dest: &mut String) -> Result<(), &'static str> {
+    let value: f64 = text.parse().map_err(|_| "invalid number")?;
+
+    if !value.is_finite() {
+        return Err("number is out of the range of an IEEE 754 double");
+    }
+
+    // `{:.0}` writes the double's exact integer value, so it gives the literal back only when
+    // nothing was rounded. JSON has no leading zeros to get in the way.
+    let is_integer = !text.contains(['.', 'e', 'E']);
+    if is_integer && format!("{:.0}", value.abs()) != text.trim_start_matches('-') {
+        return Err("integer can not be held exactly by an IEEE 754 double");
+    }
+
+    // Both zeros.
+    if value == 0.0 {
+        dest.push('0');
+        return Ok(());
+    }
+
+    if value < 0.0 {
+        dest.push('-');
+    }
+
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("`{:e}` always writes an exponent");
+    let exponent: i32 = exponent.parse().expect("`{:e}` writes a decimal exponent");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+
+    // ECMAScript's k (the digit count) and n (where the decimal point goes).
+    let k = digits.len() as i32;
+    let n = exponent + 1;
+
+    if k <= n && n <= 21 {
+        dest.push_str(&digits);
+        dest.extend(std::iter::repeat('0').take((n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        dest.push_str(&digits[..n as usize]);
+        dest.push('.');
+        dest.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        dest.push_str("0.");
+        dest.extend(std::iter::repeat('0').take(-n as usize));
+        dest.push_str(&digits);
+    } else {
+        dest.push_str(&digits[..1]);
+        if k > 1 {
+            dest.push('.');
+            dest.push_str(&digits[1..]);
+        }
+        dest.push('e');
+        dest.push(if n > 0 { '+' } else { '-' });
+        dest.push_str(&(n - 1).abs().to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_canonical_json, write_number};
+
+    fn number(text: &str) -> String {
+        let mut result = String::new();
+        write_number(text, &mut result).unwrap();
+        result
+    }
+
+    #[test]
+    fn numbers_are_written_as_ecmascript_writes_them() {
+        for (text, expected) in [
+            ("0", "0"),
+            ("-0", "0"),
+            ("-0.0e5", "0"),
+            ("1", "1"),
+            ("-1", "-1"),
+            ("4.50", "4.5"),
+            ("2e-3", "0.002"),
+            ("0.000001", "0.000001"),
+            ("0.0000001", "1e-7"),
+            ("1E30", "1e+30"),
+            ("1e20", "100000000000000000000"),
+            ("1e21", "1e+21"),
+            ("1.23456789012345678901234e23", "1.2345678901234569e+23"),
+            ("9007199254740992", "9007199254740992"),
+            ("18446744073709551616", "18446744073709552000"),
+            ("333333333.33333329", "333333333.3333333"),
+            ("0.000000000000000000000000001", "1e-27"),
+            ("4.9406564584124654e-324", "5e-324"),
+            ("1.7976931348623157e308", "1.7976931348623157e+308"),
+            ("-12.5e-1", "-1.25"),
+        ] {
+            assert_eq!(number(text), expected, "{}", text);
+        }
+
+        for text in [
+            "1e400",
+            "9007199254740993",
+            "-9007199254740993",
+            "18446744073709551615",
+        ] {
+            let mut dest = String::new();
+            assert!(write_number(text, &mut dest).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn members_are_sorted_by_utf16_code_units() {
+        // RFC 8785, section 3.2.3: U+1F600 sorts before U+FB33 in UTF-16, not in UTF-8.
+        let src = "{\"\u{20ac}\":\"Euro Sign\",\"\\r\":\"Carriage Return\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\",\"1\":\"One\",\"\u{1f600}\":\"Emoji: Grinning Face\",\"\u{80}\":\"Control\",\"\u{f6}\":\"Latin Small Letter O With Diaeresis\"}";
+
+        let canonical = String::from_utf8(to_canonical_json(src.as_bytes()).unwrap()).unwrap();
+        let keys: Vec<&str> = canonical
+            .split(',')
+            .map(|member| member.split(':').next().unwrap())
+            .collect();
+
+        assert_eq!(
+            keys,
+            [
+                "{\"\\r\"",
+                "\"1\"",
+                "\"\u{80}\"",
+                "\"\u{f6}\"",
+                "\"\u{20ac}\"",
+                "\"\u{1f600}\"",
+                "\"\u{fb33}\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn documents_are_canonicalized_recursively() {
+        let src = br#" { "b" : [ 1.0 , { "z" : null , "a" : "\u00e9\/" } ] , "a" : true } "#;
+        assert_eq!(
+            to_canonical_json(src).unwrap(),
+            "{\"a\":true,\"b\":[1,{\"a\":\"\u{e9}/\",\"z\":null}]}".as_bytes()
+        );
+    }
+
+    #[test]
+    fn repeated_members_and_broken_documents_are_errors() {
+        let err = to_canonical_json(br#"{"a":{"x":1,"y":2,"x":3}}"#).unwrap_err();
+        assert_eq!(err.to_string(), "repeated member at '/a/x'");
+
+        let err = to_canonical_json(br#"{"a":[1,1e999]}"#).unwrap_err();
+        assert_eq!(err.get_pointer(), "/a/1");
+
+        let err = to_canonical_json(br#"{"id":9007199254740993}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "integer can not be held exactly by an IEEE 754 double at '/id'"
+        );
+
+        assert!(to_canonical_json(br#"{"a":}"#).is_err());
+    }
+}
@@ -0,0 +1,43 @@
+//! The HMAC and SHA-2 primitives behind every signature and digest the crate makes or checks —
+//! `hmac` and `sha2`, both pure Rust, so all of it builds for wasm.
+
+use hmac::digest::KeyInit;
+use hmac::Mac;
+use sha2::Digest;
+
+pub(crate) use hmac::Hmac;
+pub(crate) use sha2::{Sha256, Sha384, Sha512};
+
+/// `M` is an `Hmac<…>` of the hash to use.
+pub(crate) fn hmac<M: Mac + KeyInit>(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC takes a key of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// In constant time, so a forger learns nothing from how long a rejection takes.
+pub(crate) fn verify_hmac<M: Mac + KeyInit>(key: &[u8], data: &[u8], signature: &[u8]) -> bool {
+    let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC takes a key of any length");
+    mac.update(data);
+    mac.verify_slice(signature).is_ok()
+}
+
+/// The digest of the pieces, hashed one after another as if they were one buffer.
+pub(crate) fn digest<D: Digest>(parts: &[&[u8]]) -> Vec<u8> {
+    let mut hasher = D::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().to_vec()
+}
+
+/// Lower-case hex.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut result = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        result.push(HEX[(b >> 4) as usize] as char);
+        result.push(HEX[(b & 0x0f) as usize] as char);
+    }
+    result
+}
@@ -0,0 +1,316 @@
+//! The wire spelling of a `DateTimeAsMicroseconds` field that says `format = "…"`.
+//!
+//! Without a format a date travels as RFC 3339 (`to_rfc3339_utc()` out,
+//! `DateTimeAsMicroseconds::from_str` in). With one, every half generated from the field agrees on
+//! it: the client writer, the server parser (query, header, path, JSON body, form), the
+//! object-structure reader and writer, and the schema (`integer` for the unix ones, `date` for
+//! `date`, a plain `string` for a pattern).
+//!
+//! Patterns are strftime-like and always UTC: `%Y` (four digits), `%m`, `%d`, `%H`, `%M`, `%S`
+//! (two), `%3f` / `%6f` (milliseconds / microseconds), `%%`; anything else is a literal. A field
+//! the pattern does not mention reads as its Unix-epoch value (`1970`, `01`, `00`…).
+
+use std::fmt::Write;
+
+use my_json::json_writer::JsonValueWriter;
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::schema::data_types::HttpSimpleType;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_DAY: i64 = 86_400 * MICROS_PER_SECOND;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateTimeFormat {
+    /// `unix_s` — whole seconds since the epoch, as a number.
+    UnixSeconds,
+    /// `unix_ms` — milliseconds since the epoch, as a number.
+    UnixMilliseconds,
+    /// `unix_us` — microseconds since the epoch, as a number.
+    UnixMicroseconds,
+    /// `rfc3339` — what a date without a format is written as, said explicitly.
+    Rfc3339,
+    /// `date` — `YYYY-MM-DD`, the time of day dropped.
+    Date,
+    /// Any other `format` — a pattern, see the module docs.
+    Pattern(&'static str),
+}
+
+impl DateTimeFormat {
+    /// Whether the value goes on the wire as a number rather than a string.
+    pub fn is_number(&self) -> bool {
+        matches!(
+            self,
+            Self::UnixSeconds | Self::UnixMilliseconds | Self::UnixMicroseconds
+        )
+    }
+
+    pub fn format(&self, value: &DateTimeAsMicroseconds) -> String {
+        let micros = value.unix_microseconds;
+        match self {
+            Self::UnixSeconds => micros.div_euclid(MICROS_PER_SECOND).to_string(),
+            Self::UnixMilliseconds => micros.div_euclid(1_000).to_string(),
+            Self::UnixMicroseconds => micros.to_string(),
+            Self::Rfc3339 => value.to_rfc3339_utc(),
+            Self::Date => format_pattern("%Y-%m-%d", micros),
+            Self::Pattern(pattern) => format_pattern(pattern, micros),
+        }
+    }
+
+    /// `None` when `src` is not a date in this format.
+    pub fn parse(&self, src: &str) -> Option<DateTimeAsMicroseconds> {
+        let micros = match self {
+            Self::UnixSeconds => parse_unix(src)?.checked_mul(MICROS_PER_SECOND)?,
+            Self::UnixMilliseconds => parse_unix(src)?.checked_mul(1_000)?,
+            Self::UnixMicroseconds => parse_unix(src)?,
+            Self::Rfc3339 => return DateTimeAsMicroseconds::from_str(src),
+            Self::Date => parse_pattern("%Y-%m-%d", src)?,
+            Self::Pattern(pattern) => parse_pattern(pattern, src)?,
+        };
+
+        Some(DateTimeAsMicroseconds::new(micros))
+    }
+
+    /// The schema type of a field in this format.
+    pub fn get_simple_type(&self) -> HttpSimpleType {
+        match self {
+            Self::UnixSeconds | Self::UnixMilliseconds | Self::UnixMicroseconds => {
+                HttpSimpleType::Long
+            }
+            Self::Rfc3339 => HttpSimpleType::DateTime,
+            Self::Date => HttpSimpleType::Date,
+            Self::Pattern(_) => HttpSimpleType::String,
+        }
+    }
+}
+
+/// The `format = "…"` spelling.
+impl std::fmt::Display for DateTimeFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnixSeconds => f.write_str("unix_s"),
+            Self::UnixMilliseconds => f.write_str("unix_ms"),
+            Self::UnixMicroseconds => f.write_str("unix_us"),
+            Self::Rfc3339 => f.write_str("rfc3339"),
+            Self::Date => f.write_str("date"),
+            Self::Pattern(pattern) => f.write_str(pattern),
+        }
+    }
+}
+
+/// A date written in a given format — a number for the unix formats, a string otherwise. What the
+/// generated writers append for a field with a `format`.
+pub struct JsonFormattedDateTime(pub DateTimeAsMicroseconds, pub DateTimeFormat);
+
+impl JsonValueWriter for JsonFormattedDateTime {
+    const IS_ARRAY: bool = false;
+
+    fn write(&self, dest: &mut String) {
+        let text = self.1.format(&self.0);
+        if self.1.is_number() {
+            dest.push_str(&text);
+        } else {
+            crate::json_node::write_string(&text, dest);
+        }
+    }
+}
+
+fn parse_unix(src: &str) -> Option<i64> {
+    let digits = src.strip_prefix('-').unwrap_or(src);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    src.parse().ok()
+}
+
+fn format_pattern(pattern: &str, micros: i64) -> String {
+    let days = micros.div_euclid(MICROS_PER_DAY);
+    let micros_of_day = micros.rem_euclid(MICROS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    let seconds_of_day = micros_of_day / MICROS_PER_SECOND;
+    let fraction = micros_of_day % MICROS_PER_SECOND;
+
+    let mut result = String::with_capacity(pattern.len() + 8);
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+
+        let _ = match chars.next() {
+            Some('Y') => write!(result, "{:04}", year),
+            Some('m') => write!(result, "{:02}", month),
+            Some('d') => write!(result, "{:02}", day),
+            Some('H') => write!(result, "{:02}", seconds_of_day / 3600),
+            Some('M') => write!(result, "{:02}", seconds_of_day / 60 % 60),
+            Some('S') => write!(result, "{:02}", seconds_of_day % 60),
+            Some('3') if chars.next() == Some('f') => write!(result, "{:03}", fraction / 1_000),
+            Some('6') if chars.next() == Some('f') => write!(result, "{:06}", fraction),
+            Some('%') => write!(result, "%"),
+            // The derive rejects any other specifier; a hand-built one is written as it stands.
+            Some(other) => write!(result, "%{}", other),
+            None => write!(result, "%"),
+        };
+    }
+
+    result
+}
+
+fn parse_pattern(pattern: &str, src: &str) -> Option<i64> {
+    let (mut year, mut month, mut day) = (1970, 1, 1);
+    let (mut hour, mut minute, mut second, mut fraction) = (0, 0, 0, 0);
+
+    let mut src = src.as_bytes();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            let mut buffer = [0; 4];
+            src = src.strip_prefix(c.encode_utf8(&mut buffer).as_bytes())?;
+            continue;
+        }
+
+        match chars.next()? {
+            'Y' => year = take_digits(&mut src, 4)?,
+            'm' => month = take_digits(&mut src, 2)?,
+            'd' => day = take_digits(&mut src, 2)?,
+            'H' => hour = take_digits(&mut src, 2)?,
+            'M' => minute = take_digits(&mut src, 2)?,
+            'S' => second = take_digits(&mut src, 2)?,
+            '3' if chars.next() == Some('f') => fraction = take_digits(&mut src, 3)? * 1_000,
+            '6' if chars.next() == Some('f') => fraction = take_digits(&mut src, 6)?,
+            '%' => src = src.strip_prefix(b"%")?,
+            _ => return None,
+        }
+    }
+
+    let valid = src.is_empty()
+        && (1..=12).contains(&month)
+        && day >= 1
+        && day <= days_in_month(year, month)
+        && hour < 24
+        && minute < 60
+        && second < 60;
+    if !valid {
+        return None;
+    }
+
+    let seconds_of_day = (hour * 60 + minute) * 60 + second;
+    Some(
+        days_from_civil(year, month, day) * MICROS_PER_DAY
+            + seconds_of_day * MICROS_PER_SECOND
+            + fraction,
+    )
+}
+
+/// Exactly `count` ASCII digits.
+fn take_digits(src: &mut &[u8], count: usize) -> Option<i64> {
+    if src.len() < count || !src[..count].iter().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let value = src[..count]
+        .iter()
+        .fold(0, |value, digit| value * 10 + (digit - b'0') as i64);
+    *src = &src[count..];
+    Some(value)
+}
+
+pub(crate) fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date (Howard Hinnant's `days_from_civil`).
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The inverse of [`days_from_civil`].
+pub(crate) fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{civil_from_days, days_from_civil, format_pattern, parse_pattern, parse_unix};
+
+    // 2024-02-29T13:45:07.123456Z
+    const LEAP_DAY: i64 = 1_709_214_307_123_456;
+
+    #[test]
+    fn civil_dates_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+
+        for days in [-719_468, -1, 0, 11_016, 19_782, 2_932_896] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn patterns_are_written_and_read_back() {
+        for (pattern, text) in [
+            ("%Y-%m-%d", "2024-02-29"),
+            ("%Y-%m-%dT%H:%M:%S.%6fZ", "2024-02-29T13:45:07.123456Z"),
+            ("%d/%m/%Y %H:%M:%S.%3f", "29/02/2024 13:45:07.123"),
+            ("%Y%m%d%H%M%S", "20240229134507"),
+            ("100%% %Y", "100% 2024"),
+        ] {
+            assert_eq!(format_pattern(pattern, LEAP_DAY), text, "{}", pattern);
+
+            let read = parse_pattern(pattern, text).unwrap();
+            assert_eq!(format_pattern(pattern, read), text, "{}", pattern);
+        }
+
+        assert_eq!(format_pattern("%Y-%m-%d %H:%M", -1), "1969-12-31 23:59");
+    }
+
+    #[test]
+    fn patterns_reject_what_they_do_not_describe() {
+        for (pattern, text) in [
+            ("%Y-%m-%d", "2023-02-29"),
+            ("%Y-%m-%d", "2024-13-01"),
+            ("%Y-%m-%d", "2024-1-01"),
+            ("%Y-%m-%d", "2024-01-01T00:00"),
+            ("%Y-%m-%d", "2024/01/01"),
+            ("%H:%M", "24:00"),
+        ] {
+            assert_eq!(parse_pattern(pattern, text), None, "{} {}", pattern, text);
+        }
+    }
+
+    #[test]
+    fn unix_values_are_plain_integers() {
+        assert_eq!(parse_unix("1709214307"), Some(1_709_214_307));
+        assert_eq!(parse_unix("-5"), Some(-5));
+        for text in ["", "-", "+5", "1.5", "1e9", " 1"] {
+            assert_eq!(parse_unix(text), None, "{}", text);
+        }
+    }
+}
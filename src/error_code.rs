@@ -0,0 +1,92 @@
+//! Stable machine-readable codes for every error this crate returns, and the JSON form the errors
+//! share.
+//!
+//! A client should branch on the code, never on the `Display` text: the wording of a message may
+//! change, a code may not. Each error type has `code()`, writes itself with `my-json` as
+//!
+//! ```json
+//! {"code":"E_CANNOT_PARSE","message":"Can not parse 'age' value 'x' from QueryString","name":"age","src":"QueryString","value":"x"}
+//! ```
+//!
+//! and reads the same JSON back into the typed error (`from_json`) — so a wasm client gets the
+//! server's `HttpParseError`, not a string. `message` is there for people; the other members are
+//! the variant's own data.
+//!
+//! A code names a kind of failure, not a type: a missing parameter is `E_REQUIRED_MISSING` whether
+//! an `HttpParseError`, a `ReadingEncodedDataError` or a `ReadingFromDataError` reports it.
+
+use my_json::json_reader::JsonParseError;
+
+/// A required parameter is absent (`name`, and `src` where the error knows the source).
+pub const E_REQUIRED_MISSING: &str = "E_REQUIRED_MISSING";
+/// A value is present but is not of the target type (`value`).
+pub const E_CANNOT_PARSE: &str = "E_CANNOT_PARSE";
+/// Percent-decoding failed (`detail`).
+pub const E_URL_DECODE: &str = "E_URL_DECODE";
+/// The body is not the format its `Content-Type` says (`detail`).
+pub const E_INVALID_BODY: &str = "E_INVALID_BODY";
+/// The value can not be read as the target type out of its content type (`detail`).
+pub const E_UNSUPPORTED_CONTENT_TYPE: &str = "E_UNSUPPORTED_CONTENT_TYPE";
+/// The conversion is not allowed for the source, e.g. a file out of a query string (`detail`).
+pub const E_FORBIDDEN: &str = "E_FORBIDDEN";
+/// The request carries none of the credentials its model or route requires (`detail`).
+pub const E_UNAUTHORIZED: &str = "E_UNAUTHORIZED";
+/// A validator rejected the value (`detail`, or `field` and `reason` / `error`).
+pub const E_VALIDATION: &str = "E_VALIDATION";
+/// A streamed body was asked for, but the request has none.
+pub const E_BODY_STREAM_NOT_AVAILABLE: &str = "E_BODY_STREAM_NOT_AVAILABLE";
+/// The reader of a streamed body was already taken.
+pub const E_BODY_STREAM_TAKEN: &str = "E_BODY_STREAM_TAKEN";
+/// A streamed body broke off before the transport marked it complete.
+pub const E_BODY_STREAM_TRUNCATED: &str = "E_BODY_STREAM_TRUNCATED";
+/// A streamed body went over the size its reader allowed.
+pub const E_BODY_TOO_LARGE: &str = "E_BODY_TOO_LARGE";
+/// A streamed body does not match the `Content-Digest` it was sent with.
+pub const E_BODY_DIGEST_MISMATCH: &str = "E_BODY_DIGEST_MISMATCH";
+/// Any other failure of a streamed body — what a transport reports through the stream (`detail`).
+pub const E_BODY_STREAM: &str = "E_BODY_STREAM";
+/// A request body has no canonical JSON form (`field`, `reason`).
+pub const E_CANONICAL_BODY: &str = "E_CANONICAL_BODY";
+/// A request could not be signed (`field`, `reason`).
+pub const E_SIGNATURE: &str = "E_SIGNATURE";
+
+/// A string member of an error's JSON form.
+pub(crate) fn read_member(raw: &[u8], key: &str) -> Result<String, JsonParseError> {
+    let value = my_json::j_path::get_value(raw, key)?;
+    match value.as_ref().and_then(|value| value.as_str()) {
+        Some(text) => Ok(text.into_string()),
+        None => Err(JsonParseError::new(format!(
+            "Error JSON needs a string member '{}'",
+            key
+        ))),
+    }
+}
+
+pub(crate) fn unknown_code(type_name: &str, code: &str) -> JsonParseError {
+    JsonParseError::new(format!("'{}' is not a code of {}", code, type_name))
+}
+
+/// `to_json` and the `JsonValueReader` of an error type, out of its `JsonValueWriter` and its
+/// inherent `from_json(raw: &[u8])`.
+macro_rules! impl_error_json {
+    ($ty:ty) => {
+        impl $ty {
+            /// The JSON form: `code`, `message` and the members of the variant.
+            pub fn to_json(&self) -> String {
+                let mut result = String::new();
+                my_json::json_writer::JsonValueWriter::write(self, &mut result);
+                result
+            }
+        }
+
+        impl<'s> my_json::json_reader::JsonValueReader<'s> for $ty {
+            fn from_json_value(
+                value: &my_json::json_reader::JsonValueRef<'s>,
+            ) -> Result<Self, my_json::json_reader::JsonParseError> {
+                Self::from_json(value.as_slice())
+            }
+        }
+    };
+}
+
+pub(crate) use impl_error_json;
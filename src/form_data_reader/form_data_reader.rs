@@ -39,6 +39,11 @@ impl<'s> FormDataReader<'s> {
     pub fn get_optional(&'s self, name: &str) -> Option<&'s FormDataItem<'s>> {
         self.data.iter().find(|&itm| itm.get_name() == name).map(|v| v as _)
     }
+
+    /// The names of the parts, in order and with repeats kept.
+    pub fn get_names(&self) -> impl Iterator<Item = &str> {
+        self.data.iter().map(|itm| itm.get_name())
+    }
 }
 
 #[cfg(test)]
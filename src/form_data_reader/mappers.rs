@@ -4,6 +4,10 @@ use rust_extensions::date_time::DateTimeAsMicroseconds;
 use serde::de::DeserializeOwned;
 
 use crate::form_data_reader::FormDataItem;
+use crate::http_input::{
+    Base64Bytes, Base64UrlBytes, DecimalString, EmailAddress, IsoDuration, ParseProfile, Uri, Uuid,
+};
+use crate::DateTimeFormat;
 
 use super::ReadingFromDataError;
 
@@ -31,13 +35,16 @@ impl<'s> TryInto<bool> for &'s FormDataItem<'s> {
     type Error = ReadingFromDataError;
     fn try_into(self) -> Result<bool, Self::Error> {
         match self {
-            FormDataItem::ValueAsString { value, name } => to_bool(name, value),
+            FormDataItem::ValueAsString { value, name } => {
+                to_bool(name, value, ParseProfile::Standard)
+            }
             FormDataItem::File { name, .. } => Err(file_conversion_error(name, "bool")),
         }
     }
 }
 
-/// Generates the near-identical `TryInto<$t>` impls for the primitive numeric types.
+/// Generates the near-identical `TryInto<$t>` impls for the primitive numeric types and the
+/// `FromStr`-backed simple types.
 macro_rules! impl_try_into_simple {
     ($($t:ty),+ $(,)?) => {
         $(
@@ -58,7 +65,10 @@ macro_rules! impl_try_into_simple {
     };
 }
 
-impl_try_into_simple!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64, usize, isize);
+impl_try_into_simple!(
+    u8, i8, u16, i16, u32, i32, u64, i64, i128, u128, f32, f64, usize, isize,
+    DecimalString, Uuid, Uri, EmailAddress, IsoDuration, Base64Bytes, Base64UrlBytes,
+);
 
 impl<'s> TryInto<DateTimeAsMicroseconds> for &'s FormDataItem<'s> {
     type Error = ReadingFromDataError;
@@ -111,19 +121,17 @@ fn file_conversion_error(field: &str, target: &str) -> ReadingFromDataError {
     }
 }
 
-fn to_bool(param_name: &str, value: &str) -> Result<bool, ReadingFromDataError> {
-    if value == "1" || value.eq_ignore_ascii_case("true") {
-        return Ok(true);
-    }
-
-    if value == "0" || value.eq_ignore_ascii_case("false") {
-        return Ok(false);
-    }
-
-    Err(ReadingFromDataError::ValidationError {
-        field: param_name.to_string(),
-        error: "Can not convert value to boolean".into(),
-    })
+fn to_bool(
+    param_name: &str,
+    value: &str,
+    profile: ParseProfile,
+) -> Result<bool, ReadingFromDataError> {
+    profile
+        .parse_bool(value)
+        .ok_or_else(|| ReadingFromDataError::ValidationError {
+            field: param_name.to_string(),
+            error: "Can not convert value to boolean".into(),
+        })
 }
 
 fn to_simple_value<T: std::str::FromStr>(
@@ -140,6 +148,37 @@ fn to_simple_value<T: std::str::FromStr>(
     })
 }
 
+impl<'s> FormDataItem<'s> {
+    /// The `TryInto<bool>` above in the vocabulary of another [`ParseProfile`] (`yes` / `on` when
+    /// lenient, exactly `true` / `false` when strict).
+    pub fn as_bool_with_profile(&self, profile: ParseProfile) -> Result<bool, ReadingFromDataError> {
+        match self {
+            FormDataItem::ValueAsString { value, name } => to_bool(name, value, profile),
+            FormDataItem::File { name, .. } => Err(file_conversion_error(name, "bool")),
+        }
+    }
+
+    /// The `TryInto<DateTimeAsMicroseconds>` above, for a date in an explicit wire format
+    /// (`format = "unix_ms"`, `"date"`, a pattern…) rather than RFC 3339.
+    pub fn as_formatted_date_time(
+        &self,
+        format: DateTimeFormat,
+    ) -> Result<DateTimeAsMicroseconds, ReadingFromDataError> {
+        match self {
+            FormDataItem::ValueAsString { value, name } => match format.parse(value) {
+                Some(result) => Ok(result),
+                None => Err(ReadingFromDataError::ValidationError {
+                    field: name.to_string(),
+                    error: format!("Can not convert value to DateTime in the '{}' format", format),
+                }),
+            },
+            FormDataItem::File { name, .. } => {
+                Err(file_conversion_error(name, "DateTimeAsMicroseconds"))
+            }
+        }
+    }
+}
+
 fn to_date_time(
     param_name: &str,
     value: &str,
@@ -177,6 +216,7 @@ fn to_json_from_slice<TResult: DeserializeOwned>(
 #[cfg(test)]
 mod tests {
     use crate::form_data_reader::FormDataItem;
+    use crate::http_input::ParseProfile;
 
     fn value(name: &'static str, value: &'static str) -> FormDataItem<'static> {
         FormDataItem::ValueAsString { name, value }
@@ -200,12 +240,26 @@ mod tests {
         assert_eq!(as_i64, 42);
     }
 
+    #[test]
+    fn test_base64_conversion() {
+        let bytes: crate::http_input::Base64Bytes = (&value("n", "aGk=")).try_into().unwrap();
+        assert_eq!(bytes.as_bytes(), b"hi");
+
+        let err: Result<crate::http_input::Base64Bytes, _> = (&value("n", "a_k")).try_into();
+        assert!(err.is_err());
+    }
+
     #[test]
     fn test_bool_conversion() {
         let t: bool = (&value("n", "true")).try_into().unwrap();
         let f: bool = (&value("n", "0")).try_into().unwrap();
         assert!(t);
         assert!(!f);
+
+        let on = value("n", "on");
+        assert!(TryInto::<bool>::try_into(&on).is_err());
+        assert!(on.as_bool_with_profile(ParseProfile::Lenient).unwrap());
+        assert!(value("n", "TRUE").as_bool_with_profile(ParseProfile::Strict).is_err());
     }
 
     #[test]
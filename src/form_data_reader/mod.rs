@@ -1,3 +1,8 @@
+use my_json::json_reader::JsonParseError;
+use my_json::json_writer::{JsonObjectWriter, JsonValueWriter};
+
+use crate::error_code::*;
+
 mod content_disposition_parser;
 mod content_iterator;
 mod form_data_item;
@@ -11,3 +16,80 @@ pub enum ReadingFromDataError {
     ParameterMissing(String),
     ValidationError { field: String, error: String },
 }
+
+impl ReadingFromDataError {
+    /// The stable code of the failure — one of the [`crate::error_code`] constants.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ParameterMissing(_) => E_REQUIRED_MISSING,
+            Self::ValidationError { .. } => E_VALIDATION,
+        }
+    }
+
+    /// Reads the JSON `to_json` wrote back into the same error.
+    pub fn from_json(raw: &[u8]) -> Result<Self, JsonParseError> {
+        let code = read_member(raw, "code")?;
+        let result = match code.as_str() {
+            E_REQUIRED_MISSING => Self::ParameterMissing(read_member(raw, "name")?),
+            E_VALIDATION => Self::ValidationError {
+                field: read_member(raw, "field")?,
+                error: read_member(raw, "error")?,
+            },
+            other => return Err(unknown_code("ReadingFromDataError", other)),
+        };
+        Ok(result)
+    }
+}
+
+impl std::fmt::Display for ReadingFromDataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParameterMissing(name) => write!(f, "Required parameter '{}' is missing", name),
+            Self::ValidationError { field, error } => write!(f, "Field '{}': {}", field, error),
+        }
+    }
+}
+
+impl JsonValueWriter for ReadingFromDataError {
+    const IS_ARRAY: bool = false;
+
+    fn write(&self, dest: &mut String) {
+        let obj = JsonObjectWriter::new()
+            .write_ref("code", &self.code())
+            .write_ref("message", &self.to_string());
+
+        let obj = match self {
+            Self::ParameterMissing(name) => obj.write_ref("name", name),
+            Self::ValidationError { field, error } => {
+                obj.write_ref("field", field).write_ref("error", error)
+            }
+        };
+
+        obj.build_into(dest);
+    }
+}
+
+impl_error_json!(ReadingFromDataError);
+
+#[cfg(test)]
+mod tests {
+    use super::ReadingFromDataError;
+
+    #[test]
+    fn round_trips_through_json() {
+        for err in [
+            ReadingFromDataError::ParameterMissing("file".to_string()),
+            ReadingFromDataError::ValidationError {
+                field: "file".to_string(),
+                error: "a value where a file was expected".to_string(),
+            },
+        ] {
+            let json = err.to_json();
+            let read = ReadingFromDataError::from_json(json.as_bytes()).unwrap();
+            assert_eq!(read.code(), err.code());
+            assert_eq!(read.to_json(), json);
+        }
+
+        assert!(ReadingFromDataError::from_json(br#"{"code":"E_URL_DECODE"}"#).is_err());
+    }
+}
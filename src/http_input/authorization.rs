@@ -0,0 +1,265 @@
+//! Typed `Authorization` headers: [`BasicAuthCredentials`] (RFC 7617) and [`BearerToken`]
+//! (RFC 6750), for an `#[http_header(name = "Authorization")]` field.
+//!
+//! Both keep the whole header value, so the client request builder writes it back through
+//! `as_str()` exactly like any other struct-typed header, and both keep the secret out of `Debug`
+//! (and out of a parse error's message). The schema describes such a field as a security scheme
+//! (`HttpSecurityScheme`), not as a header parameter.
+
+use std::str::FromStr;
+
+use super::Base64Bytes;
+
+const REDACTED: &str = "<redacted>";
+
+/// `Authorization: Basic <base64(user:password)>`.
+///
+/// The scheme name is read in any case; the user and the password are UTF-8. A user can not
+/// contain `:` — the first colon is where the password starts.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BasicAuthCredentials {
+    user: String,
+    password: String,
+    header: String,
+}
+
+impl BasicAuthCredentials {
+    pub fn new(user: impl Into<String>, password: impl Into<String>) -> Result<Self, String> {
+        let user = user.into();
+        if user.contains(':') {
+            return Err("A basic-auth user can not contain ':'".to_string());
+        }
+
+        let password = password.into();
+        let encoded = Base64Bytes::new(format!("{}:{}", user, password).into_bytes());
+        let header = format!("Basic {}", encoded.as_str());
+
+        Ok(Self {
+            user,
+            password,
+            header,
+        })
+    }
+
+    pub fn get_user(&self) -> &str {
+        self.user.as_str()
+    }
+
+    pub fn get_password(&self) -> &str {
+        self.password.as_str()
+    }
+
+    /// The header value, as it goes on the wire.
+    pub fn as_str(&self) -> &str {
+        self.header.as_str()
+    }
+}
+
+impl FromStr for BasicAuthCredentials {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let credentials = strip_scheme(src, "Basic")
+            .ok_or_else(|| "Authorization is not of the Basic scheme".to_string())?;
+
+        let decoded = credentials
+            .parse::<Base64Bytes>()
+            .map_err(|_| "Basic credentials are not base64".to_string())?;
+
+        let decoded = String::from_utf8(decoded.into_bytes())
+            .map_err(|_| "Basic credentials are not UTF-8".to_string())?;
+
+        match decoded.split_once(':') {
+            Some((user, password)) => Self::new(user, password),
+            None => Err("Basic credentials have no ':' between user and password".to_string()),
+        }
+    }
+}
+
+impl std::fmt::Debug for BasicAuthCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BasicAuthCredentials")
+            .field("user", &self.user)
+            .field("password", &REDACTED)
+            .finish()
+    }
+}
+
+/// `Authorization: Bearer <token>`. The token is an RFC 6750 `token68` — letters, digits,
+/// `-._~+/`, then optional `=` padding — so a JWT or an opaque token fits, and whitespace or
+/// anything a header can not carry does not.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BearerToken {
+    header: String,
+}
+
+impl BearerToken {
+    pub fn new(token: impl Into<String>) -> Result<Self, String> {
+        let token = token.into();
+        if !is_token68(token.as_str()) {
+            return Err("A bearer token is not a valid token68".to_string());
+        }
+
+        Ok(Self {
+            header: format!("Bearer {}", token),
+        })
+    }
+
+    pub fn get_token(&self) -> &str {
+        &self.header["Bearer ".len()..]
+    }
+
+    /// The header value, as it goes on the wire.
+    pub fn as_str(&self) -> &str {
+        self.header.as_str()
+    }
+}
+
+impl FromStr for BearerToken {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        match strip_scheme(src, "Bearer") {
+            Some(token) => Self::new(token),
+            None => Err("Authorization is not of the Bearer scheme".to_string()),
+        }
+    }
+}
+
+impl std::fmt::Debug for BearerToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BearerToken").field(&REDACTED).finish()
+    }
+}
+
+/// What follows `scheme` and the run of spaces / tabs after it; `None` for another scheme.
+pub(crate) fn strip_scheme<'s>(src: &'s str, scheme: &str) -> Option<&'s str> {
+    let src = src.trim_matches(|c: char| c.is_ascii_whitespace());
+    let (name, rest) = src.split_at(src.find(|c: char| c.is_ascii_whitespace())?);
+    if !name.eq_ignore_ascii_case(scheme) {
+        return None;
+    }
+
+    Some(rest.trim_start_matches(|c: char| c.is_ascii_whitespace()))
+}
+
+fn is_token68(src: &str) -> bool {
+    let body = src.trim_end_matches('=');
+    !body.is_empty()
+        && body.bytes().all(|b| {
+            b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~' | b'+' | b'/')
+        })
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use super::{BasicAuthCredentials, BearerToken, REDACTED};
+    use crate::http_input::{HttpInputValue, HttpParseError};
+    use crate::schema::data_types::{DataTypeProvider, HttpDataType};
+    use crate::schema::in_parameters::HttpSecurityScheme;
+
+    macro_rules! impl_authorization {
+        ($t:ty, $scheme:ident) => {
+            // Not through `HttpInputValue::parse`: its error would carry the header — the secret —
+            // as the offending value.
+            impl<'s> TryInto<$t> for HttpInputValue<'s> {
+                type Error = HttpParseError;
+
+                fn try_into(self) -> Result<$t, Self::Error> {
+                    let value = self.as_string()?;
+                    value.parse().map_err(|_: String| {
+                        HttpParseError::cannot_parse(self.get_name(), self.get_src(), REDACTED)
+                    })
+                }
+            }
+
+            impl DataTypeProvider for $t {
+                fn get_data_type() -> HttpDataType {
+                    HttpDataType::as_string()
+                }
+
+                fn get_security_scheme() -> Option<HttpSecurityScheme> {
+                    Some(HttpSecurityScheme::$scheme)
+                }
+            }
+        };
+    }
+
+    impl_authorization!(BasicAuthCredentials, Basic);
+    impl_authorization!(BearerToken, Bearer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{strip_scheme, BasicAuthCredentials, BearerToken};
+
+    #[test]
+    fn basic_credentials_round_trip() {
+        // RFC 7617 §2.
+        let parsed: BasicAuthCredentials = "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==".parse().unwrap();
+        assert_eq!(parsed.get_user(), "Aladdin");
+        assert_eq!(parsed.get_password(), "open sesame");
+
+        let built = BasicAuthCredentials::new("Aladdin", "open sesame").unwrap();
+        assert_eq!(built.as_str(), "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==");
+        assert_eq!(built, parsed);
+
+        let colon: BasicAuthCredentials = "basic dTpwOnE=".parse().unwrap();
+        assert_eq!((colon.get_user(), colon.get_password()), ("u", "p:q"));
+    }
+
+    #[test]
+    fn rejects_malformed_basic_credentials() {
+        for src in [
+            "Bearer QWxhZGRpbg==",
+            "Basic",
+            "Basic %%%",
+            "Basic QWxhZGRpbg==",
+        ] {
+            assert!(src.parse::<BasicAuthCredentials>().is_err(), "{:?}", src);
+        }
+        assert!(BasicAuthCredentials::new("a:b", "c").is_err());
+    }
+
+    #[test]
+    fn bearer_tokens() {
+        let token: BearerToken = "bearer  abc.DEF-_~+/==".parse().unwrap();
+        assert_eq!(token.get_token(), "abc.DEF-_~+/==");
+        assert_eq!(token.as_str(), "Bearer abc.DEF-_~+/==");
+
+        for src in [
+            "Bearer",
+            "Bearer a b",
+            "Bearer =abc",
+            "Basic abc",
+            "Bearer a=b",
+        ] {
+            assert!(src.parse::<BearerToken>().is_err(), "{:?}", src);
+        }
+    }
+
+    #[test]
+    fn the_scheme_ends_at_any_run_of_spaces_or_tabs() {
+        for src in ["Bearer abc", "Bearer\tabc", "Bearer  abc", " bearer \t abc "] {
+            assert_eq!(strip_scheme(src, "Bearer"), Some("abc"), "{:?}", src);
+        }
+        assert_eq!(strip_scheme("Bearer", "Bearer"), None);
+        assert_eq!(strip_scheme("Bearerabc", "Bearer"), None);
+        assert_eq!(strip_scheme("Basic\tabc", "Bearer"), None);
+
+        let token: BearerToken = "Bearer\tabc".parse().unwrap();
+        assert_eq!(token.get_token(), "abc");
+    }
+
+    #[test]
+    fn secrets_stay_out_of_debug() {
+        let basic = BasicAuthCredentials::new("ann", "s3cret").unwrap();
+        assert_eq!(
+            format!("{:?}", basic),
+            r#"BasicAuthCredentials { user: "ann", password: "<redacted>" }"#
+        );
+
+        let bearer = BearerToken::new("s3cret").unwrap();
+        assert_eq!(format!("{:?}", bearer), r#"BearerToken("<redacted>")"#);
+    }
+}
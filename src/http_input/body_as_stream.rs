@@ -46,9 +46,10 @@
 //! builds *without* `server` gets the whole thing. The one `server`-only item is the
 //! `DataTypeProvider` impl at the bottom, which needs the OpenAPI `schema` module.
 
-use crate::http_input::HttpParseError;
+use crate::body::{BodyStreamDigest, ContentDigest, DigestAlgorithm, StreamContentDigest};
+use crate::http_input::{ByteRange, HttpParseError};
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 
 /// Default capacity of the chunk channel: how many chunks the pump may read ahead of the handler.
@@ -88,6 +89,54 @@ enum BodyStreamState {
 struct BodyStreamInner {
     rx: tokio::sync::mpsc::Receiver<Result<Vec<u8>, HttpParseError>>,
     completed: Arc<AtomicBool>,
+    digests: Vec<Arc<BodyStreamDigest>>,
+    window: Option<BodyStreamWindow>,
+}
+
+/// The part of the source a [`HttpBodyAsStream::with_range`] stream hands out: the bytes still to
+/// skip, then the bytes still to deliver.
+///
+/// Atomics only so the reader stays `Sync`: every update happens under the receiver's lock (or
+/// through `&mut`), so `Relaxed` is enough.
+struct BodyStreamWindow {
+    skip: AtomicU64,
+    remaining: AtomicU64,
+}
+
+impl BodyStreamWindow {
+    fn new(range: ByteRange) -> Self {
+        Self {
+            skip: AtomicU64::new(range.get_start()),
+            remaining: AtomicU64::new(range.get_length()),
+        }
+    }
+
+    /// What of a source chunk falls inside the window; `None` when nothing does.
+    fn cut(&self, mut chunk: Vec<u8>) -> Option<Vec<u8>> {
+        let skip = self.skip.load(Ordering::Relaxed);
+        if skip > 0 {
+            let skipped = skip.min(chunk.len() as u64);
+            self.skip.store(skip - skipped, Ordering::Relaxed);
+            chunk.drain(..skipped as usize);
+        }
+
+        let remaining = self.remaining.load(Ordering::Relaxed);
+        if chunk.is_empty() || remaining == 0 {
+            return None;
+        }
+
+        if chunk.len() as u64 > remaining {
+            chunk.truncate(remaining as usize);
+        }
+
+        self.remaining
+            .store(remaining - chunk.len() as u64, Ordering::Relaxed);
+        Some(chunk)
+    }
+
+    fn is_filled(&self) -> bool {
+        self.remaining.load(Ordering::Relaxed) == 0
+    }
 }
 
 impl HttpBodyAsStream {
@@ -126,7 +175,12 @@ impl HttpBodyAsStream {
         };
 
         let stream = HttpBodyAsStream {
-            inner: std::sync::Mutex::new(BodyStreamState::Ready(BodyStreamInner { rx, completed })),
+            inner: std::sync::Mutex::new(BodyStreamState::Ready(BodyStreamInner {
+                rx,
+                completed,
+                digests: Vec::new(),
+                window: None,
+            })),
             content_length,
         };
 
@@ -155,20 +209,66 @@ impl HttpBodyAsStream {
                 }
                 // Not "taken" — it never existed. Leave the state alone so the message stays
                 // truthful on every repeated call.
-                BodyStreamState::NotAvailable => Err("Body stream is not available"),
-                BodyStreamState::Taken => Err("Body reader is already taken"),
+                BodyStreamState::NotAvailable => Err(HttpParseError::body_stream_not_available()),
+                BodyStreamState::Taken => Err(HttpParseError::body_reader_taken()),
             }
             // The guard is dropped here — it never crosses an `.await`, so the caller's future
             // stays `Send`.
         };
 
-        match taken {
-            Ok(inner) => Ok(HttpBodyReader {
-                rx: tokio::sync::Mutex::new(inner.rx),
-                completed: inner.completed,
-                content_length: self.content_length,
-            }),
-            Err(msg) => Err(HttpParseError::BodyStream(msg.to_string())),
+        let inner = taken?;
+        Ok(HttpBodyReader {
+            rx: tokio::sync::Mutex::new(inner.rx),
+            completed: inner.completed,
+            content_length: self.content_length,
+            digests: inner.digests,
+            window: inner.window,
+            error: std::sync::Mutex::new(None),
+        })
+    }
+
+    /// The same stream, hashed as its reader hands the chunks out — the client's way to the
+    /// `Content-Digest` of a body it streams. The returned handle has the digest once the reader
+    /// has read the body in full.
+    ///
+    /// Only a stream whose reader is still in it is hashed; for [`Self::empty`], or after
+    /// [`Self::get_body_reader`], the handle never gets a digest.
+    pub fn with_content_digest(self, algorithm: DigestAlgorithm) -> (Self, StreamContentDigest) {
+        let (digest, result) = BodyStreamDigest::calculate(algorithm);
+        self.add_digest(digest);
+        (self, result)
+    }
+
+    /// The same stream, checked against the digest the request was sent with (see
+    /// `http_input::core::read_content_digest`): the read that would end the body fails with
+    /// [`HttpParseError::body_digest_mismatch`] instead when the body does not match it.
+    pub fn verify_content_digest(self, expected: ContentDigest) -> Self {
+        self.add_digest(BodyStreamDigest::verify(expected));
+        self
+    }
+
+    /// The same stream cut down to one range of the source — how a `206 Partial Content` serves
+    /// a range out of the stream of the whole representation. The reader skips the bytes before
+    /// `range`, hands out the range, and ends the body there, closing the channel so the pump
+    /// stops; a source that ends before the range is filled is a truncated body. The content
+    /// length becomes the length of the range.
+    ///
+    /// A digest added with [`Self::with_content_digest`] covers the bytes of the range, which is
+    /// what a `Content-Digest` of a partial response is. A second range replaces the first.
+    pub fn with_range(mut self, range: ByteRange) -> Self {
+        let mut lock = self.inner.lock().unwrap_or_else(|err| err.into_inner());
+        if let BodyStreamState::Ready(inner) = &mut *lock {
+            inner.window = Some(BodyStreamWindow::new(range));
+            self.content_length = Some(range.get_length());
+        }
+        drop(lock);
+        self
+    }
+
+    fn add_digest(&self, digest: Arc<BodyStreamDigest>) {
+        let mut lock = self.inner.lock().unwrap_or_else(|err| err.into_inner());
+        if let BodyStreamState::Ready(inner) = &mut *lock {
+            inner.digests.push(digest);
         }
     }
 
@@ -240,6 +340,11 @@ pub struct HttpBodyReader {
     rx: tokio::sync::Mutex<tokio::sync::mpsc::Receiver<Result<Vec<u8>, HttpParseError>>>,
     completed: Arc<AtomicBool>,
     content_length: Option<u64>,
+    digests: Vec<Arc<BodyStreamDigest>>,
+    window: Option<BodyStreamWindow>,
+    /// The first error; the body ends there. [`get_next_chunk`](Self::get_next_chunk) returns it
+    /// again on every later call, [`poll_next_chunk`](Self::poll_next_chunk) is fused after it.
+    error: std::sync::Mutex<Option<HttpParseError>>,
 }
 
 impl HttpBodyReader {
@@ -249,20 +354,58 @@ impl HttpBodyReader {
     /// *and* when every sender was dropped because the pump died. Treating the second case as EOF
     /// would silently truncate the body — data corruption that looks like success. Hence the
     /// `completed` flag, which only [`HttpBodyStreamSender::finish`] sets: a channel that closed
-    /// without it is an abort, and it is reported as [`HttpParseError::BodyStream`].
+    /// without it is an abort, and it is reported as [`HttpParseError::body_stream_truncated`].
+    ///
+    /// An error is sticky: the body ends with it, and every later call returns the same error
+    /// again — never an `Ok(None)` a caller could take for a complete body.
     pub async fn get_next_chunk(&self) -> Result<Option<Vec<u8>>, HttpParseError> {
         let mut rx = self.rx.lock().await;
 
-        match rx.recv().await {
-            Some(Ok(chunk)) => Ok(Some(chunk)),
-            Some(Err(err)) => Err(err),
-            None => {
-                if self.completed.load(Ordering::Acquire) {
-                    Ok(None)
-                } else {
-                    Err(HttpParseError::BodyStream(
-                        "Request body stream ended unexpectedly".to_string(),
-                    ))
+        if let Some(err) = self.get_error() {
+            return Err(err);
+        }
+
+        let result = self.read_next_chunk(&mut rx).await;
+        if let Err(err) = &result {
+            *self.error.lock().unwrap_or_else(|err| err.into_inner()) = Some(err.clone());
+            rx.close();
+        }
+        result
+    }
+
+    fn get_error(&self) -> Option<HttpParseError> {
+        self.error
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    async fn read_next_chunk(
+        &self,
+        rx: &mut tokio::sync::mpsc::Receiver<Result<Vec<u8>, HttpParseError>>,
+    ) -> Result<Option<Vec<u8>>, HttpParseError> {
+        loop {
+            if self.is_window_filled() {
+                rx.close();
+                self.finish_digests()?;
+                return Ok(None);
+            }
+
+            match rx.recv().await {
+                Some(Ok(chunk)) => {
+                    let Some(chunk) = self.cut_chunk(chunk) else {
+                        continue;
+                    };
+                    self.digest_chunk(&chunk);
+                    return Ok(Some(chunk));
+                }
+                Some(Err(err)) => return Err(err),
+                None => {
+                    if !self.is_complete() {
+                        return Err(HttpParseError::body_stream_truncated());
+                    }
+                    self.finish_digests()?;
+                    return Ok(None);
                 }
             }
         }
@@ -279,22 +422,62 @@ impl HttpBodyReader {
     /// Semantics are identical to [`get_next_chunk`](Self::get_next_chunk):
     /// `Poll::Ready(None)` means the body arrived **in full** (the `completed` flag is set), and a
     /// channel closed without [`HttpBodyStreamSender::finish`] is an abort reported as
-    /// [`HttpParseError::BodyStream`] — never a silent EOF.
+    /// [`HttpParseError::body_stream_truncated`] — never a silent EOF. The one difference is what
+    /// follows an error: as a `Stream` does, it is polled once, and the stream is
+    /// `Poll::Ready(None)` from then on.
     pub fn poll_next_chunk(
         &mut self,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Result<Vec<u8>, HttpParseError>>> {
-        // `get_mut` — a `&mut self` proves nobody else holds the mutex, so there is nothing to lock.
-        match self.rx.get_mut().poll_recv(cx) {
-            std::task::Poll::Pending => std::task::Poll::Pending,
-            std::task::Poll::Ready(Some(item)) => std::task::Poll::Ready(Some(item)),
-            std::task::Poll::Ready(None) => {
-                if self.completed.load(Ordering::Acquire) {
-                    std::task::Poll::Ready(None)
-                } else {
-                    std::task::Poll::Ready(Some(Err(HttpParseError::BodyStream(
-                        "Request body stream ended unexpectedly".to_string(),
-                    ))))
+        let error = self.error.get_mut().unwrap_or_else(|err| err.into_inner());
+        if error.is_some() {
+            return std::task::Poll::Ready(None);
+        }
+
+        let result = self.poll_chunk(cx);
+        if let std::task::Poll::Ready(Some(Err(err))) = &result {
+            *self.error.get_mut().unwrap_or_else(|err| err.into_inner()) = Some(err.clone());
+            self.rx.get_mut().close();
+        }
+        result
+    }
+
+    fn poll_chunk(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Result<Vec<u8>, HttpParseError>>> {
+        loop {
+            if self.is_window_filled() {
+                self.rx.get_mut().close();
+                return match self.finish_digests() {
+                    Ok(()) => std::task::Poll::Ready(None),
+                    Err(err) => std::task::Poll::Ready(Some(Err(err))),
+                };
+            }
+
+            // `get_mut` — a `&mut self` proves nobody else holds the mutex, so there is nothing to
+            // lock.
+            match self.rx.get_mut().poll_recv(cx) {
+                std::task::Poll::Pending => return std::task::Poll::Pending,
+                std::task::Poll::Ready(Some(Ok(chunk))) => {
+                    let Some(chunk) = self.cut_chunk(chunk) else {
+                        continue;
+                    };
+                    self.digest_chunk(&chunk);
+                    return std::task::Poll::Ready(Some(Ok(chunk)));
+                }
+                std::task::Poll::Ready(Some(Err(err))) => {
+                    return std::task::Poll::Ready(Some(Err(err)))
+                }
+                std::task::Poll::Ready(None) => {
+                    if !self.is_complete() {
+                        let err = HttpParseError::body_stream_truncated();
+                        return std::task::Poll::Ready(Some(Err(err)));
+                    }
+                    return match self.finish_digests() {
+                        Ok(()) => std::task::Poll::Ready(None),
+                        Err(err) => std::task::Poll::Ready(Some(Err(err))),
+                    };
                 }
             }
         }
@@ -305,8 +488,42 @@ impl HttpBodyReader {
         self.content_length
     }
 
+    /// The sender finished, and — for a [`HttpBodyAsStream::with_range`] stream — the range was
+    /// filled before it did.
+    fn is_complete(&self) -> bool {
+        self.completed.load(Ordering::Acquire)
+            && self.window.as_ref().is_none_or(BodyStreamWindow::is_filled)
+    }
+
+    fn is_window_filled(&self) -> bool {
+        self.window
+            .as_ref()
+            .is_some_and(BodyStreamWindow::is_filled)
+    }
+
+    fn cut_chunk(&self, chunk: Vec<u8>) -> Option<Vec<u8>> {
+        match &self.window {
+            Some(window) => window.cut(chunk),
+            None => Some(chunk),
+        }
+    }
+
+    fn digest_chunk(&self, chunk: &[u8]) {
+        for digest in &self.digests {
+            digest.update(chunk);
+        }
+    }
+
+    /// Completes the digests of a body that has ended in full, and checks the expected ones.
+    fn finish_digests(&self) -> Result<(), HttpParseError> {
+        for digest in &self.digests {
+            digest.finish()?;
+        }
+        Ok(())
+    }
+
     /// Reads the rest of the body into memory. `max_size` is the safety valve — going over it
-    /// gives [`HttpParseError::BodyStream`] instead of an unbounded allocation. `None` means no
+    /// gives [`HttpParseError::body_too_large`] instead of an unbounded allocation. `None` means no
     /// limit, so only pass it where the source is trusted.
     ///
     /// This is also how the transport layer can implement "just give me the whole body".
@@ -316,10 +533,7 @@ impl HttpBodyReader {
         while let Some(chunk) = self.get_next_chunk().await? {
             if let Some(max_size) = max_size {
                 if result.len() + chunk.len() > max_size {
-                    return Err(HttpParseError::BodyStream(format!(
-                        "Request body is bigger than the allowed {} bytes",
-                        max_size
-                    )));
+                    return Err(HttpParseError::body_too_large(max_size));
                 }
             }
 
@@ -426,6 +640,11 @@ mod tests {
             reader.get_next_chunk().await,
             Err(HttpParseError::BodyStream(_))
         ));
+        // Sticky: a later read must not pass for the end of a complete body.
+        assert!(matches!(
+            reader.get_next_chunk().await,
+            Err(HttpParseError::BodyStream(_))
+        ));
     }
 
     #[tokio::test]
@@ -0,0 +1,254 @@
+//! [`ClientInfo`]: who the request really came from — its address, scheme, host and port as the
+//! client saw them — for a `#[http_client_info]` field.
+//!
+//! Behind a reverse proxy the peer of the connection is the proxy, and the client only shows up in
+//! the forwarding headers it adds (`Forwarded`, RFC 7239, or the de-facto `X-Forwarded-*` /
+//! `X-Real-IP`). Anyone can send those headers, so they are only believed when they were added by
+//! a proxy in the [`TrustedProxies`] list registered for the process
+//! ([`register_trusted_proxies`]) — a field is parsed with no context but the request.
+
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+/// The client of a request: `ip` is `None` when it cannot be told (a transport that does not
+/// surface the peer address, a trusted proxy that forwarded `unknown`); `scheme` is `None` when no
+/// trusted proxy said, and `host` / `port` fall back to the `Host` header.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientInfo {
+    pub ip: Option<IpAddr>,
+    pub scheme: Option<String>,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+}
+
+/// A CIDR block — `10.0.0.0/8`, `fd00::/8` — or, without a prefix length, a single address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpNetwork {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Self, String> {
+        let max_len = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+
+        if prefix_len > max_len {
+            return Err(format!(
+                "Prefix length {} is out of range for {}",
+                prefix_len, addr
+            ));
+        }
+
+        Ok(Self { addr, prefix_len })
+    }
+
+    pub fn get_addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    pub fn get_prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// An IPv4 address the socket reports as IPv4-mapped IPv6 (`::ffff:10.0.0.1`) matches the
+    /// IPv4 block it maps to.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                prefix_matches(&network.octets(), &ip.octets(), self.prefix_len)
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                prefix_matches(&network.octets(), &ip.octets(), self.prefix_len)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prefix_matches(network: &[u8], ip: &[u8], prefix_len: u8) -> bool {
+    let full_bytes = (prefix_len / 8) as usize;
+
+    if network[..full_bytes] != ip[..full_bytes] {
+        return false;
+    }
+
+    let rest_bits = prefix_len % 8;
+    if rest_bits == 0 {
+        return true;
+    }
+
+    let mask = 0xffu8 << (8 - rest_bits);
+    network[full_bytes] & mask == ip[full_bytes] & mask
+}
+
+impl FromStr for IpNetwork {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let src = src.trim();
+
+        let (addr, prefix_len) = match src.split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (src, None),
+        };
+
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|_| format!("'{}' is not an IP network", src))?;
+
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len
+                .parse()
+                .map_err(|_| format!("'{}' is not an IP network", src))?,
+            None if addr.is_ipv4() => 32,
+            None => 128,
+        };
+
+        Self::new(addr, prefix_len)
+    }
+}
+
+impl std::fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+/// The proxies whose forwarding headers are believed. Empty by default: the client is then the
+/// peer of the connection, whatever the headers say.
+///
+/// Parses from a comma-separated list, so it can come straight out of the settings:
+/// `"10.0.0.0/8, 127.0.0.1"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedProxies {
+    networks: Vec<IpNetwork>,
+    any: bool,
+}
+
+impl TrustedProxies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trusts every hop. Only for a server that nothing but its own proxies can reach — and the
+    /// one choice for a transport that does not surface the peer address.
+    pub fn any() -> Self {
+        Self {
+            networks: Vec::new(),
+            any: true,
+        }
+    }
+
+    pub fn with_network(mut self, network: IpNetwork) -> Self {
+        self.networks.push(network);
+        self
+    }
+
+    pub fn get_networks(&self) -> &[IpNetwork] {
+        &self.networks
+    }
+
+    /// Whether a hop is trusted; an unknown peer (`None`) only is when every hop is.
+    pub fn is_trusted(&self, ip: Option<IpAddr>) -> bool {
+        if self.any {
+            return true;
+        }
+
+        match ip {
+            Some(ip) => self.networks.iter().any(|network| network.contains(ip)),
+            None => false,
+        }
+    }
+}
+
+impl FromStr for TrustedProxies {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::new();
+
+        for network in src.split(',') {
+            if network.trim().is_empty() {
+                continue;
+            }
+
+            result.networks.push(network.parse()?);
+        }
+
+        Ok(result)
+    }
+}
+
+static TRUSTED_PROXIES: RwLock<Option<Arc<TrustedProxies>>> = RwLock::new(None);
+
+/// Sets the proxies every [`ClientInfo`] field of the process is read behind, replacing the ones
+/// registered before. Until they are, no forwarding header is believed.
+pub fn register_trusted_proxies(trusted_proxies: TrustedProxies) {
+    *TRUSTED_PROXIES.write().unwrap() = Some(Arc::new(trusted_proxies));
+}
+
+#[cfg(feature = "server")]
+pub(crate) fn get_registered_trusted_proxies() -> Arc<TrustedProxies> {
+    TRUSTED_PROXIES.read().unwrap().clone().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(src: &str) -> IpAddr {
+        src.parse().unwrap()
+    }
+
+    #[test]
+    fn ipv4_network_matches_by_prefix() {
+        let network: IpNetwork = "10.1.0.0/16".parse().unwrap();
+
+        assert!(network.contains(ip("10.1.255.3")));
+        assert!(!network.contains(ip("10.2.0.1")));
+        assert!(!network.contains(ip("::1")));
+    }
+
+    #[test]
+    fn prefix_not_on_a_byte_boundary() {
+        let network: IpNetwork = "192.168.4.0/22".parse().unwrap();
+
+        assert!(network.contains(ip("192.168.7.200")));
+        assert!(!network.contains(ip("192.168.8.1")));
+    }
+
+    #[test]
+    fn ipv6_network_and_mapped_ipv4() {
+        let network: IpNetwork = "fd00::/8".parse().unwrap();
+        assert!(network.contains(ip("fd12:3456::1")));
+        assert!(!network.contains(ip("fe80::1")));
+
+        let network: IpNetwork = "127.0.0.1".parse().unwrap();
+        assert_eq!(network.get_prefix_len(), 32);
+        assert!(network.contains(ip("::ffff:127.0.0.1")));
+    }
+
+    #[test]
+    fn invalid_networks_are_rejected() {
+        assert!("10.0.0.0/33".parse::<IpNetwork>().is_err());
+        assert!("10.0.0/8".parse::<IpNetwork>().is_err());
+        assert!("localhost".parse::<IpNetwork>().is_err());
+    }
+
+    #[test]
+    fn trusted_proxies_from_a_list() {
+        let proxies: TrustedProxies = "10.0.0.0/8, 127.0.0.1,".parse().unwrap();
+
+        assert_eq!(proxies.get_networks().len(), 2);
+        assert!(proxies.is_trusted(Some(ip("10.20.30.40"))));
+        assert!(proxies.is_trusted(Some(ip("127.0.0.1"))));
+        assert!(!proxies.is_trusted(Some(ip("127.0.0.2"))));
+        assert!(!proxies.is_trusted(None));
+
+        assert!(TrustedProxies::any().is_trusted(None));
+    }
+}
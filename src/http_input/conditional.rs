@@ -0,0 +1,669 @@
+//! Conditional requests (RFC 9110 §13): [`EntityTag`] for `ETag`, [`ETagCondition`] for
+//! `If-Match` / `If-None-Match`, [`HttpDate`] for `Last-Modified`, `If-Modified-Since` and
+//! `If-Unmodified-Since`, and [`Preconditions`] — which decides whether a request proceeds, is
+//! answered `304 Not Modified` or fails with `412 Precondition Failed`.
+//!
+//! Like the `Authorization` types, each one keeps its header value as it goes on the wire, so the
+//! client request builder writes a field of it back through `as_str()`.
+
+use std::str::FromStr;
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::date_time_format::{civil_from_days, days_from_civil, days_in_month};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+/// An entity tag: `"xyzzy"`, or `W/"xyzzy"` for a weak one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityTag {
+    weak: bool,
+    header: String,
+}
+
+impl EntityTag {
+    /// A strong tag; `tag` is the opaque part, without the quotes.
+    pub fn strong(tag: &str) -> Result<Self, String> {
+        Self::new(false, tag)
+    }
+
+    /// A weak tag: two representations that carry it are equivalent, not byte for byte equal.
+    pub fn weak(tag: &str) -> Result<Self, String> {
+        Self::new(true, tag)
+    }
+
+    fn new(weak: bool, tag: &str) -> Result<Self, String> {
+        if !tag.chars().all(is_etag_char) {
+            return Err(format!("'{}' is not a valid entity tag", tag));
+        }
+
+        let header = if weak {
+            format!("W/\"{}\"", tag)
+        } else {
+            format!("\"{}\"", tag)
+        };
+
+        Ok(Self { weak, header })
+    }
+
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// The opaque part, without `W/` and the quotes.
+    pub fn get_tag(&self) -> &str {
+        let quoted = if self.weak {
+            &self.header[2..]
+        } else {
+            self.header.as_str()
+        };
+
+        &quoted[1..quoted.len() - 1]
+    }
+
+    /// The strong comparison: both tags are strong and the same.
+    pub fn strong_eq(&self, other: &EntityTag) -> bool {
+        !self.weak && !other.weak && self.get_tag() == other.get_tag()
+    }
+
+    /// The weak comparison: the same tag, weak or not.
+    pub fn weak_eq(&self, other: &EntityTag) -> bool {
+        self.get_tag() == other.get_tag()
+    }
+
+    /// The header value, as it goes on the wire.
+    pub fn as_str(&self) -> &str {
+        self.header.as_str()
+    }
+}
+
+impl FromStr for EntityTag {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let mut rest = src.trim();
+        let tag = read_entity_tag(&mut rest)?;
+
+        if !rest.is_empty() {
+            return Err(format!("'{}' is not a valid entity tag", src));
+        }
+
+        Ok(tag)
+    }
+}
+
+/// `If-Match` / `If-None-Match`: `*`, or a list of entity tags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ETagCondition {
+    tags: Vec<EntityTag>,
+    header: String,
+}
+
+impl ETagCondition {
+    /// `*` — any current representation.
+    pub fn any() -> Self {
+        Self {
+            tags: Vec::new(),
+            header: "*".to_string(),
+        }
+    }
+
+    pub fn from_tags(tags: Vec<EntityTag>) -> Result<Self, String> {
+        if tags.is_empty() {
+            return Err("An entity tag list can not be empty".to_string());
+        }
+
+        let header = tags
+            .iter()
+            .map(|tag| tag.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Ok(Self { tags, header })
+    }
+
+    pub fn is_any(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// The listed tags; empty for `*`.
+    pub fn get_tags(&self) -> &[EntityTag] {
+        &self.tags
+    }
+
+    /// Whether the current tag is listed, compared strongly — as `If-Match` compares. `*`
+    /// matches any tag.
+    pub fn matches_strong(&self, current: &EntityTag) -> bool {
+        self.is_any() || self.tags.iter().any(|tag| tag.strong_eq(current))
+    }
+
+    /// Whether the current tag is listed, compared weakly — as `If-None-Match` compares. `*`
+    /// matches any tag.
+    pub fn matches_weak(&self, current: &EntityTag) -> bool {
+        self.is_any() || self.tags.iter().any(|tag| tag.weak_eq(current))
+    }
+
+    /// The header value, as it goes on the wire.
+    pub fn as_str(&self) -> &str {
+        self.header.as_str()
+    }
+}
+
+impl FromStr for ETagCondition {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        if src.trim() == "*" {
+            return Ok(Self::any());
+        }
+
+        // An entity tag may contain a comma itself, so the list is read tag by tag rather than
+        // split at the commas.
+        let mut rest = src.trim();
+        let mut tags = Vec::new();
+
+        loop {
+            rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+            if rest.is_empty() {
+                break;
+            }
+
+            tags.push(read_entity_tag(&mut rest)?);
+
+            rest = rest.trim_start();
+            if !rest.is_empty() && !rest.starts_with(',') {
+                return Err(format!("'{}' is not a list of entity tags", src));
+            }
+        }
+
+        Self::from_tags(tags)
+    }
+}
+
+/// Reads one entity tag off the front of `src`.
+fn read_entity_tag(src: &mut &str) -> Result<EntityTag, String> {
+    let (weak, quoted) = match src.strip_prefix("W/") {
+        Some(quoted) => (true, quoted),
+        None => (false, *src),
+    };
+
+    let Some(quoted) = quoted.strip_prefix('"') else {
+        return Err(format!("'{}' is not a quoted entity tag", src));
+    };
+
+    let Some((tag, rest)) = quoted.split_once('"') else {
+        return Err(format!("'{}' is missing its closing quote", src));
+    };
+
+    let tag = EntityTag::new(weak, tag)?;
+    *src = rest;
+    Ok(tag)
+}
+
+/// `etagc`: `!`, `#`..`~` and anything beyond ASCII — no space, no `"`.
+fn is_etag_char(c: char) -> bool {
+    c == '!' || ('#'..='~').contains(&c) || !c.is_ascii()
+}
+
+/// An HTTP-date, to the second: written as an IMF-fixdate (`Sun, 06 Nov 1994 08:49:37 GMT`),
+/// read in that form or in either obsolete one RFC 9110 still asks a recipient to accept —
+/// RFC 850 (`Sunday, 06-Nov-94 08:49:37 GMT`, a two-digit year below 70 being 20xx) and asctime
+/// (`Sun Nov  6 08:49:37 1994`). The day of the week is not checked.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HttpDate {
+    unix_seconds: i64,
+    header: String,
+}
+
+impl HttpDate {
+    /// The sub-second part of `value` is dropped: an HTTP-date has none.
+    pub fn new(value: DateTimeAsMicroseconds) -> Self {
+        Self::from_unix_seconds(value.unix_microseconds.div_euclid(1_000_000))
+    }
+
+    pub fn from_unix_seconds(unix_seconds: i64) -> Self {
+        let days = unix_seconds.div_euclid(SECONDS_PER_DAY);
+        let seconds_of_day = unix_seconds.rem_euclid(SECONDS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+
+        let header = format!(
+            "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+            WEEKDAYS[(days + 4).rem_euclid(7) as usize],
+            day,
+            MONTHS[(month - 1) as usize],
+            year,
+            seconds_of_day / 3600,
+            seconds_of_day / 60 % 60,
+            seconds_of_day % 60
+        );
+
+        Self {
+            unix_seconds,
+            header,
+        }
+    }
+
+    pub fn get_unix_seconds(&self) -> i64 {
+        self.unix_seconds
+    }
+
+    pub fn get_date_time(&self) -> DateTimeAsMicroseconds {
+        DateTimeAsMicroseconds::new(self.unix_seconds * 1_000_000)
+    }
+
+    /// The header value, as it goes on the wire.
+    pub fn as_str(&self) -> &str {
+        self.header.as_str()
+    }
+}
+
+impl FromStr for HttpDate {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        parse_http_date(src.trim())
+            .map(Self::from_unix_seconds)
+            .ok_or_else(|| format!("'{}' is not an HTTP-date", src))
+    }
+}
+
+fn parse_http_date(src: &str) -> Option<i64> {
+    let parts: Vec<&str> = src.split_ascii_whitespace().collect();
+
+    let (year, month, day, time) = match parts.as_slice() {
+        // IMF-fixdate
+        [weekday, day, month, year, time, "GMT"] if weekday.ends_with(',') => (
+            read_number(year, 4)?,
+            read_month(month)?,
+            read_number(day, 2)?,
+            *time,
+        ),
+        // RFC 850
+        [weekday, date, time, "GMT"] if weekday.ends_with(',') => {
+            let mut date = date.split('-');
+            let day = read_number(date.next()?, 2)?;
+            let month = read_month(date.next()?)?;
+            let year = read_number(date.next()?, 2)?;
+            if date.next().is_some() {
+                return None;
+            }
+            let year = if year < 70 { 2000 + year } else { 1900 + year };
+            (year, month, day, *time)
+        }
+        // asctime: the day is space-padded, not zero-padded
+        [_, month, day, time, year] => {
+            if day.is_empty() || day.len() > 2 || !day.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            (
+                read_number(year, 4)?,
+                read_month(month)?,
+                day.parse().ok()?,
+                *time,
+            )
+        }
+        _ => return None,
+    };
+
+    let mut time = time.split(':');
+    let hour = read_number(time.next()?, 2)?;
+    let minute = read_number(time.next()?, 2)?;
+    let second = read_number(time.next()?, 2)?;
+
+    let valid = time.next().is_none()
+        && day >= 1
+        && day <= days_in_month(year, month)
+        && hour < 24
+        && minute < 60
+        && second < 60;
+    if !valid {
+        return None;
+    }
+
+    Some(days_from_civil(year, month, day) * SECONDS_PER_DAY + (hour * 60 + minute) * 60 + second)
+}
+
+/// Exactly `count` ASCII digits.
+fn read_number(src: &str, count: usize) -> Option<i64> {
+    if src.len() != count || !src.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    src.parse().ok()
+}
+
+fn read_month(src: &str) -> Option<i64> {
+    MONTHS
+        .iter()
+        .position(|month| *month == src)
+        .map(|index| index as i64 + 1)
+}
+
+macro_rules! impl_display {
+    ($($t:ty),+) => {
+        $(
+            impl std::fmt::Display for $t {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+        )+
+    };
+}
+
+impl_display!(EntityTag, ETagCondition, HttpDate);
+
+/// What a request's preconditions decide (RFC 9110 §13.2.2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreconditionOutcome {
+    /// Perform the method.
+    Proceed,
+    /// `304 Not Modified`: the client's copy of a `GET` / `HEAD` is current.
+    NotModified,
+    /// `412 Precondition Failed`.
+    PreconditionFailed,
+}
+
+impl PreconditionOutcome {
+    /// The status to answer with; `None` to go on with the request.
+    pub fn get_status_code(&self) -> Option<u16> {
+        match self {
+            Self::Proceed => None,
+            Self::NotModified => Some(304),
+            Self::PreconditionFailed => Some(412),
+        }
+    }
+}
+
+/// The conditional headers of a request — typically the `Option` header fields of its model, or
+/// read straight off the request with `core::read_preconditions`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Preconditions {
+    pub if_match: Option<ETagCondition>,
+    pub if_none_match: Option<ETagCondition>,
+    pub if_modified_since: Option<HttpDate>,
+    pub if_unmodified_since: Option<HttpDate>,
+}
+
+impl Preconditions {
+    /// Evaluates the preconditions against the current state of the resource, in the order of
+    /// RFC 9110 §13.2.2. `current_etag` and `last_modified` are `None` when the resource has none;
+    /// with neither, it is taken not to exist, so `If-Match: *` fails and `If-None-Match: *` holds.
+    pub fn evaluate_preconditions(
+        &self,
+        current_etag: Option<&EntityTag>,
+        last_modified: Option<&HttpDate>,
+        method: &str,
+    ) -> PreconditionOutcome {
+        let exists = current_etag.is_some() || last_modified.is_some();
+        let is_get_or_head =
+            method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD");
+
+        if let Some(if_match) = &self.if_match {
+            let holds = match current_etag {
+                Some(current) => if_match.matches_strong(current),
+                None => if_match.is_any() && exists,
+            };
+            if !holds {
+                return PreconditionOutcome::PreconditionFailed;
+            }
+        } else if let (Some(if_unmodified_since), Some(last_modified)) =
+            (&self.if_unmodified_since, last_modified)
+        {
+            if last_modified > if_unmodified_since {
+                return PreconditionOutcome::PreconditionFailed;
+            }
+        }
+
+        if let Some(if_none_match) = &self.if_none_match {
+            let matches = match current_etag {
+                Some(current) => if_none_match.matches_weak(current),
+                None => if_none_match.is_any() && exists,
+            };
+            if matches {
+                return if is_get_or_head {
+                    PreconditionOutcome::NotModified
+                } else {
+                    PreconditionOutcome::PreconditionFailed
+                };
+            }
+        } else if let (true, Some(if_modified_since), Some(last_modified)) =
+            (is_get_or_head, &self.if_modified_since, last_modified)
+        {
+            if last_modified <= if_modified_since {
+                return PreconditionOutcome::NotModified;
+            }
+        }
+
+        PreconditionOutcome::Proceed
+    }
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use super::{ETagCondition, EntityTag, HttpDate};
+    use crate::schema::data_types::{DataTypeProvider, HttpDataType};
+
+    macro_rules! impl_data_type {
+        ($($t:ty),+) => {
+            $(
+                impl DataTypeProvider for $t {
+                    fn get_data_type() -> HttpDataType {
+                        HttpDataType::as_string()
+                    }
+                }
+            )+
+        };
+    }
+
+    impl_data_type!(EntityTag, ETagCondition, HttpDate);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(src: &str) -> EntityTag {
+        src.parse().unwrap()
+    }
+
+    #[test]
+    fn entity_tags() {
+        let strong = tag(r#""xyzzy""#);
+        assert!(!strong.is_weak());
+        assert_eq!(strong.get_tag(), "xyzzy");
+
+        let weak = tag(r#" W/"xy,zzy" "#);
+        assert!(weak.is_weak());
+        assert_eq!(weak.get_tag(), "xy,zzy");
+        assert_eq!(weak.as_str(), r#"W/"xy,zzy""#);
+
+        assert_eq!(tag(r#""""#).get_tag(), "");
+
+        for src in [
+            "xyzzy",
+            r#"w/"xyzzy""#,
+            r#""xy zzy""#,
+            r#""xyzzy"x"#,
+            r#""xyzzy"#,
+        ] {
+            assert!(src.parse::<EntityTag>().is_err(), "{}", src);
+        }
+    }
+
+    #[test]
+    fn strong_and_weak_comparison() {
+        // RFC 9110 §8.8.3.2
+        let cases = [
+            (r#"W/"1""#, r#"W/"1""#, false, true),
+            (r#"W/"1""#, r#"W/"2""#, false, false),
+            (r#"W/"1""#, r#""1""#, false, true),
+            (r#""1""#, r#""1""#, true, true),
+        ];
+
+        for (one, another, strong, weak) in cases {
+            assert_eq!(
+                tag(one).strong_eq(&tag(another)),
+                strong,
+                "{} {}",
+                one,
+                another
+            );
+            assert_eq!(tag(one).weak_eq(&tag(another)), weak, "{} {}", one, another);
+        }
+    }
+
+    #[test]
+    fn etag_conditions() {
+        let any: ETagCondition = " * ".parse().unwrap();
+        assert!(any.is_any());
+        assert_eq!(any.as_str(), "*");
+
+        let list: ETagCondition = r#""a,b" ,W/"c",, "d""#.parse().unwrap();
+        assert_eq!(list.get_tags().len(), 3);
+        assert_eq!(list.get_tags()[0].get_tag(), "a,b");
+        assert_eq!(list.as_str(), r#""a,b", W/"c", "d""#);
+        assert!(list.matches_weak(&tag(r#""c""#)));
+        assert!(!list.matches_strong(&tag(r#""c""#)));
+        assert!(list.matches_strong(&tag(r#""d""#)));
+
+        for src in ["", ",", r#""a" "b""#, r#""a", *"#] {
+            assert!(src.parse::<ETagCondition>().is_err(), "{}", src);
+        }
+    }
+
+    #[test]
+    fn http_dates_in_all_three_forms() {
+        let expected = 784_111_777;
+
+        for src in [
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+        ] {
+            let date: HttpDate = src.parse().unwrap();
+            assert_eq!(date.get_unix_seconds(), expected, "{}", src);
+            assert_eq!(date.as_str(), "Sun, 06 Nov 1994 08:49:37 GMT");
+        }
+
+        let date = HttpDate::new(DateTimeAsMicroseconds::new(1_709_214_307_123_456));
+        assert_eq!(date.as_str(), "Thu, 29 Feb 2024 13:45:07 GMT");
+        assert_eq!(date.to_string().parse::<HttpDate>().unwrap(), date);
+
+        assert_eq!(
+            "Wednesday, 01-Jan-25 00:00:00 GMT"
+                .parse::<HttpDate>()
+                .unwrap()
+                .as_str(),
+            "Wed, 01 Jan 2025 00:00:00 GMT"
+        );
+
+        for src in [
+            "Sun, 06 Nov 1994 08:49:37 UTC",
+            "Sun, 6 Nov 1994 08:49:37 GMT",
+            "Sun, 06 nov 1994 08:49:37 GMT",
+            "Sun, 29 Feb 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 24:00:00 GMT",
+            "1994-11-06T08:49:37Z",
+        ] {
+            assert!(src.parse::<HttpDate>().is_err(), "{}", src);
+        }
+    }
+
+    #[test]
+    fn preconditions_in_rfc_order() {
+        let current = tag(r#""v2""#);
+        let modified = HttpDate::from_unix_seconds(1_000);
+        let earlier = HttpDate::from_unix_seconds(999);
+
+        let evaluate = |preconditions: Preconditions, method: &str| {
+            preconditions.evaluate_preconditions(Some(&current), Some(&modified), method)
+        };
+
+        let if_match = |src: &str| Preconditions {
+            if_match: Some(src.parse().unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(
+            evaluate(if_match(r#""v2""#), "PUT"),
+            PreconditionOutcome::Proceed
+        );
+        assert_eq!(
+            evaluate(if_match(r#""v1""#), "PUT"),
+            PreconditionOutcome::PreconditionFailed
+        );
+        assert_eq!(
+            evaluate(if_match(r#"W/"v2""#), "PUT"),
+            PreconditionOutcome::PreconditionFailed
+        );
+
+        let if_none_match = |src: &str| Preconditions {
+            if_none_match: Some(src.parse().unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(
+            evaluate(if_none_match(r#"W/"v2""#), "GET"),
+            PreconditionOutcome::NotModified
+        );
+        assert_eq!(
+            evaluate(if_none_match("*"), "PUT"),
+            PreconditionOutcome::PreconditionFailed
+        );
+        assert_eq!(
+            evaluate(if_none_match(r#""v1""#), "GET"),
+            PreconditionOutcome::Proceed
+        );
+
+        // If-Unmodified-Since only counts without If-Match, If-Modified-Since only without
+        // If-None-Match, and only for GET / HEAD.
+        let dates = |if_modified_since: &HttpDate, if_unmodified_since: &HttpDate| Preconditions {
+            if_modified_since: Some(if_modified_since.clone()),
+            if_unmodified_since: Some(if_unmodified_since.clone()),
+            ..Default::default()
+        };
+        assert_eq!(
+            evaluate(dates(&modified, &earlier), "GET"),
+            PreconditionOutcome::PreconditionFailed
+        );
+        assert_eq!(
+            evaluate(dates(&modified, &modified), "HEAD"),
+            PreconditionOutcome::NotModified
+        );
+        assert_eq!(
+            evaluate(dates(&earlier, &modified), "GET"),
+            PreconditionOutcome::Proceed
+        );
+        assert_eq!(
+            evaluate(dates(&modified, &modified), "POST"),
+            PreconditionOutcome::Proceed
+        );
+
+        let both = Preconditions {
+            if_match: Some(r#""v2""#.parse().unwrap()),
+            if_unmodified_since: Some(earlier.clone()),
+            if_none_match: Some(r#""v1""#.parse().unwrap()),
+            if_modified_since: Some(modified.clone()),
+        };
+        assert_eq!(evaluate(both, "GET"), PreconditionOutcome::Proceed);
+
+        // No current representation: `*` fails If-Match and holds for If-None-Match.
+        let create = Preconditions {
+            if_none_match: Some(ETagCondition::any()),
+            ..Default::default()
+        };
+        assert_eq!(
+            create.evaluate_preconditions(None, None, "PUT"),
+            PreconditionOutcome::Proceed
+        );
+        assert_eq!(
+            if_match("*").evaluate_preconditions(None, None, "PUT"),
+            PreconditionOutcome::PreconditionFailed
+        );
+    }
+}
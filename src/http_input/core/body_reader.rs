@@ -1,11 +1,12 @@
 use crate::form_data_reader::FormDataReader;
 use crate::url_encoded_data_reader::UrlEncodedDataReader;
 
-use crate::http_input::{HttpInputValue, HttpParseError};
+use crate::http_input::{HttpInputValue, HttpParseError, Patch};
 
 use super::content_type::BodyContentType;
 use super::data_src::{SRC_BODY, SRC_BODY_JSON, SRC_BODY_URL_ENCODED};
 use super::json_encoded_data::JsonEncodedData;
+use super::strict_body::StrictJson;
 
 enum ParsedBody<'s> {
     UrlEncoded(UrlEncodedDataReader<'s>),
@@ -67,27 +68,100 @@ impl<'s> BodyReader<'s> {
             ParsedBody::UrlEncoded(reader) => reader
                 .get_optional(name)
                 .map(|value| HttpInputValue::from_url_encoded(value, SRC_BODY_URL_ENCODED)),
-            ParsedBody::Json(reader) => reader.get_optional(name).map(|value| HttpInputValue::Json {
-                name,
-                value,
-                src: SRC_BODY_JSON,
-            }),
+            ParsedBody::Json(reader) => reader
+                .get_optional(name)
+                .map(|value| HttpInputValue::from_json(name, value, SRC_BODY_JSON)),
             ParsedBody::FormData(reader) => reader
                 .get_optional(name)
-                .map(|value| HttpInputValue::FormData { name, value }),
+                .map(|value| HttpInputValue::from_form_data(name, value)),
             ParsedBody::Unknown | ParsedBody::Empty => None,
         }
     }
 
+    /// A tri-state read. Only a JSON body can say `null`; a url-encoded or form-data body has
+    /// just absent or a value.
+    pub fn get_patch(&'s self, name: &'static str) -> Patch<HttpInputValue<'s>> {
+        match &self.inner {
+            ParsedBody::Json(reader) => reader
+                .get_patch(name)
+                .map(|value| HttpInputValue::from_json(name, value, SRC_BODY_JSON)),
+            _ => match self.get_optional(name) {
+                Some(value) => Patch::Value(value),
+                None => Patch::Absent,
+            },
+        }
+    }
+
+    /// Rejects a body with members the model does not declare, or repeated ones
+    /// (`#[http_input(strict_body)]`; see [`JsonEncodedData::check_strict`]). A url-encoded or
+    /// form-data body is checked name by name, a `path` member by its dotted path.
+    pub fn check_strict(&self, expected: &StrictJson) -> Result<(), HttpParseError> {
+        match &self.inner {
+            ParsedBody::Json(reader) => reader.check_strict(expected),
+            ParsedBody::UrlEncoded(reader) => {
+                super::strict_body::check_strict_flat_body(reader.get_names(), expected)
+            }
+            ParsedBody::FormData(reader) => {
+                super::strict_body::check_strict_flat_body(reader.get_names(), expected)
+            }
+            ParsedBody::Unknown | ParsedBody::Empty => Ok(()),
+        }
+    }
+
+    /// A view that reads members named by a dotted path (`#[http_body(path = "a.b.c")]`).
+    pub fn nested(&'s self) -> NestedBodyReader<'s> {
+        NestedBodyReader { body: self }
+    }
+
     pub fn get_required(&'s self, name: &'static str) -> Result<HttpInputValue<'s>, HttpParseError> {
         match &self.inner {
             ParsedBody::Unknown => Err(HttpParseError::InvalidBodyFormat(
                 "Body has an unknown format; can not read named fields from it".to_string(),
             )),
             ParsedBody::Empty => Err(HttpParseError::required(name, SRC_BODY)),
+            ParsedBody::Json(_) => self
+                .get_optional(name)
+                .ok_or_else(|| HttpParseError::required(name, SRC_BODY_JSON)),
             _ => self
                 .get_optional(name)
                 .ok_or_else(|| HttpParseError::required(name, SRC_BODY)),
         }
     }
 }
+
+/// Reads body members named by a dotted path, such as `data.attributes.email`. In a JSON body the
+/// path walks nested envelope objects, borrowing each from the body in turn; errors and values
+/// carry the whole path as the member's name. A url-encoded or form-data body has no nesting, so
+/// there the path is looked up as one flat key — the `data.attributes.email=…` a form would send.
+pub struct NestedBodyReader<'s> {
+    body: &'s BodyReader<'s>,
+}
+
+impl<'s> NestedBodyReader<'s> {
+    pub fn get_optional(&self, path: &'static str) -> Option<HttpInputValue<'s>> {
+        match &self.body.inner {
+            ParsedBody::Json(reader) => reader
+                .get_optional_at(path)
+                .map(|value| HttpInputValue::from_json(path, value, SRC_BODY_JSON)),
+            _ => self.body.get_optional(path),
+        }
+    }
+
+    pub fn get_patch(&self, path: &'static str) -> Patch<HttpInputValue<'s>> {
+        match &self.body.inner {
+            ParsedBody::Json(reader) => reader
+                .get_patch_at(path)
+                .map(|value| HttpInputValue::from_json(path, value, SRC_BODY_JSON)),
+            _ => self.body.get_patch(path),
+        }
+    }
+
+    pub fn get_required(&self, path: &'static str) -> Result<HttpInputValue<'s>, HttpParseError> {
+        match &self.body.inner {
+            ParsedBody::Json(_) => self
+                .get_optional(path)
+                .ok_or_else(|| HttpParseError::required(path, SRC_BODY_JSON)),
+            _ => self.body.get_required(path),
+        }
+    }
+}
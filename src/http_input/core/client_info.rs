@@ -0,0 +1,245 @@
+use std::net::{IpAddr, SocketAddr};
+
+use crate::http_input::client_info::get_registered_trusted_proxies;
+use crate::http_input::{ClientInfo, TrustedProxies};
+
+use super::THttpRequest;
+
+/// The [`ClientInfo`] of a request, read behind the proxies registered with
+/// `register_trusted_proxies` — what a `#[http_client_info]` field is parsed with.
+pub fn read_client_info<R: THttpRequest + ?Sized>(request: &R) -> ClientInfo {
+    read_client_info_with(request, &get_registered_trusted_proxies())
+}
+
+/// The [`ClientInfo`] of a request, read behind these proxies.
+///
+/// The forwarding headers are only read when the peer of the connection is a trusted proxy. The
+/// list of hops (`Forwarded: for=…` or `X-Forwarded-For`) is then walked from the right — the hop
+/// the peer added — skipping every trusted proxy; the first hop that is not one is the client. A
+/// `Forwarded` header wins over the `X-Forwarded-*` ones, and `X-Real-IP` is only read when there
+/// is no list of hops at all. Of `X-Forwarded-Proto` / `-Host` / `-Port`, the last value counts:
+/// it is the one the nearest proxy set.
+pub fn read_client_info_with<R: THttpRequest + ?Sized>(
+    request: &R,
+    trusted_proxies: &TrustedProxies,
+) -> ClientInfo {
+    let peer = request.get_peer_ip();
+
+    if !trusted_proxies.is_trusted(peer) {
+        let (host, port) = read_host_header(request);
+        return ClientInfo {
+            ip: peer,
+            scheme: None,
+            host,
+            port,
+        };
+    }
+
+    if let Some(forwarded) = request.get_header("forwarded") {
+        let elements: Vec<ForwardedElement> = split_unquoted(forwarded, ',')
+            .into_iter()
+            .map(ForwardedElement::parse)
+            .collect();
+
+        let hops: Vec<Option<IpAddr>> = elements.iter().map(|element| element.node).collect();
+
+        if let Some(index) = find_client(&hops, trusted_proxies) {
+            let element = &elements[index];
+
+            let (host, port) = match &element.host {
+                Some(host) => split_host_port(host),
+                None => read_host_header(request),
+            };
+
+            return ClientInfo {
+                ip: element.node,
+                scheme: element.proto.clone(),
+                host,
+                port,
+            };
+        }
+    }
+
+    let ip = match request.get_header("x-forwarded-for") {
+        Some(forwarded_for) => {
+            let hops: Vec<Option<IpAddr>> = forwarded_for.split(',').map(parse_node).collect();
+            find_client(&hops, trusted_proxies).and_then(|index| hops[index])
+        }
+        None => match request.get_header("x-real-ip") {
+            Some(real_ip) => parse_node(real_ip),
+            None => peer,
+        },
+    };
+
+    let scheme = last_value(request.get_header("x-forwarded-proto"))
+        .map(|scheme| scheme.to_ascii_lowercase());
+
+    let (host, mut port) = match last_value(request.get_header("x-forwarded-host")) {
+        Some(host) => split_host_port(host),
+        None => read_host_header(request),
+    };
+
+    if let Some(forwarded_port) =
+        last_value(request.get_header("x-forwarded-port")).and_then(|port| port.parse().ok())
+    {
+        port = Some(forwarded_port);
+    }
+
+    ClientInfo {
+        ip,
+        scheme,
+        host,
+        port,
+    }
+}
+
+/// The index of the client in a list of hops: the rightmost one that is not a trusted proxy, or
+/// the leftmost when they all are.
+fn find_client(hops: &[Option<IpAddr>], trusted_proxies: &TrustedProxies) -> Option<usize> {
+    if hops.is_empty() {
+        return None;
+    }
+
+    for index in (1..hops.len()).rev() {
+        if !trusted_proxies.is_trusted(hops[index]) {
+            return Some(index);
+        }
+    }
+
+    Some(0)
+}
+
+/// One element of `Forwarded` — the pairs one proxy added. `by` is not read.
+struct ForwardedElement {
+    node: Option<IpAddr>,
+    proto: Option<String>,
+    host: Option<String>,
+}
+
+impl ForwardedElement {
+    fn parse(src: &str) -> Self {
+        let mut result = Self {
+            node: None,
+            proto: None,
+            host: None,
+        };
+
+        for pair in split_unquoted(src, ';') {
+            let Some((name, value)) = pair.split_once('=') else {
+                continue;
+            };
+
+            let value = unquote(value.trim());
+
+            match name.trim().to_ascii_lowercase().as_str() {
+                "for" => result.node = parse_node(&value),
+                "proto" => result.proto = Some(value.to_ascii_lowercase()),
+                "host" => result.host = Some(value),
+                _ => {}
+            }
+        }
+
+        result
+    }
+}
+
+/// A hop as a proxy writes it: `192.0.2.60`, `2001:db8::1`, with a port (`192.0.2.60:4711`,
+/// `[2001:db8::1]:4711`) or in brackets alone. `unknown` and an obfuscated `_hidden` are `None`.
+fn parse_node(src: &str) -> Option<IpAddr> {
+    let src = src.trim();
+
+    if let Ok(ip) = src.parse::<IpAddr>() {
+        return Some(ip);
+    }
+
+    if let Ok(addr) = src.parse::<SocketAddr>() {
+        return Some(addr.ip());
+    }
+
+    src.strip_prefix('[')?.strip_suffix(']')?.parse().ok()
+}
+
+fn read_host_header<R: THttpRequest + ?Sized>(request: &R) -> (Option<String>, Option<u16>) {
+    match request.get_header("host") {
+        Some(host) => split_host_port(host),
+        None => (None, None),
+    }
+}
+
+/// `example.com:8080` → (`example.com`, `8080`); an IPv6 literal keeps its brackets.
+fn split_host_port(src: &str) -> (Option<String>, Option<u16>) {
+    let src = src.trim();
+
+    if src.is_empty() {
+        return (None, None);
+    }
+
+    if let Some((host, port)) = src.rsplit_once(':') {
+        if !host.contains(':') || host.ends_with(']') {
+            if let Ok(port) = port.parse() {
+                return (Some(host.to_string()), Some(port));
+            }
+        }
+    }
+
+    (Some(src.to_string()), None)
+}
+
+fn last_value(src: Option<&str>) -> Option<&str> {
+    let value = src?.rsplit(',').next()?.trim();
+
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Splits at `separator`, except inside a quoted string.
+fn split_unquoted(src: &str, separator: char) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (index, c) in src.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+
+        match c {
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            c if c == separator && !in_quotes => {
+                result.push(&src[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    result.push(&src[start..]);
+    result
+}
+
+fn unquote(src: &str) -> String {
+    let Some(quoted) = src.strip_prefix('"').and_then(|src| src.strip_suffix('"')) else {
+        return src.to_string();
+    };
+
+    let mut result = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                result.push(escaped);
+            }
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
@@ -0,0 +1,35 @@
+use crate::body::{ContentDigest, CONTENT_DIGEST_HEADER};
+use crate::http_input::HttpParseError;
+
+use super::data_src::SRC_HEADER;
+use super::THttpRequest;
+
+/// The `Content-Digest` the request was sent with — its strongest supported member. A streamed
+/// body is checked against it with `HttpBodyAsStream::verify_content_digest`:
+///
+/// ```ignore
+/// let expected = read_content_digest(request)?;
+/// let reader = input.body.verify_content_digest(expected).get_body_reader()?;
+/// ```
+pub fn read_content_digest<R: THttpRequest + ?Sized>(
+    request: &R,
+) -> Result<ContentDigest, HttpParseError> {
+    let value = request
+        .get_header(CONTENT_DIGEST_HEADER)
+        .ok_or_else(|| HttpParseError::required(CONTENT_DIGEST_HEADER, SRC_HEADER))?;
+
+    ContentDigest::parse_header(value)
+        .map_err(|_| HttpParseError::cannot_parse(CONTENT_DIGEST_HEADER, SRC_HEADER, value))
+}
+
+/// Checks the body of the request against its `Content-Digest`; a body that does not match fails
+/// with [`HttpParseError::Validation`].
+pub fn check_content_digest<R: THttpRequest + ?Sized>(request: &R) -> Result<(), HttpParseError> {
+    if read_content_digest(request)?.matches(request.get_body()) {
+        Ok(())
+    } else {
+        Err(HttpParseError::Validation(
+            "Content-Digest does not match the body".to_string(),
+        ))
+    }
+}
@@ -26,17 +26,18 @@ pub fn to_simple_value<T: FromStr>(
     }
 }
 
+/// A boolean in the vocabulary of `profile` (see [`crate::http_input::ParseProfile::parse_bool`]).
 #[cfg(feature = "server")]
-pub fn to_bool(name: &str, value: &str, src: &'static str) -> Result<bool, HttpParseError> {
-    if value == "1" || value.eq_ignore_ascii_case("true") {
-        return Ok(true);
-    }
-
-    if value == "0" || value.eq_ignore_ascii_case("false") {
-        return Ok(false);
+pub fn to_bool(
+    name: &str,
+    value: &str,
+    profile: crate::http_input::ParseProfile,
+    src: &'static str,
+) -> Result<bool, HttpParseError> {
+    match profile.parse_bool(value) {
+        Some(result) => Ok(result),
+        None => Err(HttpParseError::cannot_parse(name, src, value)),
     }
-
-    Err(HttpParseError::cannot_parse(name, src, value))
 }
 
 #[cfg(feature = "server")]
@@ -51,6 +52,20 @@ pub fn to_date_time(
     }
 }
 
+/// A date in an explicit `format = "…"` — see [`crate::DateTimeFormat`].
+#[cfg(feature = "server")]
+pub fn to_formatted_date_time(
+    name: &str,
+    value: &str,
+    format: crate::DateTimeFormat,
+    src: &'static str,
+) -> Result<DateTimeAsMicroseconds, HttpParseError> {
+    match format.parse(value) {
+        Some(result) => Ok(result),
+        None => Err(HttpParseError::cannot_parse(name, src, value)),
+    }
+}
+
 pub fn to_json_from_slice<T: DeserializeOwned>(
     name: &str,
     value: &[u8],
@@ -0,0 +1,300 @@
+use crate::http_input::HttpParseError;
+use crate::schema::in_parameters::HttpInputParameter;
+
+use super::THttpRequest;
+
+/// The request headers a browser sends cross-origin without asking: they never need allowing.
+/// `Content-Type` is not among them — it is safelisted only for the form and `text/plain` media
+/// types, so a JSON body is preflighted and has to be allowed like any other header.
+const SAFELISTED_HEADERS: [&str; 3] = ["accept", "accept-language", "content-language"];
+
+/// The methods a cross-origin request may use without a preflight.
+const SAFELISTED_METHODS: [&str; 3] = ["GET", "HEAD", "POST"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CorsOrigin {
+    Any,
+    Exact(String),
+    /// `https://*.example.com` (`scheme` `Some("https")`) or `*.example.com` — any subdomain on
+    /// any port, the domain itself not included. `suffix` is `.example.com`.
+    Subdomain {
+        scheme: Option<String>,
+        suffix: String,
+    },
+}
+
+impl CorsOrigin {
+    fn parse(src: &str) -> Self {
+        let src = src.trim().to_ascii_lowercase();
+
+        if src == "*" {
+            return Self::Any;
+        }
+
+        let (scheme, host) = match src.split_once("://") {
+            Some((scheme, host)) => (Some(scheme), host),
+            None => (None, src.as_str()),
+        };
+
+        match host.strip_prefix('*') {
+            Some(suffix) if suffix.starts_with('.') => Self::Subdomain {
+                scheme: scheme.map(|scheme| scheme.to_string()),
+                suffix: suffix.to_string(),
+            },
+            _ => Self::Exact(src.trim_end_matches('/').to_string()),
+        }
+    }
+
+    fn matches(&self, origin: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Exact(allowed) => allowed == origin,
+            Self::Subdomain { scheme, suffix } => {
+                let Some((origin_scheme, host)) = origin.split_once("://") else {
+                    return false;
+                };
+
+                if let Some(scheme) = scheme {
+                    if scheme != origin_scheme {
+                        return false;
+                    }
+                }
+
+                let host = match host.rsplit_once(':') {
+                    Some((host, port)) if port.bytes().all(|b| b.is_ascii_digit()) => host,
+                    _ => host,
+                };
+
+                host.len() > suffix.len() && host.ends_with(suffix.as_str())
+            }
+        }
+    }
+}
+
+/// Which cross-origin requests a route accepts, and the response headers that tell the browser so
+/// — evaluated over [`THttpRequest`], with no server behind it.
+///
+/// ```ignore
+/// let cors = CorsPolicy::new()
+///     .with_origin("https://app.example.com")
+///     .with_origin("https://*.example.com")
+///     .with_method("PUT")
+///     .with_headers_of(&UpdateUserInput::get_input_params())
+///     .with_header("Content-Type")
+///     .with_credentials()
+///     .with_max_age_seconds(600);
+///
+/// match cors.evaluate(request) {
+///     Ok(response) => { /* emit response.headers; a preflight ends here with 204 */ }
+///     Err(err) => { /* 403 */ }
+/// }
+/// ```
+///
+/// `GET` / `HEAD` / `POST` and the safelisted request headers (`Accept`, `Accept-Language`,
+/// `Content-Language`) are always allowed; `Content-Type` is not, as a JSON body needs it allowed. A request that is not cross-origin (no
+/// `Origin`) passes with no headers to emit.
+#[derive(Debug, Clone, Default)]
+pub struct CorsPolicy {
+    origins: Vec<CorsOrigin>,
+    methods: Vec<String>,
+    headers: Vec<String>,
+    exposed_headers: Vec<String>,
+    credentials: bool,
+    max_age_seconds: Option<u64>,
+}
+
+impl CorsPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An origin to allow: exact (`https://app.example.com`), any subdomain of a domain
+    /// (`https://*.example.com`, or `*.example.com` for any scheme), or `*` for every origin.
+    /// `*` allows nothing once [`Self::with_credentials`] is on.
+    pub fn with_origin(mut self, origin: &str) -> Self {
+        self.origins.push(CorsOrigin::parse(origin));
+        self
+    }
+
+    pub fn with_method(mut self, method: &str) -> Self {
+        let method = method.trim().to_ascii_uppercase();
+        if !self.methods.contains(&method) {
+            self.methods.push(method);
+        }
+        self
+    }
+
+    pub fn with_header(mut self, name: &str) -> Self {
+        let name = name.trim().to_ascii_lowercase();
+        if !self.headers.contains(&name) {
+            self.headers.push(name);
+        }
+        self
+    }
+
+    /// Allows every header the parameters of a model are read from — the `#[http_header]` fields
+    /// of `Model::get_input_params()`.
+    pub fn with_headers_of(mut self, params: &[HttpInputParameter]) -> Self {
+        for param in params {
+            if param.source.is_header() {
+                self = self.with_header(&param.field.name);
+            }
+        }
+        self
+    }
+
+    /// A response header the page may read (`Access-Control-Expose-Headers`).
+    pub fn with_exposed_header(mut self, name: &str) -> Self {
+        self.exposed_headers.push(name.trim().to_string());
+        self
+    }
+
+    /// Lets the browser send cookies and `Authorization`. Only the origins named explicitly are
+    /// allowed then — a `*` origin is not, since echoing every origin back with credentials would
+    /// let any site make credentialed requests.
+    pub fn with_credentials(mut self) -> Self {
+        self.credentials = true;
+        self
+    }
+
+    /// How long a browser may cache the answer to a preflight.
+    pub fn with_max_age_seconds(mut self, max_age_seconds: u64) -> Self {
+        self.max_age_seconds = Some(max_age_seconds);
+        self
+    }
+
+    pub fn is_origin_allowed(&self, origin: &str) -> bool {
+        let origin = origin.trim().to_ascii_lowercase();
+        self.origins
+            .iter()
+            .filter(|allowed| !self.credentials || **allowed != CorsOrigin::Any)
+            .any(|allowed| allowed.matches(&origin))
+    }
+
+    pub fn is_method_allowed(&self, method: &str) -> bool {
+        let method = method.trim().to_ascii_uppercase();
+        SAFELISTED_METHODS.contains(&method.as_str()) || self.methods.contains(&method)
+    }
+
+    pub fn is_header_allowed(&self, name: &str) -> bool {
+        let name = name.trim().to_ascii_lowercase();
+        SAFELISTED_HEADERS.contains(&name.as_str()) || self.headers.contains(&name)
+    }
+
+    /// Evaluates a preflight (`OPTIONS` with `Access-Control-Request-Method`) or an actual
+    /// cross-origin request. A request the policy does not allow fails with
+    /// [`HttpParseError::Forbidden`] saying why.
+    ///
+    /// A preflight is told apart by its method when [`THttpRequest::get_method`] knows it, and
+    /// by `Access-Control-Request-Method` alone when it does not.
+    pub fn evaluate<R: THttpRequest + ?Sized>(
+        &self,
+        request: &R,
+    ) -> Result<CorsResponse, HttpParseError> {
+        let Some(origin) = request.get_header("origin") else {
+            return Ok(CorsResponse::default());
+        };
+
+        if !self.is_origin_allowed(origin) {
+            return Err(forbidden(format!("Origin '{}' is not allowed", origin)));
+        }
+
+        let requested_method = request.get_header("access-control-request-method");
+        let is_preflight = requested_method.is_some()
+            && match request.get_method() {
+                Some(method) => method.eq_ignore_ascii_case("OPTIONS"),
+                None => true,
+            };
+
+        let mut response = CorsResponse {
+            is_preflight,
+            headers: Vec::new(),
+        };
+
+        // `is_origin_allowed` never lets `*` through with credentials, so there it is the origin.
+        let any_origin = self.origins.contains(&CorsOrigin::Any);
+        if any_origin && !self.credentials {
+            response.add("Access-Control-Allow-Origin", "*".to_string());
+        } else {
+            response.add("Access-Control-Allow-Origin", origin.to_string());
+            response.add("Vary", "Origin".to_string());
+        }
+
+        if self.credentials {
+            response.add("Access-Control-Allow-Credentials", "true".to_string());
+        }
+
+        if !is_preflight {
+            if !self.exposed_headers.is_empty() {
+                response.add(
+                    "Access-Control-Expose-Headers",
+                    self.exposed_headers.join(", "),
+                );
+            }
+
+            return Ok(response);
+        }
+
+        let requested_method = requested_method.unwrap_or_default().trim();
+        if !self.is_method_allowed(requested_method) {
+            return Err(forbidden(format!(
+                "Method '{}' is not allowed",
+                requested_method
+            )));
+        }
+
+        let mut requested_headers = Vec::new();
+        if let Some(src) = request.get_header("access-control-request-headers") {
+            for name in src
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+            {
+                if !self.is_header_allowed(name) {
+                    return Err(forbidden(format!("Header '{}' is not allowed", name)));
+                }
+                requested_headers.push(name.to_ascii_lowercase());
+            }
+        }
+
+        response.add(
+            "Access-Control-Allow-Methods",
+            requested_method.to_ascii_uppercase(),
+        );
+
+        if !requested_headers.is_empty() {
+            response.add("Access-Control-Allow-Headers", requested_headers.join(", "));
+        }
+
+        if let Some(max_age_seconds) = self.max_age_seconds {
+            response.add("Access-Control-Max-Age", max_age_seconds.to_string());
+        }
+
+        Ok(response)
+    }
+}
+
+/// What [`CorsPolicy::evaluate`] lets through: the headers to add to the response. A preflight
+/// is answered with them alone (`204 No Content`) and does not reach the handler.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorsResponse {
+    pub is_preflight: bool,
+    pub headers: Vec<(&'static str, String)>,
+}
+
+impl CorsResponse {
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn add(&mut self, name: &'static str, value: String) {
+        self.headers.push((name, value));
+    }
+}
+
+fn forbidden(reason: String) -> HttpParseError {
+    HttpParseError::Forbidden(reason)
+}
@@ -12,3 +12,19 @@ pub const SRC_QUERY_STRING: &str = "QueryString";
 pub const SRC_HEADER: &str = "Header";
 pub const SRC_FORM_DATA: &str = "FormData";
 pub const SRC_PATH: &str = "Path";
+
+/// The constant spelled `name` — how an error read back from its JSON form gets its `&'static`
+/// source again.
+pub fn find(name: &str) -> Option<&'static str> {
+    [
+        SRC_BODY,
+        SRC_BODY_JSON,
+        SRC_BODY_URL_ENCODED,
+        SRC_QUERY_STRING,
+        SRC_HEADER,
+        SRC_FORM_DATA,
+        SRC_PATH,
+    ]
+    .into_iter()
+    .find(|src| *src == name)
+}
@@ -1,7 +1,7 @@
 use serde::de::DeserializeOwned;
 
 use crate::http_input::core::data_src::SRC_BODY;
-use crate::http_input::{HttpParseError, RawData, RawDataTyped};
+use crate::http_input::{HttpParseError, JsonMergePatch, JsonPatch, RawData, RawDataTyped};
 
 /// Builds a non-`Option` `#[http_body_raw]` field out of the whole request body (the `Vec<u8>`
 /// returned by [`super::read_raw_body`]). The derive's server-side `parse` calls it once for the
@@ -16,7 +16,9 @@ use crate::http_input::{HttpParseError, RawData, RawDataTyped};
 /// * `String` — a utf-8 check (same error the old `TryFrom<Vec<u8>>` path produced);
 /// * [`RawData`] — verbatim bytes, no content-type parsing;
 /// * [`RawDataTyped<T>`] — verbatim bytes; the JSON error is deferred to
-///   [`RawDataTyped::deserialize_json`], exactly as before.
+///   [`RawDataTyped::deserialize_json`], exactly as before;
+/// * [`JsonPatch`] / [`JsonMergePatch`] — checked here, so a malformed patch is a 400 with the
+///   pointer of what is wrong rather than a surprise when the handler applies it.
 pub trait FromRawBody: Sized {
     fn from_raw_body(body: Vec<u8>) -> Result<Self, HttpParseError>;
 }
@@ -44,3 +46,15 @@ impl<T: DeserializeOwned> FromRawBody for RawDataTyped<T> {
         Ok(RawDataTyped::new(body, SRC_BODY))
     }
 }
+
+impl FromRawBody for JsonPatch {
+    fn from_raw_body(body: Vec<u8>) -> Result<Self, HttpParseError> {
+        JsonPatch::new(body).map_err(Into::into)
+    }
+}
+
+impl FromRawBody for JsonMergePatch {
+    fn from_raw_body(body: Vec<u8>) -> Result<Self, HttpParseError> {
+        JsonMergePatch::new(body).map_err(Into::into)
+    }
+}
@@ -1,5 +1,6 @@
 use crate::http_input::core::data_src::SRC_BODY_JSON;
-use crate::http_input::HttpParseError;
+use crate::http_input::core::StrictJson;
+use crate::http_input::{HttpParseError, Patch};
 
 use super::JsonEncodedValueAsString;
 
@@ -48,6 +49,67 @@ impl<'s> JsonEncodedData<'s> {
         }
     }
 
+    /// Like [`Self::get_optional`], but keeps a `null` member apart from an absent one — for a
+    /// tri-state (`Patch<T>` / `Option<Option<T>>`) field.
+    pub fn get_patch(&self, name: &'s str) -> Patch<JsonEncodedValueAsString<'s>> {
+        match my_json::j_path::get_value(self.raw, name) {
+            Ok(Some(value)) if value.is_null() => Patch::Null,
+            Ok(Some(value)) => Patch::Value(JsonEncodedValueAsString::new(name, value)),
+            _ => Patch::Absent,
+        }
+    }
+
+    /// Reads a member that sits inside envelope objects, named by its dotted `path`
+    /// (`data.attributes.email`). Each envelope is looked up in the one before it, borrowing its
+    /// bytes from the body the same way a member is borrowed — still zero-copy, still lazy. A
+    /// missing, `null` or non-object envelope reports the member as absent.
+    pub fn get_optional_at(&self, path: &'s str) -> Option<JsonEncodedValueAsString<'s>> {
+        let (raw, key) = self.resolve_envelopes(path)?;
+        match my_json::j_path::get_value(raw, key) {
+            Ok(Some(value)) if !value.is_null() => Some(JsonEncodedValueAsString::new(path, value)),
+            _ => None,
+        }
+    }
+
+    /// [`Self::get_patch`] for a member named by its dotted `path`. An absent envelope is an
+    /// absent member; only the member itself can be `null`.
+    pub fn get_patch_at(&self, path: &'s str) -> Patch<JsonEncodedValueAsString<'s>> {
+        let (raw, key) = match self.resolve_envelopes(path) {
+            Some(found) => found,
+            None => return Patch::Absent,
+        };
+
+        match my_json::j_path::get_value(raw, key) {
+            Ok(Some(value)) if value.is_null() => Patch::Null,
+            Ok(Some(value)) => Patch::Value(JsonEncodedValueAsString::new(path, value)),
+            _ => Patch::Absent,
+        }
+    }
+
+    /// The innermost envelope's bytes and the member's own key, for a dotted `path`.
+    fn resolve_envelopes(&self, path: &'s str) -> Option<(&'s [u8], &'s str)> {
+        let mut raw = self.raw;
+        let mut segments = path.split('.');
+        let mut key = segments.next()?;
+
+        for next in segments {
+            match my_json::j_path::get_value(raw, key) {
+                Ok(Some(value)) if is_object(value.as_slice()) => raw = value.as_slice(),
+                _ => return None,
+            }
+            key = next;
+        }
+
+        Some((raw, key))
+    }
+
+    /// The `#[http_input(strict_body)]` check: every member of the body, at any depth the model
+    /// describes, is one `expected` declares, and no object repeats a key. Unlike the lookups
+    /// above it steps through the whole body, once.
+    pub fn check_strict(&self, expected: &StrictJson) -> Result<(), HttpParseError> {
+        crate::http_input::core::strict_body::check_strict_json_body(self.raw, expected)
+    }
+
     pub fn get_required(
         &self,
         name: &'s str,
@@ -56,3 +118,7 @@ impl<'s> JsonEncodedData<'s> {
             .ok_or_else(|| HttpParseError::required(name, SRC_BODY_JSON))
     }
 }
+
+fn is_object(raw: &[u8]) -> bool {
+    raw.iter().find(|b| **b > b' ') == Some(&b'{')
+}
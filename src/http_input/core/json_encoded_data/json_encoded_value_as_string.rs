@@ -6,7 +6,7 @@ use serde::de::DeserializeOwned;
 
 use crate::http_input::core::convert_from_str;
 use crate::http_input::core::data_src::SRC_BODY_JSON;
-use crate::http_input::HttpParseError;
+use crate::http_input::{HttpParseError, ParseProfile};
 
 /// One named value read out of a JSON body object.
 ///
@@ -54,7 +54,7 @@ impl<'s> JsonEncodedValueAsString<'s> {
     }
 
     /// Textual form of a scalar (string → unescaped; number/bool → verbatim literal). `None` for
-    /// arrays/objects/null. This is what `parse` / `as_bool` / `as_date_time` read.
+    /// arrays/objects/null. This is what `parse` / `as_bool` / `as_date_time` / `as_formatted_date_time` read.
     fn scalar_text(&self) -> Option<Result<String, HttpParseError>> {
         if self.value.is_string() {
             Some(self.as_decoded_string())
@@ -77,23 +77,77 @@ impl<'s> JsonEncodedValueAsString<'s> {
         }
     }
 
-    pub fn as_bool(&self) -> Result<bool, HttpParseError> {
+    /// Whether the member is a string of nothing but whitespace — what a lenient read reports as
+    /// absent.
+    pub fn is_blank(&self) -> bool {
+        self.value.is_string()
+            && self
+                .as_decoded_string()
+                .map(|text| text.trim().is_empty())
+                .unwrap_or(false)
+    }
+
+    /// A strict read takes a JSON number or boolean only as itself, not spelled as a JSON string.
+    fn check_not_quoted(&self, profile: ParseProfile) -> Result<(), HttpParseError> {
+        if !profile.accepts_quoted_scalars() && self.value.is_string() {
+            return Err(HttpParseError::cannot_parse(
+                self.name,
+                SRC_BODY_JSON,
+                self.raw_text(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn as_bool(&self, profile: ParseProfile) -> Result<bool, HttpParseError> {
+        self.check_not_quoted(profile)?;
         match self.scalar_text() {
-            Some(text) => convert_from_str::to_bool(self.name, &text?, SRC_BODY_JSON),
+            Some(text) => convert_from_str::to_bool(self.name, &text?, profile, SRC_BODY_JSON),
             None => Err(HttpParseError::required(self.name, SRC_BODY_JSON)),
         }
     }
 
-    pub fn parse<T: FromStr>(&self) -> Result<T, HttpParseError> {
+    /// A string-carried value (`Uuid`, `DecimalString`, …) or a number.
+    pub fn parse<T: FromStr>(&self, profile: ParseProfile) -> Result<T, HttpParseError> {
         match self.scalar_text() {
-            Some(text) => convert_from_str::to_simple_value(self.name, &text?, SRC_BODY_JSON),
+            Some(text) => {
+                convert_from_str::to_simple_value(self.name, profile.trim(&text?), SRC_BODY_JSON)
+            }
+            None => Err(HttpParseError::required(self.name, SRC_BODY_JSON)),
+        }
+    }
+
+    /// [`Self::parse`] for a number, which a strict read does not take out of a JSON string.
+    pub fn parse_number<T: FromStr>(&self, profile: ParseProfile) -> Result<T, HttpParseError> {
+        self.check_not_quoted(profile)?;
+        self.parse(profile)
+    }
+
+    pub fn as_date_time(
+        &self,
+        profile: ParseProfile,
+    ) -> Result<DateTimeAsMicroseconds, HttpParseError> {
+        match self.scalar_text() {
+            Some(text) => {
+                convert_from_str::to_date_time(self.name, profile.trim(&text?), SRC_BODY_JSON)
+            }
             None => Err(HttpParseError::required(self.name, SRC_BODY_JSON)),
         }
     }
 
-    pub fn as_date_time(&self) -> Result<DateTimeAsMicroseconds, HttpParseError> {
+    pub fn as_formatted_date_time(
+        &self,
+        format: crate::DateTimeFormat,
+        profile: ParseProfile,
+    ) -> Result<DateTimeAsMicroseconds, HttpParseError> {
         match self.scalar_text() {
-            Some(text) => convert_from_str::to_date_time(self.name, &text?, SRC_BODY_JSON),
+            Some(text) => convert_from_str::to_formatted_date_time(
+                self.name,
+                profile.trim(&text?),
+                format,
+                SRC_BODY_JSON,
+            ),
             None => Err(HttpParseError::required(self.name, SRC_BODY_JSON)),
         }
     }
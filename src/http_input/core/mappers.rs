@@ -9,7 +9,11 @@ use serde::de::DeserializeOwned;
 
 use crate::form_data_reader::FormDataItem;
 
-use crate::http_input::{FileContent, HttpInputValue, HttpParseError, RawData, RawDataTyped};
+use crate::http_input::{
+    Base64Bytes, Base64UrlBytes, ContentRange, DecimalString, ETagCondition, EmailAddress,
+    EntityTag, FileContent, HttpDate, HttpFlags, HttpFlagsEnum, HttpInputValue, HttpParseError,
+    IsoDuration, RangeHeader, RawData, RawDataTyped, Uri, Uuid,
+};
 
 impl<'s> TryInto<String> for HttpInputValue<'s> {
     type Error = HttpParseError;
@@ -26,19 +30,28 @@ impl<'s> TryInto<bool> for HttpInputValue<'s> {
 }
 
 macro_rules! impl_try_into_simple {
-    ($($t:ty),+ $(,)?) => {
+    ($parse:ident: $($t:ty),+ $(,)?) => {
         $(
             impl<'s> TryInto<$t> for HttpInputValue<'s> {
                 type Error = HttpParseError;
                 fn try_into(self) -> Result<$t, Self::Error> {
-                    self.parse()
+                    self.$parse()
                 }
             }
         )+
     };
 }
 
-impl_try_into_simple!(u8, i8, u16, i16, u32, i32, u64, i64, usize, isize, f32, f64);
+// Numbers apart from the string-carried types: a strict read refuses a number spelled as a JSON
+// string, while a `Uuid` or a `DecimalString` is a JSON string by definition.
+impl_try_into_simple!(
+    parse_number: u8, i8, u16, i16, u32, i32, u64, i64, i128, u128, usize, isize, f32, f64,
+);
+
+impl_try_into_simple!(
+    parse: DecimalString, Uuid, Uri, EmailAddress, IsoDuration, Base64Bytes, Base64UrlBytes,
+    EntityTag, ETagCondition, HttpDate, RangeHeader, ContentRange,
+);
 
 impl<'s> TryInto<DateTimeAsMicroseconds> for HttpInputValue<'s> {
     type Error = HttpParseError;
@@ -85,6 +98,24 @@ impl<'s, T: DeserializeOwned> TryInto<RawDataTyped<T>> for HttpInputValue<'s> {
     }
 }
 
+/// A JSON member goes through the set's own reader (a number, a name-list string or an array);
+/// every other source is text, read as an integer or a comma-separated list of names.
+impl<'s, T: HttpFlagsEnum> TryInto<HttpFlags<T>> for HttpInputValue<'s> {
+    type Error = HttpParseError;
+    fn try_into(self) -> Result<HttpFlags<T>, Self::Error> {
+        if let HttpInputValue::Json { .. } = &self {
+            return self.read_json_object();
+        }
+
+        let value = self.as_string()?;
+        HttpFlags::parse_str(value.as_str()).map_err(|_| HttpParseError::CanNotParseValue {
+            name: self.get_name().to_string(),
+            src: self.get_src(),
+            value,
+        })
+    }
+}
+
 impl<'s> TryInto<FileContent> for HttpInputValue<'s> {
     type Error = HttpParseError;
     fn try_into(self) -> Result<FileContent, Self::Error> {
@@ -24,27 +24,56 @@ pub mod json_encoded_data;
 #[cfg(feature = "server")]
 mod body_reader;
 #[cfg(feature = "server")]
+mod client_info;
+#[cfg(feature = "server")]
+mod content_digest;
+#[cfg(feature = "server")]
 mod content_type;
 #[cfg(feature = "server")]
+mod cors;
+#[cfg(feature = "server")]
 mod from_raw_body;
 #[cfg(feature = "server")]
+mod http_signature;
+#[cfg(feature = "server")]
 mod mappers;
 #[cfg(feature = "server")]
+mod preconditions;
+#[cfg(feature = "server")]
 mod query_reader;
 #[cfg(feature = "server")]
 mod request;
+#[cfg(feature = "server")]
+mod security;
+#[cfg(feature = "server")]
+mod strict_body;
 
+#[cfg(feature = "server")]
+pub use client_info::{read_client_info, read_client_info_with};
+#[cfg(feature = "server")]
+pub use content_digest::{check_content_digest, read_content_digest};
 #[cfg(feature = "server")]
 pub use content_type::{extract_web_form_boundary, BodyContentType};
 #[cfg(feature = "server")]
-pub use body_reader::BodyReader;
+pub use cors::{CorsPolicy, CorsResponse};
+#[cfg(feature = "server")]
+pub use strict_body::{StrictJson, StrictJsonMember};
+#[cfg(feature = "server")]
+pub use body_reader::{BodyReader, NestedBodyReader};
 #[cfg(feature = "server")]
 pub use from_raw_body::FromRawBody;
 #[cfg(feature = "server")]
+pub use http_signature::HttpSignatureVerifier;
+#[cfg(feature = "server")]
 pub use json_encoded_data::{JsonEncodedData, JsonEncodedValueAsString};
 #[cfg(feature = "server")]
+pub use preconditions::read_preconditions;
+#[cfg(feature = "server")]
 pub use query_reader::QueryStringReader;
 #[cfg(feature = "server")]
+pub use security::check_security;
+#[cfg(feature = "server")]
 pub use request::{
-    read_header_optional, read_header_required, read_path_value, read_raw_body, THttpRequest,
+    read_header_optional, read_header_required, read_json_array_body, read_path_value,
+    read_raw_body, THttpRequest,
 };
@@ -0,0 +1,32 @@
+use crate::http_input::{ETagCondition, HttpParseError, Preconditions};
+
+use super::data_src::SRC_HEADER;
+use super::THttpRequest;
+
+/// The conditional headers of a request, for a handler whose model does not carry them as
+/// fields. A malformed `If-Match` / `If-None-Match` fails with `cannot_parse` — ignoring it would
+/// let a write through unchecked; a date that is not an HTTP-date is ignored, as RFC 9110 asks.
+pub fn read_preconditions<R: THttpRequest + ?Sized>(
+    request: &R,
+) -> Result<Preconditions, HttpParseError> {
+    let read_condition = |name: &str| -> Result<Option<ETagCondition>, HttpParseError> {
+        match request.get_header(name) {
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_: String| HttpParseError::cannot_parse(name, SRC_HEADER, value)),
+            None => Ok(None),
+        }
+    };
+
+    Ok(Preconditions {
+        if_match: read_condition("If-Match")?,
+        if_none_match: read_condition("If-None-Match")?,
+        if_modified_since: request
+            .get_header("If-Modified-Since")
+            .and_then(|value| value.parse().ok()),
+        if_unmodified_since: request
+            .get_header("If-Unmodified-Since")
+            .and_then(|value| value.parse().ok()),
+    })
+}
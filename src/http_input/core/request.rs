@@ -48,6 +48,26 @@ pub trait THttpRequest {
     fn take_body_stream(&self) -> Option<crate::http_input::HttpBodyAsStream> {
         None
     }
+
+    /// The request method, e.g. `POST`. Only [`super::HttpSignatureVerifier`] reads it (for a
+    /// signature that covers `@method`); defaults to `None`.
+    fn get_method(&self) -> Option<&str> {
+        None
+    }
+
+    /// The request target as it came in — path and query, still percent-encoded. Only
+    /// [`super::HttpSignatureVerifier`] reads it (for `@path` / `@query`); defaults to `None`.
+    fn get_path_and_query(&self) -> Option<&str> {
+        None
+    }
+
+    /// The address of the peer of the connection — the client itself, or the proxy in front of
+    /// the server. [`super::read_client_info`] only believes the forwarding headers of a peer in
+    /// the trusted-proxy list; defaults to `None`, which no list but `TrustedProxies::any()`
+    /// trusts.
+    fn get_peer_ip(&self) -> Option<std::net::IpAddr> {
+        None
+    }
 }
 
 /// Reads a required path value (path fields are never `Option`). The raw segment is wrapped as
@@ -89,3 +109,47 @@ pub fn read_header_required<'s, R: THttpRequest + ?Sized>(
 pub fn read_raw_body<R: THttpRequest + ?Sized>(request: &R) -> Vec<u8> {
     request.get_body().to_vec()
 }
+
+/// The whole body as a JSON array of objects, for a `#[http_body_array]` field.
+///
+/// Lazy: the elements are found one at a time by `my-json`'s array iterator, and each one is read
+/// by `T`'s own generated reader straight off its slice of the body — no intermediate tree, no
+/// copy of the element. The body is read as JSON whatever its `Content-Type`, as a raw body is. A
+/// failure names the element it happened in (`/3`).
+pub fn read_json_array_body<'s, T, R>(request: &'s R) -> Result<Vec<T>, HttpParseError>
+where
+    T: crate::FromJsonObject<'s>,
+    R: THttpRequest + ?Sized,
+{
+    let to_error = |err: crate::json_node::JsonSyntaxError| {
+        HttpParseError::InvalidBodyFormat(format!("{} at '{}'", err.message, err.pointer))
+    };
+
+    let mut result = Vec::new();
+
+    for (index, element) in crate::json_node::array_elements(request.get_body())
+        .map_err(to_error)?
+        .enumerate()
+    {
+        let element = element.map_err(to_error)?;
+
+        if element.first() != Some(&b'{') {
+            return Err(HttpParseError::InvalidBodyFormat(format!(
+                "expected an object at '/{}'",
+                index
+            )));
+        }
+
+        let item = T::from_json_object(element).map_err(|err| {
+            HttpParseError::InvalidBodyFormat(format!(
+                "{} at '/{}'",
+                crate::json_node::parse_error_message(&err),
+                index
+            ))
+        })?;
+
+        result.push(item);
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,82 @@
+use crate::http_input::authorization::strip_scheme;
+use crate::http_input::HttpParseError;
+use crate::schema::in_parameters::{
+    HttpApiKeyLocation, HttpSecurityRequirement, HttpSecurityScheme,
+};
+
+use super::{QueryStringReader, THttpRequest};
+
+/// Fails with [`HttpParseError::Unauthorized`] unless the request meets one of `requirements` —
+/// what the generated `parse` of a `#[http_security]` model does first, and what a server calls
+/// with a route's own requirements. No requirements means nothing is required.
+///
+/// A scheme counts as met when its credential is there (the header or query parameter of an API
+/// key, an `Authorization` of the right scheme) and is not blank — `?api_key=` is no key at all;
+/// whether the credential is any good is for the field that reads it, or the handler, to decide.
+pub fn check_security<R: THttpRequest + ?Sized>(
+    request: &R,
+    requirements: &[HttpSecurityRequirement],
+) -> Result<(), HttpParseError> {
+    if requirements.is_empty() {
+        return Ok(());
+    }
+
+    for requirement in requirements {
+        let mut met = true;
+        for scheme in &requirement.schemes {
+            if !is_present(request, scheme)? {
+                met = false;
+                break;
+            }
+        }
+
+        if met {
+            return Ok(());
+        }
+    }
+
+    let expected: Vec<String> = requirements.iter().map(|r| r.describe()).collect();
+    Err(HttpParseError::Unauthorized(format!(
+        "expected {}",
+        expected.join(" or ")
+    )))
+}
+
+fn is_present<R: THttpRequest + ?Sized>(
+    request: &R,
+    scheme: &HttpSecurityScheme,
+) -> Result<bool, HttpParseError> {
+    let has_authorization = |name: &str| {
+        request
+            .get_header("authorization")
+            .and_then(|value| strip_scheme(value, name))
+            .is_some_and(|credentials| !credentials.is_empty())
+    };
+
+    let result = match scheme {
+        HttpSecurityScheme::Basic => has_authorization("Basic"),
+        HttpSecurityScheme::Bearer => has_authorization("Bearer"),
+        HttpSecurityScheme::ApiKey {
+            location: HttpApiKeyLocation::Header,
+            name,
+        } => request
+            .get_header(name)
+            .is_some_and(|value| !value.trim().is_empty()),
+        HttpSecurityScheme::ApiKey {
+            location: HttpApiKeyLocation::Query,
+            name,
+        } => {
+            let query = QueryStringReader::new(request.get_query_string())?;
+            match query.get_optional(name) {
+                // One that does not decode is there; reading it is what fails.
+                Some(value) => match value.as_str_or_string() {
+                    Ok(value) => !value.as_str().trim().is_empty(),
+                    Err(_) => true,
+                },
+                None => false,
+            }
+        }
+    };
+
+    Ok(result)
+}
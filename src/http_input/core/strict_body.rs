@@ -0,0 +1,191 @@
+//! `#[http_input(strict_body)]`: a body may carry only the members the model declares, each
+//! once — at the root and inside every nested object the model describes.
+//!
+//! The lenient readers look up the keys they want and never see the rest, so a misspelt member is
+//! simply absent and a repeated one silently loses a copy. This check walks the body once against
+//! a [`StrictJson`] table the derive builds at compile time: `json_name` / `rename_all`
+//! spellings, `path` envelopes, and the tables nested object structures carry as
+//! [`DataTypeProvider::STRICT_JSON`](crate::schema::data_types::DataTypeProvider::STRICT_JSON).
+//! Member names are read with `my-json`'s iterators, one container at a time, and nothing is
+//! built.
+
+use std::collections::HashSet;
+
+use my_json::json_reader::{JsonArrayIterator, JsonFirstLineIterator, JsonParseError};
+
+use crate::http_input::HttpParseError;
+use crate::json_node;
+
+/// What a strict body lets through in one JSON value. Generated as a `const`, so a table costs
+/// nothing per request.
+#[derive(Debug)]
+pub enum StrictJson {
+    /// Nothing is known — a scalar, a free-form value, a generic object: only repeated keys are
+    /// rejected, at any depth.
+    Any,
+    /// A described object: only these members, and those of the structures flattened into it.
+    Object {
+        members: &'static [StrictJsonMember],
+        flattened: &'static [&'static StrictJson],
+    },
+    /// An array, each element checked against the inner table.
+    ArrayOf(&'static StrictJson),
+    /// An object with free keys (a `HashMap<String, T>`), each value checked against the inner
+    /// table.
+    DictionaryOf(&'static StrictJson),
+}
+
+#[derive(Debug)]
+pub struct StrictJsonMember {
+    pub name: &'static str,
+    pub value: &'static StrictJson,
+}
+
+impl StrictJson {
+    /// The table of the member `name`; `None` when an object does not declare it.
+    fn get_member(&self, name: &str) -> Option<&'static StrictJson> {
+        match self {
+            Self::Object { members, flattened } => members
+                .iter()
+                .find(|member| member.name == name)
+                .map(|member| member.value)
+                .or_else(|| flattened.iter().find_map(|inner| inner.get_member(name))),
+            Self::DictionaryOf(element) => Some(*element),
+            Self::Any | Self::ArrayOf(_) => Some(&Self::Any),
+        }
+    }
+
+    fn is_array(&self) -> bool {
+        matches!(self, Self::ArrayOf(_))
+    }
+}
+
+pub(crate) fn check_strict_json_body(
+    raw: &[u8],
+    expected: &StrictJson,
+) -> Result<(), HttpParseError> {
+    let raw = raw.trim_ascii();
+    if raw.first() != Some(&b'{') {
+        return Err(HttpParseError::InvalidBodyFormat(
+            "JSON body must be an object to read named fields from it".to_string(),
+        ));
+    }
+
+    check_value(raw, expected, &mut Vec::new())
+}
+
+/// A url-encoded or form-data body has no nesting: every name is looked up as one flat key — a
+/// member of the root, or the dotted `path` of a member inside envelopes. A name may repeat only
+/// for an array member, which is how a form sends one.
+pub(crate) fn check_strict_flat_body<'s>(
+    names: impl Iterator<Item = &'s str>,
+    expected: &StrictJson,
+) -> Result<(), HttpParseError> {
+    let mut seen = HashSet::new();
+
+    for name in names {
+        let path = [name.to_string()];
+
+        let value =
+            get_flat_member(expected, name).ok_or_else(|| strict_error("unknown member", &path))?;
+
+        if !seen.insert(name) && !value.is_array() {
+            return Err(strict_error("repeated member", &path));
+        }
+    }
+
+    Ok(())
+}
+
+fn get_flat_member(expected: &StrictJson, name: &str) -> Option<&'static StrictJson> {
+    if let Some(result) = expected.get_member(name) {
+        return Some(result);
+    }
+
+    let (envelope, rest) = name.split_once('.')?;
+    match expected.get_member(envelope)? {
+        inner @ StrictJson::Object { .. } => get_flat_member(inner, rest),
+        _ => None,
+    }
+}
+
+fn check_value(
+    raw: &[u8],
+    expected: &StrictJson,
+    path: &mut Vec<String>,
+) -> Result<(), HttpParseError> {
+    match raw.first() {
+        Some(b'{' | b'[') if path.len() > json_node::MAX_DEPTH => {
+            Err(strict_error("the document is nested too deeply", path))
+        }
+        Some(b'{') => check_object(raw, expected, path),
+        Some(b'[') => check_array(raw, expected, path),
+        // A scalar is checked by whoever reads the member.
+        _ => Ok(()),
+    }
+}
+
+fn check_object(
+    raw: &[u8],
+    expected: &StrictJson,
+    path: &mut Vec<String>,
+) -> Result<(), HttpParseError> {
+    let mut seen = HashSet::new();
+    let iterator: JsonFirstLineIterator = raw.into();
+
+    while let Some(member) = iterator.get_next() {
+        let (key, value) = member.map_err(|err| syntax_error(path, err))?;
+        let key = key
+            .as_str()
+            .map_err(|err| syntax_error(path, err))?
+            .into_string();
+
+        let value_expected = expected.get_member(&key);
+        let repeated = !seen.insert(key.clone());
+        path.push(key);
+
+        if repeated {
+            return Err(strict_error("repeated member", path));
+        }
+
+        let value_expected = value_expected.ok_or_else(|| strict_error("unknown member", path))?;
+
+        check_value(value.as_slice(), value_expected, path)?;
+        path.pop();
+    }
+
+    Ok(())
+}
+
+fn check_array(
+    raw: &[u8],
+    expected: &StrictJson,
+    path: &mut Vec<String>,
+) -> Result<(), HttpParseError> {
+    let element = match expected {
+        StrictJson::ArrayOf(element) => *element,
+        _ => &StrictJson::Any,
+    };
+
+    let iterator: JsonArrayIterator = raw.into();
+    let mut index = 0;
+
+    while let Some(item) = iterator.get_next() {
+        let item = item.map_err(|err| syntax_error(path, err))?;
+
+        path.push(index.to_string());
+        check_value(item.as_slice(), element, path)?;
+        path.pop();
+        index += 1;
+    }
+
+    Ok(())
+}
+
+fn syntax_error(path: &[String], err: JsonParseError) -> HttpParseError {
+    strict_error(&json_node::parse_error_message(&err), path)
+}
+
+fn strict_error(message: &str, path: &[String]) -> HttpParseError {
+    HttpParseError::InvalidBodyFormat(format!("{} at '{}'", message, json_node::to_pointer(path)))
+}
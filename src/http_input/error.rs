@@ -1,14 +1,30 @@
+use my_json::json_reader::JsonParseError;
+use my_json::json_writer::{JsonObjectWriter, JsonValueWriter};
+
+use crate::error_code::*;
 use crate::form_data_reader::ReadingFromDataError;
-use crate::http_input::core::data_src::SRC_FORM_DATA;
+use crate::http_input::core::data_src::{self, SRC_FORM_DATA};
 use crate::url_decoder::UrlDecodeError;
 use crate::url_encoded_data_reader::ReadingEncodedDataError;
 
+const BODY_STREAM_NOT_AVAILABLE: &str = "Body stream is not available";
+const BODY_READER_TAKEN: &str = "Body reader is already taken";
+const BODY_STREAM_TRUNCATED: &str = "Request body stream ended unexpectedly";
+const BODY_TOO_LARGE: &str = "Request body is bigger than the allowed";
+const BODY_DIGEST_MISMATCH: &str = "Request body does not match its Content-Digest";
+
 /// Everything the server-independent parse layer can fail with.
 ///
 /// Each variant carries enough structured data for `my-http-server` to reconstruct — without
 /// loss — the same `HttpFailResult` (status code + text) it used to produce inline, via a
 /// `From<HttpParseError> for HttpFailResult`. That conversion lives on the server; this type
 /// stays transport-free and wasm-safe.
+///
+/// [`Self::code`] is the stable code of a failure and [`Self::to_json`] / [`Self::from_json`] its
+/// JSON form (see [`crate::error_code`]).
+///
+/// [`Self::status_code`] is the status a server answers with by default; a server that answers
+/// otherwise does so through an [`super::HttpParseErrorStatusPolicy`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum HttpParseError {
     /// A required parameter was not present. `src` is one of the `data_src::SRC_*` constants.
@@ -30,12 +46,18 @@ pub enum HttpParseError {
     NotSupportedContentType(String),
     /// The conversion is forbidden for this source (e.g. reading a file out of a query-string or
     /// JSON value) — the reference server answered 403 here, so this maps back to `as_forbidden`.
+    /// Also a `JwtClaims` token that does not verify (its signature, `exp`, `nbf` or `aud`).
     Forbidden(String),
     /// A field `validator` rejected the value.
     Validation(String),
+    /// None of the ways the model requires to authenticate (`#[http_security]`) is present in the
+    /// request. Only presence is checked here; a credential field still validates its own value.
+    Unauthorized(String),
     /// Streaming the body (`#[http_body_as_stream]`) failed: the stream broke off mid-body, the
-    /// stream is not available / its reader was already taken, a size limit was exceeded, or the
-    /// channel ended without the transport marking the body complete.
+    /// stream is not available / its reader was already taken, a size limit was exceeded, the
+    /// body did not match its digest, or the channel ended without the transport marking the body
+    /// complete. The crate's own failures are built by the `body_*` constructors, which is what
+    /// gives each of them a code of its own; any other message is `E_BODY_STREAM`.
     BodyStream(String),
 }
 
@@ -59,6 +81,98 @@ impl HttpParseError {
         }
     }
 
+    pub fn body_stream_not_available() -> Self {
+        Self::BodyStream(BODY_STREAM_NOT_AVAILABLE.to_string())
+    }
+
+    pub fn body_reader_taken() -> Self {
+        Self::BodyStream(BODY_READER_TAKEN.to_string())
+    }
+
+    /// The channel closed before the transport marked the body complete.
+    pub fn body_stream_truncated() -> Self {
+        Self::BodyStream(BODY_STREAM_TRUNCATED.to_string())
+    }
+
+    pub fn body_too_large(max_size: usize) -> Self {
+        Self::BodyStream(format!("{} {} bytes", BODY_TOO_LARGE, max_size))
+    }
+
+    /// A streamed body ended in full, but not with the digest it was sent with.
+    pub fn body_digest_mismatch() -> Self {
+        Self::BodyStream(BODY_DIGEST_MISMATCH.to_string())
+    }
+
+    /// The stable code of the failure — one of the [`crate::error_code`] constants.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::RequiredParameterIsMissing { .. } => E_REQUIRED_MISSING,
+            Self::CanNotParseValue { .. } => E_CANNOT_PARSE,
+            Self::UrlDecodeError(_) => E_URL_DECODE,
+            Self::InvalidBodyFormat(_) => E_INVALID_BODY,
+            Self::NotSupportedContentType(_) => E_UNSUPPORTED_CONTENT_TYPE,
+            Self::Forbidden(_) => E_FORBIDDEN,
+            Self::Validation(_) => E_VALIDATION,
+            Self::Unauthorized(_) => E_UNAUTHORIZED,
+            Self::BodyStream(msg) => match msg.as_str() {
+                BODY_STREAM_NOT_AVAILABLE => E_BODY_STREAM_NOT_AVAILABLE,
+                BODY_READER_TAKEN => E_BODY_STREAM_TAKEN,
+                BODY_STREAM_TRUNCATED => E_BODY_STREAM_TRUNCATED,
+                BODY_DIGEST_MISMATCH => E_BODY_DIGEST_MISMATCH,
+                msg if is_body_too_large(msg) => E_BODY_TOO_LARGE,
+                _ => E_BODY_STREAM,
+            },
+        }
+    }
+
+    /// The HTTP status of the failure: 400 for input that is missing or malformed, 401 for
+    /// [`Self::Unauthorized`], 403 for [`Self::Forbidden`], 415 for
+    /// [`Self::NotSupportedContentType`], 422 for [`Self::Validation`], and 413 for a body over its
+    /// size limit. A body reader taken twice is the server's own mistake, so it is a 500.
+    pub fn status_code(&self) -> u16 {
+        match self.code() {
+            E_UNAUTHORIZED => 401,
+            E_FORBIDDEN => 403,
+            E_UNSUPPORTED_CONTENT_TYPE => 415,
+            E_VALIDATION => 422,
+            E_BODY_TOO_LARGE => 413,
+            E_BODY_STREAM_TAKEN => 500,
+            _ => 400,
+        }
+    }
+
+    /// Reads the JSON [`Self::to_json`] wrote back into the same error.
+    pub fn from_json(raw: &[u8]) -> Result<Self, JsonParseError> {
+        let code = read_member(raw, "code")?;
+        let result = match code.as_str() {
+            E_REQUIRED_MISSING => Self::RequiredParameterIsMissing {
+                name: read_member(raw, "name")?,
+                src: read_src(raw)?,
+            },
+            E_CANNOT_PARSE => Self::CanNotParseValue {
+                name: read_member(raw, "name")?,
+                src: read_src(raw)?,
+                value: read_member(raw, "value")?,
+            },
+            E_URL_DECODE => Self::UrlDecodeError(read_member(raw, "detail")?),
+            E_INVALID_BODY => Self::InvalidBodyFormat(read_member(raw, "detail")?),
+            E_UNSUPPORTED_CONTENT_TYPE => {
+                Self::NotSupportedContentType(read_member(raw, "detail")?)
+            }
+            E_FORBIDDEN => Self::Forbidden(read_member(raw, "detail")?),
+            E_VALIDATION => Self::Validation(read_member(raw, "detail")?),
+            E_UNAUTHORIZED => Self::Unauthorized(read_member(raw, "detail")?),
+            E_BODY_STREAM_NOT_AVAILABLE
+            | E_BODY_STREAM_TAKEN
+            | E_BODY_STREAM_TRUNCATED
+            | E_BODY_TOO_LARGE
+            | E_BODY_DIGEST_MISMATCH
+            | E_BODY_STREAM => Self::BodyStream(read_member(raw, "detail")?),
+            other => return Err(unknown_code("HttpParseError", other)),
+        };
+        Ok(result)
+    }
+
     /// Maps a low-level [`ReadingEncodedDataError`] (from `UrlEncodedValue`) into the richer
     /// parse error, attaching the caller's `name`/`src` context. Mirrors the server's
     /// `url_encoded_data::convert_error`.
@@ -126,6 +240,14 @@ impl From<std::string::FromUtf8Error> for HttpParseError {
     }
 }
 
+/// A `#[http_body_raw]` `JsonPatch` / `JsonMergePatch` that is not a well-formed patch. The
+/// pointer goes into the message, so the client learns which operation / member to fix.
+impl From<crate::http_input::JsonPatchError> for HttpParseError {
+    fn from(err: crate::http_input::JsonPatchError) -> Self {
+        Self::InvalidBodyFormat(err.to_string())
+    }
+}
+
 impl std::fmt::Display for HttpParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -142,9 +264,158 @@ impl std::fmt::Display for HttpParseError {
             Self::NotSupportedContentType(msg) => write!(f, "Not supported content type: {}", msg),
             Self::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
             Self::Validation(msg) => write!(f, "Validation error: {}", msg),
+            Self::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
             Self::BodyStream(msg) => write!(f, "Body stream error: {}", msg),
         }
     }
 }
 
 impl std::error::Error for HttpParseError {}
+
+impl JsonValueWriter for HttpParseError {
+    const IS_ARRAY: bool = false;
+
+    fn write(&self, dest: &mut String) {
+        let obj = JsonObjectWriter::new()
+            .write_ref("code", &self.code())
+            .write_ref("message", &self.to_string());
+
+        let obj = match self {
+            Self::RequiredParameterIsMissing { name, src } => {
+                obj.write_ref("name", name).write_ref("src", src)
+            }
+            Self::CanNotParseValue { name, src, value } => obj
+                .write_ref("name", name)
+                .write_ref("src", src)
+                .write_ref("value", value),
+            Self::UrlDecodeError(detail)
+            | Self::InvalidBodyFormat(detail)
+            | Self::NotSupportedContentType(detail)
+            | Self::Forbidden(detail)
+            | Self::Validation(detail)
+            | Self::Unauthorized(detail)
+            | Self::BodyStream(detail) => obj.write_ref("detail", detail),
+        };
+
+        obj.build_into(dest);
+    }
+}
+
+impl_error_json!(HttpParseError);
+
+/// The `src` member, which has to be one of the `data_src` constants.
+fn read_src(raw: &[u8]) -> Result<&'static str, JsonParseError> {
+    let src = read_member(raw, "src")?;
+    data_src::find(&src)
+        .ok_or_else(|| JsonParseError::new(format!("'{}' is not a source of a value", src)))
+}
+
+/// Only the exact message [`HttpParseError::body_too_large`] writes, so a transport's own text
+/// that merely starts the same way stays `E_BODY_STREAM`.
+fn is_body_too_large(msg: &str) -> bool {
+    msg.strip_prefix(BODY_TOO_LARGE)
+        .and_then(|rest| rest.strip_prefix(' '))
+        .and_then(|rest| rest.strip_suffix(" bytes"))
+        .is_some_and(|size| size.parse::<usize>().is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HttpParseError;
+    use crate::http_input::core::data_src::{SRC_HEADER, SRC_QUERY_STRING};
+
+    #[test]
+    fn every_variant_round_trips_through_json() {
+        for err in [
+            HttpParseError::required("X-Key", SRC_HEADER),
+            HttpParseError::cannot_parse("n", SRC_QUERY_STRING, "a\"b"),
+            HttpParseError::UrlDecodeError("bad escape".to_string()),
+            HttpParseError::InvalidBodyFormat("not JSON".to_string()),
+            HttpParseError::NotSupportedContentType("text/csv".to_string()),
+            HttpParseError::Forbidden("file from a query string".to_string()),
+            HttpParseError::Validation("not digits".to_string()),
+            HttpParseError::Unauthorized("a bearer token".to_string()),
+            HttpParseError::body_stream_not_available(),
+            HttpParseError::body_reader_taken(),
+            HttpParseError::body_stream_truncated(),
+            HttpParseError::body_too_large(1024),
+            HttpParseError::body_digest_mismatch(),
+            HttpParseError::BodyStream("connection reset".to_string()),
+        ] {
+            let json = err.to_json();
+            assert_eq!(
+                HttpParseError::from_json(json.as_bytes()).unwrap(),
+                err,
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn body_stream_failures_have_codes_of_their_own() {
+        assert_eq!(
+            HttpParseError::body_stream_not_available().code(),
+            "E_BODY_STREAM_NOT_AVAILABLE"
+        );
+        assert_eq!(
+            HttpParseError::body_reader_taken().code(),
+            "E_BODY_STREAM_TAKEN"
+        );
+        assert_eq!(
+            HttpParseError::body_stream_truncated().code(),
+            "E_BODY_STREAM_TRUNCATED"
+        );
+        assert_eq!(
+            HttpParseError::body_too_large(10).code(),
+            "E_BODY_TOO_LARGE"
+        );
+        assert_eq!(
+            HttpParseError::body_digest_mismatch().code(),
+            "E_BODY_DIGEST_MISMATCH"
+        );
+        assert_eq!(
+            HttpParseError::BodyStream("connection reset".to_string()).code(),
+            "E_BODY_STREAM"
+        );
+        assert_eq!(
+            HttpParseError::BodyStream("Request body is bigger than the allowed limit".to_string())
+                .code(),
+            "E_BODY_STREAM"
+        );
+    }
+
+    #[test]
+    fn default_status_codes() {
+        for (err, status) in [
+            (HttpParseError::required("n", SRC_QUERY_STRING), 400),
+            (
+                HttpParseError::cannot_parse("n", SRC_QUERY_STRING, "x"),
+                400,
+            ),
+            (HttpParseError::InvalidBodyFormat("x".to_string()), 400),
+            (HttpParseError::Unauthorized("x".to_string()), 401),
+            (HttpParseError::Forbidden("x".to_string()), 403),
+            (
+                HttpParseError::NotSupportedContentType("x".to_string()),
+                415,
+            ),
+            (HttpParseError::Validation("x".to_string()), 422),
+            (HttpParseError::body_too_large(10), 413),
+            (HttpParseError::body_stream_truncated(), 400),
+            (HttpParseError::body_reader_taken(), 500),
+        ] {
+            assert_eq!(err.status_code(), status, "{:?}", err);
+        }
+    }
+
+    #[test]
+    fn unknown_code_or_source_is_an_error() {
+        assert!(HttpParseError::from_json(br#"{"code":"E_NOPE","detail":"x"}"#).is_err());
+        assert!(HttpParseError::from_json(
+            br#"{"code":"E_REQUIRED_MISSING","name":"n","src":"Cookie"}"#
+        )
+        .is_err());
+        assert!(HttpParseError::from_json(br#"{"code":"E_VALIDATION"}"#).is_err());
+    }
+}
@@ -0,0 +1,400 @@
+//! A bit-flag set over the cases of a `MyHttpFlagsEnum` enum.
+//!
+//! The enum names the individual bits; [`HttpFlags<T>`] is the *value* — any number of them ORed
+//! together — and is what a model field is typed as (`perms: HttpFlags<Permission>`). It is a field
+//! type like [`super::RawData`], so it is ungated: a wasm client builds it, and only the
+//! `DataTypeProvider` / `TryInto` halves are `server`-only.
+//!
+//! **Reading** accepts either spelling, on every source:
+//!
+//! * an integer — `5`, as text in a query / header / form field, or a JSON number;
+//! * a list of case names — `read,admin` (comma-separated, whitespace around a name is ignored),
+//!   or a JSON array `["read","admin"]`. An item of a list may itself be an integer, so `1,4` works.
+//!
+//! An empty value is the empty set. A name no case has, or an integer with a bit no case stands
+//! for, is rejected — a set that silently dropped a bit would grant or deny something other than
+//! what was sent.
+//!
+//! **Writing** always emits the canonical form: the integer, in decimal (`5`; a JSON number in a
+//! body). One spelling per value keeps the client's output stable however the set was built.
+
+use std::marker::PhantomData;
+
+/// Implemented by `#[derive(MyHttpFlagsEnum)]`: the bit and wire name of every case.
+pub trait HttpFlagsEnum: Sized + 'static {
+    /// Every case as `(bit, name)`, in declaration order.
+    fn get_flag_cases() -> &'static [(i64, &'static str)];
+
+    /// The single bit this case stands for.
+    fn get_bit(&self) -> i64;
+
+    /// Every bit some case stands for, ORed together.
+    fn get_known_bits() -> i64 {
+        Self::get_flag_cases()
+            .iter()
+            .fold(0, |acc, (bit, _)| acc | bit)
+    }
+}
+
+pub struct HttpFlags<T: HttpFlagsEnum> {
+    bits: i64,
+    // The canonical text, kept in step with `bits` so `as_str()` can hand out a `&str` — the
+    // client request builder writes a struct-typed query / header / form field through `as_str()`.
+    canonical: String,
+    _cases: PhantomData<fn() -> T>,
+}
+
+impl<T: HttpFlagsEnum> HttpFlags<T> {
+    pub fn empty() -> Self {
+        Self::from_known_bits(0)
+    }
+
+    /// Builds a set from its integer form. Fails when `bits` has a bit no case stands for.
+    pub fn from_bits(bits: i64) -> Result<Self, String> {
+        let unknown = bits & !T::get_known_bits();
+
+        if unknown != 0 {
+            return Err(format!("{} has bits no flag stands for: {}", bits, unknown));
+        }
+
+        Ok(Self::from_known_bits(bits))
+    }
+
+    fn from_known_bits(bits: i64) -> Self {
+        Self {
+            bits,
+            canonical: bits.to_string(),
+            _cases: PhantomData,
+        }
+    }
+
+    /// Reads either spelling — an integer, or a comma-separated list of case names and/or
+    /// integers. See the module docs.
+    pub fn parse_str(src: &str) -> Result<Self, String> {
+        let mut bits = 0;
+
+        for item in src.split(',') {
+            let item = item.trim();
+
+            if item.is_empty() {
+                continue;
+            }
+
+            bits |= Self::read_item(item)?;
+        }
+
+        Self::from_bits(bits)
+    }
+
+    fn read_item(item: &str) -> Result<i64, String> {
+        if let Some((bit, _)) = T::get_flag_cases().iter().find(|(_, name)| *name == item) {
+            return Ok(*bit);
+        }
+
+        match item.parse::<i64>() {
+            Ok(bits) => Ok(bits),
+            Err(_) => Err(format!(
+                "unknown flag `{}`, expected one of {}",
+                item,
+                Self::list_names()
+            )),
+        }
+    }
+
+    fn list_names() -> String {
+        let names: Vec<String> = T::get_flag_cases()
+            .iter()
+            .map(|(_, name)| format!("`{}`", name))
+            .collect();
+
+        names.join(", ")
+    }
+
+    pub fn bits(&self) -> i64 {
+        self.bits
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    pub fn contains(&self, flag: T) -> bool {
+        let bit = flag.get_bit();
+        self.bits & bit == bit
+    }
+
+    pub fn insert(&mut self, flag: T) {
+        self.set_bits(self.bits | flag.get_bit());
+    }
+
+    pub fn remove(&mut self, flag: T) {
+        self.set_bits(self.bits & !flag.get_bit());
+    }
+
+    fn set_bits(&mut self, bits: i64) {
+        self.bits = bits;
+        self.canonical = bits.to_string();
+    }
+
+    /// The names of the flags that are set, in declaration order.
+    pub fn get_names(&self) -> Vec<&'static str> {
+        T::get_flag_cases()
+            .iter()
+            .filter(|(bit, _)| self.bits & bit == *bit)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// The canonical form: the integer, in decimal.
+    pub fn as_str(&self) -> &str {
+        self.canonical.as_str()
+    }
+}
+
+impl<T: HttpFlagsEnum> Clone for HttpFlags<T> {
+    fn clone(&self) -> Self {
+        Self::from_known_bits(self.bits)
+    }
+}
+
+impl<T: HttpFlagsEnum> PartialEq for HttpFlags<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<T: HttpFlagsEnum> Eq for HttpFlags<T> {}
+
+impl<T: HttpFlagsEnum> Default for HttpFlags<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T: HttpFlagsEnum> std::fmt::Debug for HttpFlags<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("HttpFlags").field(&self.get_names()).finish()
+    }
+}
+
+impl<T: HttpFlagsEnum> std::fmt::Display for HttpFlags<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<T: HttpFlagsEnum> From<T> for HttpFlags<T> {
+    fn from(flag: T) -> Self {
+        Self::from_known_bits(flag.get_bit())
+    }
+}
+
+impl<T: HttpFlagsEnum> FromIterator<T> for HttpFlags<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let bits = iter.into_iter().fold(0, |acc, flag| acc | flag.get_bit());
+        Self::from_known_bits(bits)
+    }
+}
+
+impl<T: HttpFlagsEnum> std::ops::BitOr<T> for HttpFlags<T> {
+    type Output = Self;
+
+    fn bitor(mut self, flag: T) -> Self {
+        self.insert(flag);
+        self
+    }
+}
+
+impl<T: HttpFlagsEnum> std::ops::BitOr for HttpFlags<T> {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self::from_known_bits(self.bits | other.bits)
+    }
+}
+
+// my-json: the writer emits the canonical integer as a JSON number; the reader takes a number, a
+// name-list string or an array, through the same serde impl below.
+impl<T: HttpFlagsEnum> my_json::json_writer::JsonValueWriter for HttpFlags<T> {
+    const IS_ARRAY: bool = false;
+
+    fn write(&self, dest: &mut String) {
+        dest.push_str(self.as_str());
+    }
+}
+
+impl<'s, T: HttpFlagsEnum> my_json::json_reader::JsonValueReader<'s> for HttpFlags<T> {
+    fn from_json_value(
+        value: &my_json::json_reader::JsonValueRef<'s>,
+    ) -> Result<Self, my_json::json_reader::JsonParseError> {
+        serde_json::from_slice(value.as_slice())
+            .map_err(|err| my_json::json_reader::JsonParseError::new(err.to_string()))
+    }
+}
+
+impl<T: HttpFlagsEnum> serde::Serialize for HttpFlags<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.bits)
+    }
+}
+
+impl<'de, T: HttpFlagsEnum> serde::Deserialize<'de> for HttpFlags<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(FlagsVisitor(PhantomData))
+    }
+}
+
+struct FlagsVisitor<T>(PhantomData<fn() -> T>);
+
+impl<'de, T: HttpFlagsEnum> serde::de::Visitor<'de> for FlagsVisitor<T> {
+    type Value = HttpFlags<T>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("an integer, a comma-separated list of flag names, or an array of them")
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        HttpFlags::from_bits(v).map_err(E::custom)
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        match i64::try_from(v) {
+            Ok(v) => self.visit_i64(v),
+            Err(_) => Err(E::custom(format!("{} is out of range for a flag set", v))),
+        }
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        HttpFlags::parse_str(v).map_err(E::custom)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bits = 0;
+
+        while let Some(item) = seq.next_element::<FlagItem>()? {
+            bits |= match item {
+                FlagItem::Bits(v) => v,
+                FlagItem::Name(name) => {
+                    HttpFlags::<T>::read_item(name.as_str()).map_err(serde::de::Error::custom)?
+                }
+            };
+        }
+
+        HttpFlags::from_bits(bits).map_err(serde::de::Error::custom)
+    }
+}
+
+/// One element of a JSON array of flags: a name or an integer.
+enum FlagItem {
+    Name(String),
+    Bits(i64),
+}
+
+impl<'de> serde::Deserialize<'de> for FlagItem {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ItemVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ItemVisitor {
+            type Value = FlagItem;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a flag name or an integer")
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(FlagItem::Bits(v))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                i64::try_from(v)
+                    .map(FlagItem::Bits)
+                    .map_err(|_| E::custom(format!("{} is out of range for a flag set", v)))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(FlagItem::Name(v.to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(ItemVisitor)
+    }
+}
+
+/// Schema (server-only): the enum's own description — an `EnumType::Flags` structure listing each
+/// case's bit — which is what `#[derive(MyHttpFlagsEnum)]` emits as the enum's `DataTypeProvider`.
+#[cfg(feature = "server")]
+impl<T: HttpFlagsEnum + crate::schema::data_types::DataTypeProvider>
+    crate::schema::data_types::DataTypeProvider for HttpFlags<T>
+{
+    fn get_data_type() -> crate::schema::data_types::HttpDataType {
+        T::get_data_type()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Perm {
+        Read,
+        Write,
+        Admin,
+    }
+
+    impl HttpFlagsEnum for Perm {
+        fn get_flag_cases() -> &'static [(i64, &'static str)] {
+            &[(1, "read"), (2, "write"), (4, "admin")]
+        }
+
+        fn get_bit(&self) -> i64 {
+            match self {
+                Self::Read => 1,
+                Self::Write => 2,
+                Self::Admin => 4,
+            }
+        }
+    }
+
+    #[test]
+    fn parses_an_integer_or_a_name_list() {
+        for src in ["5", "read,admin", " admin , read ", "1,4", "read,4"] {
+            let flags = HttpFlags::<Perm>::parse_str(src).unwrap();
+            assert_eq!(flags.bits(), 5, "{}", src);
+        }
+
+        assert!(HttpFlags::<Perm>::parse_str("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_names_and_bits() {
+        assert!(HttpFlags::<Perm>::parse_str("read,delete")
+            .unwrap_err()
+            .contains("delete"));
+        assert!(HttpFlags::<Perm>::from_bits(9).unwrap_err().contains("8"));
+    }
+
+    #[test]
+    fn writes_the_canonical_integer() {
+        let flags = HttpFlags::from(Perm::Admin) | Perm::Read;
+        assert_eq!(flags.as_str(), "5");
+        assert_eq!(flags.get_names(), vec!["read", "admin"]);
+        assert!(flags.contains(Perm::Read));
+        assert!(!flags.contains(Perm::Write));
+
+        let mut flags = flags;
+        flags.remove(Perm::Admin);
+        assert_eq!(flags.as_str(), "1");
+    }
+
+    #[test]
+    fn serde_reads_every_spelling_and_writes_a_number() {
+        for json in ["5", r#""read,admin""#, r#"["read","admin"]"#, r#"["read",4]"#] {
+            let flags: HttpFlags<Perm> = serde_json::from_str(json).unwrap();
+            assert_eq!(flags.bits(), 5, "{}", json);
+        }
+
+        let flags: HttpFlags<Perm> = [Perm::Write].into_iter().collect();
+        assert_eq!(serde_json::to_string(&flags).unwrap(), "2");
+    }
+}
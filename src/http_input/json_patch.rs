@@ -0,0 +1,606 @@
+//! Patch documents as `#[http_body_raw]` fields: [`JsonPatch`] (RFC 6902,
+//! `application/json-patch+json`) and [`JsonMergePatch`] (RFC 7396,
+//! `application/merge-patch+json`).
+//!
+//! Both keep the body's bytes and are checked when they are built, so a model that parsed has a
+//! patch that can at least be applied; whether it applies to a given document is only known when
+//! it is. Either way a failure is a [`JsonPatchError`] naming the offending JSON pointer. Applying
+//! walks a borrowed tree over the body and the target (`crate::json_node`), so neither document is
+//! copied member by member — only the result is written out.
+//!
+//! [`JsonPatch::apply_to`] / [`JsonMergePatch::apply_to`] patch a model rather than raw JSON:
+//! anything with the my-json writer and reader — every `MyHttpInputObjectStructure` — is written
+//! out, patched, and read back through the same generated reader a request body goes through.
+
+use std::borrow::Cow;
+
+use my_json::json_reader::JsonValueReader;
+use my_json::json_writer::JsonValueWriter;
+
+use crate::json_node::{self, JsonNode, JsonSyntaxError};
+
+pub const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+pub const JSON_MERGE_PATCH_CONTENT_TYPE: &str = "application/merge-patch+json";
+
+/// Why a patch document was rejected, or why it did not apply.
+///
+/// `pointer` is an RFC 6901 JSON pointer. For a patch that is malformed it points into the
+/// *patch* (`/2/op` — the third operation's `op`); for one that does not apply it is the path in
+/// the *target* document the failing operation names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPatchError {
+    pointer: String,
+    message: String,
+}
+
+impl JsonPatchError {
+    pub fn new(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            pointer: pointer.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn get_pointer(&self) -> &str {
+        self.pointer.as_str()
+    }
+
+    pub fn get_message(&self) -> &str {
+        self.message.as_str()
+    }
+}
+
+impl From<JsonSyntaxError> for JsonPatchError {
+    fn from(err: JsonSyntaxError) -> Self {
+        Self::new(err.pointer, err.message)
+    }
+}
+
+impl std::fmt::Display for JsonPatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at '{}'", self.message, self.pointer)
+    }
+}
+
+impl std::error::Error for JsonPatchError {}
+
+/// An RFC 6902 JSON Patch: an array of `add` / `remove` / `replace` / `move` / `copy` / `test`
+/// operations, applied in order and all-or-nothing.
+///
+/// Built only from a well-formed patch — every operation an object with a known `op`, a valid
+/// `path`, and the `from` / `value` its `op` needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPatch {
+    data: Vec<u8>,
+}
+
+impl JsonPatch {
+    pub fn new(data: Vec<u8>) -> Result<Self, JsonPatchError> {
+        let document = json_node::parse(&data)?;
+        read_operations(&document)?;
+        Ok(Self { data })
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Applies the patch to a JSON document and returns the patched one, compact. A document
+    /// that is not JSON is reported with the pointer where it broke.
+    pub fn apply_to_json(&self, document: &[u8]) -> Result<String, JsonPatchError> {
+        let patch = json_node::parse(&self.data)?;
+        let operations = read_operations(&patch)?;
+
+        let mut document = json_node::parse(document)?;
+        for operation in &operations {
+            operation.apply(&mut document)?;
+        }
+
+        Ok(document.to_json_string())
+    }
+
+    /// Applies the patch to a model: writes `value` out as JSON, patches it, and reads the result
+    /// back as a `T`. A result that no longer fits `T` is an error at the root pointer.
+    pub fn apply_to<T>(&self, value: &T) -> Result<T, JsonPatchError>
+    where
+        T: JsonValueWriter + for<'s> JsonValueReader<'s>,
+    {
+        let mut document = String::new();
+        value.write(&mut document);
+        read_patched(&self.apply_to_json(document.as_bytes())?)
+    }
+}
+
+impl From<JsonPatch> for Vec<u8> {
+    fn from(value: JsonPatch) -> Self {
+        value.data
+    }
+}
+
+impl AsRef<[u8]> for JsonPatch {
+    fn as_ref(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+}
+
+/// An RFC 7396 JSON Merge Patch: a document shaped like the target, where a member sets that
+/// member, `null` removes it, and an object merges into the object it lands on. Any JSON value
+/// is a valid merge patch; a non-object one replaces the whole target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonMergePatch {
+    data: Vec<u8>,
+}
+
+impl JsonMergePatch {
+    pub fn new(data: Vec<u8>) -> Result<Self, JsonPatchError> {
+        json_node::parse(&data)?;
+        Ok(Self { data })
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Merges the patch into a JSON document and returns the merged one, compact. Members keep
+    /// their order; a new member goes last.
+    pub fn apply_to_json(&self, document: &[u8]) -> Result<String, JsonPatchError> {
+        let patch = json_node::parse(&self.data)?;
+        let document = json_node::parse(document)?;
+        Ok(merge(document, &patch).to_json_string())
+    }
+
+    /// Merges the patch into a model — see [`JsonPatch::apply_to`].
+    pub fn apply_to<T>(&self, value: &T) -> Result<T, JsonPatchError>
+    where
+        T: JsonValueWriter + for<'s> JsonValueReader<'s>,
+    {
+        let mut document = String::new();
+        value.write(&mut document);
+        read_patched(&self.apply_to_json(document.as_bytes())?)
+    }
+}
+
+impl From<JsonMergePatch> for Vec<u8> {
+    fn from(value: JsonMergePatch) -> Self {
+        value.data
+    }
+}
+
+impl AsRef<[u8]> for JsonMergePatch {
+    fn as_ref(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+}
+
+/// The generated readers read an object's members out of its enclosing document, so the patched
+/// value is read as the one member of a wrapper rather than on its own.
+fn read_patched<T>(patched: &str) -> Result<T, JsonPatchError>
+where
+    T: for<'s> JsonValueReader<'s>,
+{
+    let wrapped = format!("{{\"value\":{}}}", patched);
+    crate::read_json_object_field(wrapped.as_bytes(), "value").map_err(|err| {
+        JsonPatchError::new(
+            "",
+            format!("the patched document does not fit the model: {:?}", err),
+        )
+    })
+}
+
+fn merge<'s>(target: JsonNode<'s>, patch: &JsonNode<'s>) -> JsonNode<'s> {
+    let JsonNode::Object(patch_members) = patch else {
+        return patch.clone();
+    };
+
+    let mut members = match target {
+        JsonNode::Object(members) => members,
+        _ => Vec::new(),
+    };
+
+    for (key, value) in patch_members {
+        if let JsonNode::Null = value {
+            members.retain(|(name, _)| name != key);
+            continue;
+        }
+
+        let existing = match json_node::member_position(&members, key) {
+            Some(position) => std::mem::replace(&mut members[position].1, JsonNode::Null),
+            None => JsonNode::Null,
+        };
+
+        json_node::set_member(&mut members, key.clone(), merge(existing, value));
+    }
+
+    JsonNode::Object(members)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OperationKind {
+    Add,
+    Remove,
+    Replace,
+    Move,
+    Copy,
+    Test,
+}
+
+impl OperationKind {
+    fn parse(src: &str) -> Option<Self> {
+        match src {
+            "add" => Some(Self::Add),
+            "remove" => Some(Self::Remove),
+            "replace" => Some(Self::Replace),
+            "move" => Some(Self::Move),
+            "copy" => Some(Self::Copy),
+            "test" => Some(Self::Test),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Remove => "remove",
+            Self::Replace => "replace",
+            Self::Move => "move",
+            Self::Copy => "copy",
+            Self::Test => "test",
+        }
+    }
+}
+
+struct Operation<'a, 's> {
+    index: usize,
+    kind: OperationKind,
+    path: Vec<String>,
+    from: Vec<String>,
+    value: Option<&'a JsonNode<'s>>,
+}
+
+fn read_operations<'a, 's>(
+    patch: &'a JsonNode<'s>,
+) -> Result<Vec<Operation<'a, 's>>, JsonPatchError> {
+    let JsonNode::Array(items) = patch else {
+        return Err(JsonPatchError::new(
+            "",
+            "a JSON Patch must be an array of operations",
+        ));
+    };
+
+    let mut result = Vec::with_capacity(items.len());
+
+    for (index, item) in items.iter().enumerate() {
+        let JsonNode::Object(_) = item else {
+            return Err(JsonPatchError::new(
+                format!("/{}", index),
+                "an operation must be an object",
+            ));
+        };
+
+        let member = |name: &str| item.get(&[name.to_string()]);
+
+        let kind = match member("op") {
+            Some(JsonNode::String(op)) => OperationKind::parse(op).ok_or_else(|| {
+                JsonPatchError::new(
+                    format!("/{}/op", index),
+                    format!("'{}' is not a JSON Patch operation", op),
+                )
+            })?,
+            _ => {
+                return Err(JsonPatchError::new(
+                    format!("/{}/op", index),
+                    "'op' must be a string",
+                ))
+            }
+        };
+
+        let read_pointer = |name: &str| -> Result<Vec<String>, JsonPatchError> {
+            let pointer_error = |message: &str| {
+                JsonPatchError::new(format!("/{}/{}", index, name), message.to_string())
+            };
+
+            match member(name) {
+                Some(JsonNode::String(pointer)) => json_node::parse_pointer(pointer)
+                    .ok_or_else(|| pointer_error("not a JSON pointer")),
+                Some(_) => Err(pointer_error("must be a string")),
+                None => Err(JsonPatchError::new(
+                    format!("/{}", index),
+                    format!("'{}' operation needs '{}'", kind.as_str(), name),
+                )),
+            }
+        };
+
+        let path = read_pointer("path")?;
+
+        let from = match kind {
+            OperationKind::Move | OperationKind::Copy => read_pointer("from")?,
+            _ => Vec::new(),
+        };
+
+        let value = match kind {
+            OperationKind::Add | OperationKind::Replace | OperationKind::Test => {
+                Some(member("value").ok_or_else(|| {
+                    JsonPatchError::new(
+                        format!("/{}", index),
+                        format!("'{}' operation needs 'value'", kind.as_str()),
+                    )
+                })?)
+            }
+            _ => None,
+        };
+
+        if kind == OperationKind::Move && path.len() > from.len() && path.starts_with(&from) {
+            return Err(JsonPatchError::new(
+                format!("/{}/from", index),
+                "a value can not be moved into one of its own children",
+            ));
+        }
+
+        result.push(Operation {
+            index,
+            kind,
+            path,
+            from,
+            value,
+        });
+    }
+
+    Ok(result)
+}
+
+impl<'a, 's> Operation<'a, 's> {
+    fn error(&self, tokens: &[String], message: &str) -> JsonPatchError {
+        JsonPatchError::new(
+            json_node::to_pointer(tokens),
+            format!(
+                "operation {} ('{}'): {}",
+                self.index,
+                self.kind.as_str(),
+                message
+            ),
+        )
+    }
+
+    fn apply(&self, document: &mut JsonNode<'s>) -> Result<(), JsonPatchError> {
+        match self.kind {
+            OperationKind::Add => self.add(document, &self.path, self.get_value().clone()),
+            OperationKind::Remove => self.remove(document, &self.path).map(|_| ()),
+            OperationKind::Replace => match document.get_mut(&self.path) {
+                Some(target) => {
+                    *target = self.get_value().clone();
+                    Ok(())
+                }
+                None => Err(self.error(&self.path, "there is no value to replace")),
+            },
+            OperationKind::Move => {
+                if self.from == self.path {
+                    return match document.get(&self.from) {
+                        Some(_) => Ok(()),
+                        None => Err(self.error(&self.from, "there is no value to move")),
+                    };
+                }
+                let value = self.remove(document, &self.from)?;
+                self.add(document, &self.path, value)
+            }
+            OperationKind::Copy => match document.get(&self.from) {
+                Some(value) => {
+                    let value = value.clone();
+                    self.add(document, &self.path, value)
+                }
+                None => Err(self.error(&self.from, "there is no value to copy")),
+            },
+            OperationKind::Test => match document.get(&self.path) {
+                Some(value) if json_node::json_eq(value, self.get_value()) => Ok(()),
+                Some(_) => Err(self.error(&self.path, "the value is not the expected one")),
+                None => Err(self.error(&self.path, "there is no value to test")),
+            },
+        }
+    }
+
+    fn get_value(&self) -> &JsonNode<'s> {
+        // `read_operations` made sure every operation that reads `value` has one.
+        self.value.unwrap_or(&JsonNode::Null)
+    }
+
+    fn add(
+        &self,
+        document: &mut JsonNode<'s>,
+        path: &[String],
+        value: JsonNode<'s>,
+    ) -> Result<(), JsonPatchError> {
+        let Some((last, parent)) = path.split_last() else {
+            *document = value;
+            return Ok(());
+        };
+
+        match document.get_mut(parent) {
+            Some(JsonNode::Object(members)) => {
+                json_node::set_member(members, Cow::Owned(last.clone()), value);
+                Ok(())
+            }
+            Some(JsonNode::Array(items)) => {
+                let index = if last == "-" {
+                    items.len()
+                } else {
+                    match json_node::parse_array_index(last) {
+                        Some(index) if index <= items.len() => index,
+                        _ => return Err(self.error(path, "the array index is out of range")),
+                    }
+                };
+                items.insert(index, value);
+                Ok(())
+            }
+            Some(_) => Err(self.error(path, "the parent is neither an object nor an array")),
+            None => Err(self.error(path, "the parent does not exist")),
+        }
+    }
+
+    fn remove(
+        &self,
+        document: &mut JsonNode<'s>,
+        path: &[String],
+    ) -> Result<JsonNode<'s>, JsonPatchError> {
+        let Some((last, parent)) = path.split_last() else {
+            return Err(self.error(path, "the whole document can not be removed"));
+        };
+
+        let removed = match document.get_mut(parent) {
+            Some(JsonNode::Object(members)) => {
+                json_node::member_position(members, last).map(|position| members.remove(position).1)
+            }
+            Some(JsonNode::Array(items)) => json_node::parse_array_index(last)
+                .filter(|index| *index < items.len())
+                .map(|index| items.remove(index)),
+            _ => None,
+        };
+
+        removed.ok_or_else(|| self.error(path, "there is no value at this path"))
+    }
+}
+
+/// Schema (server-only): an array of operations, each `{op, path, from?, value?}`. `value` can be
+/// any JSON, which the schema has no type for, so it is left untyped.
+#[cfg(feature = "server")]
+impl crate::schema::data_types::DataTypeProvider for JsonPatch {
+    fn get_data_type() -> crate::schema::data_types::HttpDataType {
+        use crate::schema::data_types::{HttpDataType, HttpField, HttpObjectStructure};
+
+        let mut operation = HttpObjectStructure::new("JsonPatchOperation", None);
+        operation.main.fields = vec![
+            HttpField::new("op", HttpDataType::as_string(), true),
+            HttpField::new("path", HttpDataType::as_string(), true),
+            HttpField::new("from", HttpDataType::as_string(), false),
+            HttpField::new("value", HttpDataType::None, false),
+        ];
+
+        operation.into_http_data_type_array()
+    }
+
+    fn get_body_content_type() -> Option<&'static str> {
+        Some(JSON_PATCH_CONTENT_TYPE)
+    }
+}
+
+/// Schema (server-only): a merge patch has the target's shape with every member optional, which
+/// only the target knows — so it is documented as a free-form object.
+#[cfg(feature = "server")]
+impl crate::schema::data_types::DataTypeProvider for JsonMergePatch {
+    fn get_data_type() -> crate::schema::data_types::HttpDataType {
+        crate::schema::data_types::HttpObjectStructure::new("JsonMergePatch", None)
+            .into_http_data_type_object()
+    }
+
+    fn get_body_content_type() -> Option<&'static str> {
+        Some(JSON_MERGE_PATCH_CONTENT_TYPE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonMergePatch, JsonPatch};
+
+    fn patch(src: &str) -> JsonPatch {
+        JsonPatch::new(src.as_bytes().to_vec()).unwrap()
+    }
+
+    fn merge_patch(src: &str) -> JsonMergePatch {
+        JsonMergePatch::new(src.as_bytes().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn applies_every_operation() {
+        let result = patch(
+            r#"[
+                {"op":"test","path":"/a","value":1.0},
+                {"op":"add","path":"/b/-","value":3},
+                {"op":"add","path":"/b/0","value":0},
+                {"op":"replace","path":"/c","value":{"d":true}},
+                {"op":"copy","from":"/c","path":"/e"},
+                {"op":"move","from":"/a","path":"/f"},
+                {"op":"remove","path":"/b/1"}
+            ]"#,
+        )
+        .apply_to_json(br#"{"a":1,"b":[1,2],"c":null}"#)
+        .unwrap();
+
+        assert_eq!(
+            result,
+            r#"{"b":[0,2,3],"c":{"d":true},"e":{"d":true},"f":1}"#
+        );
+    }
+
+    #[test]
+    fn a_malformed_patch_is_reported_where_it_is_wrong() {
+        for (src, pointer) in [
+            (r#"{"op":"add"}"#, ""),
+            (r#"[{"op":"add","path":"/a","value":1}, 5]"#, "/1"),
+            (r#"[{"op":"nope","path":"/a"}]"#, "/0/op"),
+            (r#"[{"op":"remove","path":"a"}]"#, "/0/path"),
+            (r#"[{"op":"replace","path":"/a"}]"#, "/0"),
+            (r#"[{"op":"move","from":"/a","path":"/a/b"}]"#, "/0/from"),
+            (r#"[{"op":"add","path":"/a","value":tru}]"#, "/0/value"),
+        ] {
+            let err = JsonPatch::new(src.as_bytes().to_vec()).unwrap_err();
+            assert_eq!(err.get_pointer(), pointer, "{}", src);
+        }
+    }
+
+    #[test]
+    fn a_patch_that_does_not_apply_names_the_target_path() {
+        for (src, pointer) in [
+            (r#"[{"op":"remove","path":"/x/y"}]"#, "/x/y"),
+            (r#"[{"op":"add","path":"/b/5","value":1}]"#, "/b/5"),
+            (r#"[{"op":"test","path":"/a","value":2}]"#, "/a"),
+            (r#"[{"op":"copy","from":"/x","path":"/y"}]"#, "/x"),
+        ] {
+            let err = patch(src).apply_to_json(br#"{"a":1,"b":[]}"#).unwrap_err();
+            assert_eq!(err.get_pointer(), pointer, "{}", src);
+        }
+    }
+
+    #[test]
+    fn merges_as_rfc_7396_describes() {
+        for (target, patch, expected) in [
+            (r#"{"a":"b"}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+            (r#"{"a":"b"}"#, r#"{"b":"c"}"#, r#"{"a":"b","b":"c"}"#),
+            (r#"{"a":"b"}"#, r#"{"a":null}"#, r#"{}"#),
+            (r#"{"a":"b","b":"c"}"#, r#"{"a":null}"#, r#"{"b":"c"}"#),
+            (r#"{"a":["b"]}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+            (r#"{"a":"c"}"#, r#"{"a":["b"]}"#, r#"{"a":["b"]}"#),
+            (
+                r#"{"a":{"b":"c"}}"#,
+                r#"{"a":{"b":"d","c":null}}"#,
+                r#"{"a":{"b":"d"}}"#,
+            ),
+            (r#"{"a":[{"b":"c"}]}"#, r#"{"a":[1]}"#, r#"{"a":[1]}"#),
+            (r#"["a","b"]"#, r#"["c","d"]"#, r#"["c","d"]"#),
+            (r#"{"a":"b"}"#, r#"["c"]"#, r#"["c"]"#),
+            (r#"{"a":"foo"}"#, r#"null"#, r#"null"#),
+            (r#"{"e":null}"#, r#"{"a":1}"#, r#"{"e":null,"a":1}"#),
+            (r#"[1,2]"#, r#"{"a":"b","c":null}"#, r#"{"a":"b"}"#),
+            (
+                r#"{}"#,
+                r#"{"a":{"bb":{"ccc":null}}}"#,
+                r#"{"a":{"bb":{}}}"#,
+            ),
+        ] {
+            let result = merge_patch(patch).apply_to_json(target.as_bytes()).unwrap();
+            assert_eq!(result, expected, "{} + {}", target, patch);
+        }
+    }
+
+    #[test]
+    fn a_merge_patch_must_be_json() {
+        // Into the broken value, or the container `my-json` gave up in.
+        let err = JsonMergePatch::new(br#"{"a":{"b":}}"#.to_vec()).unwrap_err();
+        assert!(err.get_pointer().starts_with("/a"), "{}", err);
+    }
+}
@@ -0,0 +1,487 @@
+//! [`JwtClaims<T>`]: the claims of an HMAC-signed JWT (RFC 7519) carried as
+//! `Authorization: Bearer <token>`, for an `#[http_header(name = "Authorization")]` field.
+//!
+//! A token is only accepted once its signature (HS256 / HS384 / HS512, RFC 7518 §3.2) checks out
+//! against the key the registered [`JwtVerifier`] hands out, and its `exp`, `nbf` and `aud` claims
+//! let it through; then the payload is deserialized into `T`. The verifier is registered once per
+//! process ([`register_jwt_verifier`]) because a field is parsed with no context but the request.
+
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::authorization::strip_scheme;
+use super::Base64UrlBytes;
+use crate::crypto::{hmac, verify_hmac, Hmac, Sha256, Sha384, Sha512};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwtAlgorithm {
+    HS256,
+    HS384,
+    HS512,
+}
+
+impl JwtAlgorithm {
+    /// The JOSE `alg` of the algorithm.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::HS256 => "HS256",
+            Self::HS384 => "HS384",
+            Self::HS512 => "HS512",
+        }
+    }
+
+    fn sign(&self, key: &[u8], data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::HS256 => hmac::<Hmac<Sha256>>(key, data),
+            Self::HS384 => hmac::<Hmac<Sha384>>(key, data),
+            Self::HS512 => hmac::<Hmac<Sha512>>(key, data),
+        }
+    }
+
+    fn verify(&self, key: &[u8], data: &[u8], signature: &[u8]) -> bool {
+        match self {
+            Self::HS256 => verify_hmac::<Hmac<Sha256>>(key, data, signature),
+            Self::HS384 => verify_hmac::<Hmac<Sha384>>(key, data, signature),
+            Self::HS512 => verify_hmac::<Hmac<Sha512>>(key, data, signature),
+        }
+    }
+}
+
+impl FromStr for JwtAlgorithm {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        match src {
+            "HS256" => Ok(Self::HS256),
+            "HS384" => Ok(Self::HS384),
+            "HS512" => Ok(Self::HS512),
+            _ => Err(format!("JWT algorithm '{}' is not supported", src)),
+        }
+    }
+}
+
+/// The JOSE header of a token: what [`JwtVerifier::get_key`] picks the key by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwtHeader {
+    pub alg: JwtAlgorithm,
+    pub kid: Option<String>,
+}
+
+/// Where a [`JwtClaims`] field gets its key and its rules from.
+pub trait JwtVerifier: Send + Sync {
+    /// The HMAC key a token with this header is signed with; `None` rejects the token (an unknown
+    /// `kid`, an algorithm the service does not accept).
+    fn get_key(&self, header: &JwtHeader) -> Option<Vec<u8>>;
+
+    /// The audience a token has to name in its `aud`; `None` accepts any.
+    fn get_audience(&self) -> Option<&str> {
+        None
+    }
+
+    /// How far `exp` and `nbf` may be off, for clocks that drift apart.
+    fn get_leeway_seconds(&self) -> i64 {
+        0
+    }
+
+    /// The time `exp` and `nbf` are checked against.
+    fn now(&self) -> DateTimeAsMicroseconds {
+        DateTimeAsMicroseconds::now()
+    }
+}
+
+static JWT_VERIFIER: RwLock<Option<Arc<dyn JwtVerifier>>> = RwLock::new(None);
+
+/// Sets the verifier every [`JwtClaims`] field of the process is checked with, replacing the one
+/// registered before. Until one is, no token is accepted.
+pub fn register_jwt_verifier(verifier: impl JwtVerifier + 'static) {
+    *JWT_VERIFIER.write().unwrap() = Some(Arc::new(verifier));
+}
+
+fn get_registered_verifier() -> Option<Arc<dyn JwtVerifier>> {
+    JWT_VERIFIER.read().unwrap().clone()
+}
+
+/// The verified claims of `Authorization: Bearer <jwt>`.
+///
+/// Parsing it ([`FromStr`], and so the header field) goes through the registered
+/// [`JwtVerifier`]; [`Self::verify`] takes one explicitly. On the client, [`Self::sign`] builds
+/// the token, and the field is written back as `as_str()` like any other credential. The token
+/// stays out of `Debug`.
+#[derive(Clone)]
+pub struct JwtClaims<T> {
+    claims: T,
+    header: JwtHeader,
+    authorization: String,
+}
+
+impl<T> JwtClaims<T> {
+    pub fn get_claims(&self) -> &T {
+        &self.claims
+    }
+
+    pub fn into_claims(self) -> T {
+        self.claims
+    }
+
+    pub fn get_header(&self) -> &JwtHeader {
+        &self.header
+    }
+
+    /// The compact token itself.
+    pub fn get_token(&self) -> &str {
+        &self.authorization["Bearer ".len()..]
+    }
+
+    /// The header value, as it goes on the wire.
+    pub fn as_str(&self) -> &str {
+        self.authorization.as_str()
+    }
+}
+
+impl<T: Serialize> JwtClaims<T> {
+    pub fn sign(
+        claims: T,
+        alg: JwtAlgorithm,
+        kid: Option<&str>,
+        key: &[u8],
+    ) -> Result<Self, String> {
+        let mut header = serde_json::json!({ "alg": alg.as_str(), "typ": "JWT" });
+        if let Some(kid) = kid {
+            header["kid"] = serde_json::Value::String(kid.to_string());
+        }
+
+        let payload =
+            serde_json::to_vec(&claims).map_err(|err| format!("Can not write claims: {}", err))?;
+
+        let mut token = format!(
+            "{}.{}",
+            Base64UrlBytes::new(header.to_string().into_bytes()).as_str(),
+            Base64UrlBytes::new(payload).as_str()
+        );
+        let signature = Base64UrlBytes::new(alg.sign(key, token.as_bytes()));
+        token.push('.');
+        token.push_str(signature.as_str());
+
+        Ok(Self {
+            claims,
+            header: JwtHeader {
+                alg,
+                kid: kid.map(|kid| kid.to_string()),
+            },
+            authorization: format!("Bearer {}", token),
+        })
+    }
+}
+
+impl<T: DeserializeOwned> JwtClaims<T> {
+    /// Reads `Bearer <jwt>` and checks it against `verifier`. The error never quotes the token.
+    pub fn verify(src: &str, verifier: &dyn JwtVerifier) -> Result<Self, String> {
+        let token = strip_scheme(src, "Bearer")
+            .ok_or_else(|| "Authorization is not of the Bearer scheme".to_string())?;
+
+        let mut parts = token.split('.');
+        let (encoded_header, encoded_payload, encoded_signature) =
+            match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                (Some(header), Some(payload), Some(signature), None) => {
+                    (header, payload, signature)
+                }
+                _ => return Err("A JWT has three dot-separated parts".to_string()),
+            };
+
+        let header = read_json_part(encoded_header, "header")?;
+        let alg: JwtAlgorithm = match header.get("alg") {
+            Some(serde_json::Value::String(alg)) => alg.parse()?,
+            _ => return Err("JWT header has no 'alg'".to_string()),
+        };
+        let kid = match header.get("kid") {
+            None => None,
+            Some(serde_json::Value::String(kid)) => Some(kid.to_string()),
+            Some(_) => return Err("JWT 'kid' is not a string".to_string()),
+        };
+        let header = JwtHeader { alg, kid };
+
+        let key = verifier
+            .get_key(&header)
+            .ok_or_else(|| "No key to verify the JWT with".to_string())?;
+
+        let signature = encoded_signature
+            .parse::<Base64UrlBytes>()
+            .map_err(|_| "JWT signature is not url-safe base64".to_string())?;
+        let signed = &token[..encoded_header.len() + 1 + encoded_payload.len()];
+        if !alg.verify(&key, signed.as_bytes(), signature.as_bytes()) {
+            return Err("JWT signature does not match".to_string());
+        }
+
+        let payload = read_json_part(encoded_payload, "payload")?;
+        check_registered_claims(&payload, verifier)?;
+
+        let claims = serde_json::from_value(payload)
+            .map_err(|err| format!("Can not read JWT claims: {}", err))?;
+
+        Ok(Self {
+            claims,
+            header,
+            authorization: format!("Bearer {}", token),
+        })
+    }
+}
+
+impl<T: DeserializeOwned> FromStr for JwtClaims<T> {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        match get_registered_verifier() {
+            Some(verifier) => Self::verify(src, verifier.as_ref()),
+            None => Err("No JWT verifier is registered".to_string()),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for JwtClaims<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JwtClaims")
+            .field("claims", &self.claims)
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+/// A JSON object, base64url-encoded.
+fn read_json_part(src: &str, part: &str) -> Result<serde_json::Value, String> {
+    let bytes = src
+        .parse::<Base64UrlBytes>()
+        .map_err(|_| format!("JWT {} is not url-safe base64", part))?;
+
+    match serde_json::from_slice(bytes.as_bytes()) {
+        Ok(value @ serde_json::Value::Object(_)) => Ok(value),
+        _ => Err(format!("JWT {} is not a JSON object", part)),
+    }
+}
+
+/// `exp` and `nbf` (RFC 7519 §4.1.4 / §4.1.5, seconds since the epoch) and, when the verifier
+/// names an audience, `aud` (§4.1.3: a string or an array of them).
+fn check_registered_claims(
+    payload: &serde_json::Value,
+    verifier: &dyn JwtVerifier,
+) -> Result<(), String> {
+    let now = verifier.now().unix_microseconds as f64 / 1_000_000.0;
+    let leeway = verifier.get_leeway_seconds() as f64;
+
+    if let Some(exp) = read_numeric_date(payload, "exp")? {
+        if now >= exp + leeway {
+            return Err("JWT has expired".to_string());
+        }
+    }
+
+    if let Some(nbf) = read_numeric_date(payload, "nbf")? {
+        if now + leeway < nbf {
+            return Err("JWT is not valid yet".to_string());
+        }
+    }
+
+    if let Some(audience) = verifier.get_audience() {
+        let accepted = match payload.get("aud") {
+            Some(serde_json::Value::String(aud)) => aud == audience,
+            Some(serde_json::Value::Array(auds)) => {
+                auds.iter().any(|aud| aud.as_str() == Some(audience))
+            }
+            _ => false,
+        };
+
+        if !accepted {
+            return Err("JWT is not issued for this audience".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+fn read_numeric_date(payload: &serde_json::Value, claim: &str) -> Result<Option<f64>, String> {
+    match payload.get(claim) {
+        None => Ok(None),
+        Some(value) => match value.as_f64() {
+            Some(date) => Ok(Some(date)),
+            None => Err(format!("JWT '{}' is not a number", claim)),
+        },
+    }
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use serde::de::DeserializeOwned;
+
+    use super::JwtClaims;
+    use crate::http_input::{HttpInputValue, HttpParseError};
+    use crate::schema::data_types::{DataTypeProvider, HttpDataType};
+    use crate::schema::in_parameters::HttpSecurityScheme;
+
+    // A token that does not verify is a credential the server refuses, so it is `Forbidden`
+    // rather than a malformed value — and the message names the reason, never the token.
+    impl<'s, T: DeserializeOwned> TryInto<JwtClaims<T>> for HttpInputValue<'s> {
+        type Error = HttpParseError;
+
+        fn try_into(self) -> Result<JwtClaims<T>, Self::Error> {
+            let value = self.as_string()?;
+            value.parse().map_err(|reason: String| {
+                HttpParseError::Forbidden(format!("{}: {}", self.get_name(), reason))
+            })
+        }
+    }
+
+    impl<T> DataTypeProvider for JwtClaims<T> {
+        fn get_data_type() -> HttpDataType {
+            HttpDataType::as_string()
+        }
+
+        fn get_security_scheme() -> Option<HttpSecurityScheme> {
+            Some(HttpSecurityScheme::Bearer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use super::{JwtAlgorithm, JwtClaims, JwtHeader, JwtVerifier};
+
+    const KEY: &[u8] = b"your-256-bit-secret";
+
+    struct Verifier {
+        audience: Option<&'static str>,
+        now: i64,
+    }
+
+    impl JwtVerifier for Verifier {
+        fn get_key(&self, header: &JwtHeader) -> Option<Vec<u8>> {
+            match header.kid.as_deref() {
+                None | Some("k1") => Some(KEY.to_vec()),
+                Some(_) => None,
+            }
+        }
+
+        fn get_audience(&self) -> Option<&str> {
+            self.audience
+        }
+
+        fn now(&self) -> DateTimeAsMicroseconds {
+            DateTimeAsMicroseconds::new(self.now * 1_000_000)
+        }
+    }
+
+    const AT_1000: Verifier = Verifier {
+        audience: None,
+        now: 1000,
+    };
+
+    fn verify(token: &str, verifier: &Verifier) -> Result<serde_json::Value, String> {
+        JwtClaims::<serde_json::Value>::verify(&format!("Bearer {}", token), verifier)
+            .map(|claims| claims.into_claims())
+    }
+
+    #[test]
+    fn verifies_a_known_hs256_token() {
+        // jwt.io's HS256 example.
+        let token = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.\
+                     eyJzdWIiOiIxMjM0NTY3ODkwIiwibmFtZSI6IkpvaG4gRG9lIiwiaWF0IjoxNTE2MjM5MDIyfQ.\
+                     SflKxwRJSMeKKF2QT4fwpMeJf36POk6yJV_adQssw5c";
+        let claims = verify(token, &AT_1000).unwrap();
+        assert_eq!(claims["name"], "John Doe");
+
+        let tampered = token.replace("SflK", "SflL");
+        assert_eq!(
+            verify(&tampered, &AT_1000).unwrap_err(),
+            "JWT signature does not match"
+        );
+    }
+
+    #[test]
+    fn every_algorithm_round_trips() {
+        for alg in [
+            JwtAlgorithm::HS256,
+            JwtAlgorithm::HS384,
+            JwtAlgorithm::HS512,
+        ] {
+            let claims = serde_json::json!({ "sub": "ann", "exp": 2000 });
+            let signed = JwtClaims::sign(claims.clone(), alg, Some("k1"), KEY).unwrap();
+
+            let parsed = JwtClaims::<serde_json::Value>::verify(signed.as_str(), &AT_1000).unwrap();
+            assert_eq!(parsed.get_claims(), &claims);
+            assert_eq!(parsed.get_header().alg, alg);
+            assert_eq!(parsed.get_token(), signed.get_token());
+        }
+
+        let other_kid =
+            JwtClaims::sign(serde_json::json!({}), JwtAlgorithm::HS256, Some("k2"), KEY).unwrap();
+        assert_eq!(
+            verify(other_kid.get_token(), &AT_1000).unwrap_err(),
+            "No key to verify the JWT with"
+        );
+    }
+
+    #[test]
+    fn checks_exp_nbf_and_aud() {
+        let token = |claims: serde_json::Value| {
+            JwtClaims::sign(claims, JwtAlgorithm::HS256, None, KEY)
+                .unwrap()
+                .get_token()
+                .to_string()
+        };
+
+        assert!(verify(&token(serde_json::json!({ "exp": 1001 })), &AT_1000).is_ok());
+        assert_eq!(
+            verify(&token(serde_json::json!({ "exp": 1000 })), &AT_1000).unwrap_err(),
+            "JWT has expired"
+        );
+        assert_eq!(
+            verify(&token(serde_json::json!({ "nbf": 1001 })), &AT_1000).unwrap_err(),
+            "JWT is not valid yet"
+        );
+
+        let orders = Verifier {
+            audience: Some("orders"),
+            now: 1000,
+        };
+        assert!(verify(&token(serde_json::json!({ "aud": "orders" })), &orders).is_ok());
+        assert!(verify(
+            &token(serde_json::json!({ "aud": ["a", "orders"] })),
+            &orders
+        )
+        .is_ok());
+        for claims in [
+            serde_json::json!({ "aud": "billing" }),
+            serde_json::json!({}),
+        ] {
+            assert_eq!(
+                verify(&token(claims), &orders).unwrap_err(),
+                "JWT is not issued for this audience"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_tokens() {
+        let none = "eyJhbGciOiJub25lIn0.e30.";
+        assert_eq!(
+            verify(none, &AT_1000).unwrap_err(),
+            "JWT algorithm 'none' is not supported"
+        );
+
+        for token in ["a.b", "a.b.c.d", "%%.e30.x", "e30.e30.x"] {
+            assert!(verify(token, &AT_1000).is_err(), "{:?}", token);
+        }
+
+        let signed = JwtClaims::sign(
+            serde_json::json!({ "sub": "ann" }),
+            JwtAlgorithm::HS256,
+            None,
+            KEY,
+        )
+        .unwrap();
+        assert!(!format!("{:?}", signed).contains(signed.get_token()));
+    }
+}
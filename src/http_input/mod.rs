@@ -2,10 +2,17 @@
 //!
 //! Layout convention: the **types** live at the root of this module — the error
 //! ([`HttpParseError`]), the raw/file field types ([`RawData`], [`RawDataTyped`],
-//! [`FileContent`]), the custom field types ([`PasswordHttpInputField`]), and — behind the
-//! `server` feature — the concrete value type (`HttpInputValue`). All the **logic** — the
-//! JSON/url-encoded/form-data body readers, the value→field conversions, and the one abstraction
-//! the server implements (`core::THttpRequest`) — lives under [`self::core`].
+//! [`FileContent`]), the status of a failed parse ([`HttpParseErrorStatusPolicy`]), the bit-flag
+//! set ([`HttpFlags`]), the tri-state body member ([`Patch`]), the
+//! parse profile ([`ParseProfile`]), the string-carried simple types ([`DecimalString`], [`Uuid`],
+//! [`Uri`], [`EmailAddress`], [`IsoDuration`], [`Base64Bytes`], [`Base64UrlBytes`]), the patch documents ([`JsonPatch`],
+//! [`JsonMergePatch`]), the `Authorization` headers ([`BasicAuthCredentials`],
+//! [`BearerToken`], [`JwtClaims`]), the client behind the proxies ([`ClientInfo`],
+//! [`TrustedProxies`]), the conditional headers ([`EntityTag`], [`ETagCondition`],
+//! [`HttpDate`]), the range headers ([`RangeHeader`], [`ContentRange`]), the custom field types
+//! ([`PasswordHttpInputField`]), and — behind the `server` feature — the concrete value type (`HttpInputValue`). All the **logic** — the JSON/url-encoded/form-data body readers, the
+//! value→field conversions, and the one abstraction the server implements (`core::THttpRequest`)
+//! — lives under [`self::core`].
 //!
 //! This is the runtime half of the derive-generated sync `parse`. It was ported out of
 //! `my-http-server-core` so both the client (schema + request builder) and the server read the
@@ -30,12 +37,23 @@
 
 pub mod core;
 
+mod authorization;
 mod body_as_stream;
+mod client_info;
+mod conditional;
 mod error;
 mod file_content;
+mod flags;
+mod json_patch;
+mod jwt;
+mod parse_profile;
 mod password;
+mod patch;
+mod range;
 mod raw_data;
 mod raw_data_typed;
+mod simple_types;
+mod status_policy;
 // The parse engine's value type: only a server reads values out of an incoming request.
 #[cfg(feature = "server")]
 mod value;
@@ -43,13 +61,31 @@ mod value;
 // All ungated: the channel carries a body in BOTH directions — a server reading an incoming
 // `#[http_body_as_stream]` field, and a client streaming an outgoing body out of the same model —
 // so a wasm client that does not enable `server` needs the whole thing.
+pub use authorization::{BasicAuthCredentials, BearerToken};
 pub use body_as_stream::{
     HttpBodyAsStream, HttpBodyReader, HttpBodyStreamSender, BODY_STREAM_DEFAULT_BUFFER,
 };
+pub use client_info::{register_trusted_proxies, ClientInfo, IpNetwork, TrustedProxies};
+pub use conditional::{ETagCondition, EntityTag, HttpDate, PreconditionOutcome, Preconditions};
 pub use error::HttpParseError;
 pub use file_content::FileContent;
+pub use flags::{HttpFlags, HttpFlagsEnum};
+pub use json_patch::{
+    JsonMergePatch, JsonPatch, JsonPatchError, JSON_MERGE_PATCH_CONTENT_TYPE,
+    JSON_PATCH_CONTENT_TYPE,
+};
+pub use jwt::{register_jwt_verifier, JwtAlgorithm, JwtClaims, JwtHeader, JwtVerifier};
+pub use parse_profile::ParseProfile;
 pub use password::PasswordHttpInputField;
+pub use patch::Patch;
+pub use range::{
+    ByteRange, ByteRangeSpec, ContentRange, RangeHeader, RangeOutcome, MAX_BYTE_RANGES,
+};
 pub use raw_data::RawData;
 pub use raw_data_typed::RawDataTyped;
+pub use simple_types::{
+    Base64Bytes, Base64UrlBytes, DecimalString, EmailAddress, IsoDuration, Uri, Uuid,
+};
+pub use status_policy::{DefaultHttpParseErrorStatusPolicy, HttpParseErrorStatusPolicy};
 #[cfg(feature = "server")]
 pub use value::HttpInputValue;
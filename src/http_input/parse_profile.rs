@@ -0,0 +1,170 @@
+#[cfg(feature = "server")]
+use super::{HttpInputValue, HttpParseError, Patch};
+
+/// How forgiving the parse of a scalar value is: `#[http_input(parse_profile = "…")]` on a model,
+/// `parse_profile = "…"` on one field (which wins over the model's).
+///
+/// The same rules hold for every source — path, query, header, form field and JSON body member —
+/// and the schema carries the profile of each parameter
+/// ([`crate::schema::in_parameters::HttpInputParameter::parse_profile`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseProfile {
+    /// What a model without the attribute gets: `true` / `false` (any case) and `1` / `0` for a
+    /// boolean, a JSON number or boolean may also come as a string, and whitespace or an empty
+    /// value is a parse error like any other stray text.
+    #[default]
+    Standard,
+    /// For hand-written query strings and legacy clients: `yes` / `no` and `on` / `off` are
+    /// booleans too, whitespace around a value is ignored, and an empty (or all-whitespace) value
+    /// is the same as no value — `None` for an `Option`, the `default` if there is one, a
+    /// missing-parameter error otherwise.
+    Lenient,
+    /// For machine clients that should be told about sloppy input: a boolean is exactly `true` or
+    /// `false`, a JSON number or boolean must be a JSON number or boolean (not a string), and
+    /// whitespace or an empty value is an error.
+    Strict,
+}
+
+impl ParseProfile {
+    /// The attribute spelling, which is also what the schema shows.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Lenient => "lenient",
+            Self::Strict => "strict",
+        }
+    }
+
+    /// The boolean a textual value spells under this profile.
+    pub fn parse_bool(&self, value: &str) -> Option<bool> {
+        let value = self.trim(value);
+        match self {
+            Self::Strict => match value {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            },
+            Self::Standard | Self::Lenient => {
+                if value == "1" || value.eq_ignore_ascii_case("true") {
+                    return Some(true);
+                }
+
+                if value == "0" || value.eq_ignore_ascii_case("false") {
+                    return Some(false);
+                }
+
+                if *self == Self::Lenient {
+                    if value.eq_ignore_ascii_case("yes") || value.eq_ignore_ascii_case("on") {
+                        return Some(true);
+                    }
+
+                    if value.eq_ignore_ascii_case("no") || value.eq_ignore_ascii_case("off") {
+                        return Some(false);
+                    }
+                }
+
+                None
+            }
+        }
+    }
+
+    /// The part of a scalar value that is parsed: the surrounding whitespace is dropped only when
+    /// lenient.
+    pub fn trim<'v>(&self, value: &'v str) -> &'v str {
+        match self {
+            Self::Lenient => value.trim(),
+            Self::Standard | Self::Strict => value,
+        }
+    }
+
+    /// Whether a value read as `value` counts as not sent at all.
+    pub fn is_absent(&self, value: &str) -> bool {
+        *self == Self::Lenient && value.trim().is_empty()
+    }
+
+    /// Whether a JSON number or boolean may arrive as a JSON string (`"42"`, `"true"`).
+    pub fn accepts_quoted_scalars(&self) -> bool {
+        *self != Self::Strict
+    }
+}
+
+/// What the derive-generated `parse` wraps a read in when a field's profile is not `Standard`.
+#[cfg(feature = "server")]
+impl ParseProfile {
+    /// An optional read: the value converts under this profile, and a lenient blank is `None`.
+    pub fn apply<'s>(self, value: Option<HttpInputValue<'s>>) -> Option<HttpInputValue<'s>> {
+        let value = value?.with_profile(self);
+        if value.is_absent() {
+            return None;
+        }
+
+        Some(value)
+    }
+
+    /// A required read: a lenient blank is reported as missing.
+    pub fn apply_required<'s>(
+        self,
+        value: Result<HttpInputValue<'s>, HttpParseError>,
+    ) -> Result<HttpInputValue<'s>, HttpParseError> {
+        let value = value?.with_profile(self);
+        if value.is_absent() {
+            return Err(HttpParseError::required(value.get_name(), value.get_src()));
+        }
+
+        Ok(value)
+    }
+
+    /// A tri-state read: a lenient blank is `Absent`, never `Null`.
+    pub fn apply_patch<'s>(self, value: Patch<HttpInputValue<'s>>) -> Patch<HttpInputValue<'s>> {
+        match value {
+            Patch::Value(value) => match self.apply(Some(value)) {
+                Some(value) => Patch::Value(value),
+                None => Patch::Absent,
+            },
+            other => other,
+        }
+    }
+}
+
+impl std::fmt::Display for ParseProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseProfile;
+
+    #[test]
+    fn booleans_per_profile() {
+        for (value, expected) in [
+            // (standard, lenient, strict)
+            ("true", (Some(true), Some(true), Some(true))),
+            ("false", (Some(false), Some(false), Some(false))),
+            ("TRUE", (Some(true), Some(true), None)),
+            ("1", (Some(true), Some(true), None)),
+            ("0", (Some(false), Some(false), None)),
+            ("yes", (None, Some(true), None)),
+            ("Off", (None, Some(false), None)),
+            (" true ", (None, Some(true), None)),
+            ("", (None, None, None)),
+        ] {
+            let actual = (
+                ParseProfile::Standard.parse_bool(value),
+                ParseProfile::Lenient.parse_bool(value),
+                ParseProfile::Strict.parse_bool(value),
+            );
+            assert_eq!(actual, expected, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn only_lenient_drops_whitespace_and_empty_values() {
+        assert_eq!(ParseProfile::Lenient.trim(" 42\t"), "42");
+        assert_eq!(ParseProfile::Strict.trim(" 42"), " 42");
+        assert!(ParseProfile::Lenient.is_absent(" "));
+        assert!(!ParseProfile::Standard.is_absent(""));
+        assert!(!ParseProfile::Strict.is_absent(""));
+    }
+}
@@ -0,0 +1,191 @@
+//! A tri-state JSON body member, for PATCH semantics.
+//!
+//! A plain `Option<T>` cannot tell "the key was not sent" from "the key was sent as `null`": the
+//! writer omits a `None` and the reader treats absent and `null` alike. A PATCH needs both —
+//! absent means *leave it unchanged*, `null` means *clear it*. [`Patch<T>`] carries the three
+//! states; `Option<Option<T>>` is accepted as the same thing (`None` = absent, `Some(None)` =
+//! `null`), for models that would rather not name a crate type.
+//!
+//! Only a `#[http_body]` field or a member of a `MyHttpInputObjectStructure` can be tri-state —
+//! a query string, a header or a form field has no `null`. The schema documents such a field as
+//! `nullable` and not required.
+
+/// The value of a tri-state body member: absent, `null`, or a value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Patch<T> {
+    /// The key was not sent: leave the target unchanged.
+    Absent,
+    /// The key was sent as `null`: clear the target.
+    Null,
+    /// The key was sent with a value: set the target.
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Self::Absent)
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    pub fn is_value(&self) -> bool {
+        matches!(self, Self::Value(_))
+    }
+
+    pub fn as_value(&self) -> Option<&T> {
+        match self {
+            Self::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn into_value(self) -> Option<T> {
+        match self {
+            Self::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_ref(&self) -> Patch<&T> {
+        match self {
+            Self::Absent => Patch::Absent,
+            Self::Null => Patch::Null,
+            Self::Value(value) => Patch::Value(value),
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Patch<U> {
+        match self {
+            Self::Absent => Patch::Absent,
+            Self::Null => Patch::Null,
+            Self::Value(value) => Patch::Value(f(value)),
+        }
+    }
+
+    pub fn try_map<U, E>(self, f: impl FnOnce(T) -> Result<U, E>) -> Result<Patch<U>, E> {
+        match self {
+            Self::Absent => Ok(Patch::Absent),
+            Self::Null => Ok(Patch::Null),
+            Self::Value(value) => Ok(Patch::Value(f(value)?)),
+        }
+    }
+
+    /// `None` = absent, `Some(None)` = `null`, `Some(Some(value))` = a value.
+    pub fn into_option(self) -> Option<Option<T>> {
+        match self {
+            Self::Absent => None,
+            Self::Null => Some(None),
+            Self::Value(value) => Some(Some(value)),
+        }
+    }
+
+    /// Applies the patch to a stored value: absent leaves it, `null` clears it, a value replaces
+    /// it.
+    pub fn apply_to(self, target: &mut Option<T>) {
+        match self {
+            Self::Absent => {}
+            Self::Null => *target = None,
+            Self::Value(value) => *target = Some(value),
+        }
+    }
+}
+
+impl<T> Default for Patch<T> {
+    fn default() -> Self {
+        Self::Absent
+    }
+}
+
+impl<T> From<Option<Option<T>>> for Patch<T> {
+    fn from(value: Option<Option<T>>) -> Self {
+        match value {
+            None => Self::Absent,
+            Some(None) => Self::Null,
+            Some(Some(value)) => Self::Value(value),
+        }
+    }
+}
+
+// my-json: `Absent` never reaches the writer — the generated object writer omits the key for it —
+// so anything that is not a value is `null`. Reading goes through
+// `my_http_utils::read_json_object_patch_field`, which can see whether the key was there at all.
+impl<T: my_json::json_writer::JsonValueWriter> my_json::json_writer::JsonValueWriter for Patch<T> {
+    const IS_ARRAY: bool = T::IS_ARRAY;
+
+    fn write(&self, dest: &mut String) {
+        match self {
+            Self::Value(value) => value.write(dest),
+            _ => dest.push_str("null"),
+        }
+    }
+}
+
+/// serde sees `null` or a value; to keep "absent" apart from `null`, put
+/// `#[serde(default, skip_serializing_if = "Patch::is_absent")]` on the field.
+impl<T: serde::Serialize> serde::Serialize for Patch<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Value(value) => serializer.serialize_some(value),
+            _ => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Patch<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<T>::deserialize(deserializer)? {
+            Some(value) => Ok(Self::Value(value)),
+            None => Ok(Self::Null),
+        }
+    }
+}
+
+/// Schema (server-only): the value's own type. That the member may be omitted or `null` is a
+/// property of the field, not of the type, and is recorded on the `HttpField` instead.
+#[cfg(feature = "server")]
+impl<T: crate::schema::data_types::DataTypeProvider> crate::schema::data_types::DataTypeProvider
+    for Patch<T>
+{
+    const STRICT_JSON: &'static crate::http_input::core::StrictJson = T::STRICT_JSON;
+
+    fn get_data_type() -> crate::schema::data_types::HttpDataType {
+        T::get_data_type()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Patch;
+
+    #[test]
+    fn apply_to_leaves_clears_or_sets() {
+        let mut target = Some(1);
+
+        Patch::Absent.apply_to(&mut target);
+        assert_eq!(target, Some(1));
+
+        Patch::Value(2).apply_to(&mut target);
+        assert_eq!(target, Some(2));
+
+        Patch::Null.apply_to(&mut target);
+        assert_eq!(target, None);
+    }
+
+    #[test]
+    fn converts_to_and_from_option_option() {
+        for value in [None, Some(None), Some(Some(5))] {
+            assert_eq!(Patch::from(value).into_option(), value);
+        }
+    }
+
+    #[test]
+    fn serde_reads_null_and_a_value() {
+        for (json, expected) in [("null", Patch::Null), (r#""x""#, Patch::Value("x".to_string()))] {
+            let value: Patch<String> = serde_json::from_str(json).unwrap();
+            assert_eq!(value, expected);
+            assert_eq!(serde_json::to_string(&value).unwrap(), json);
+        }
+    }
+}
@@ -0,0 +1,543 @@
+//! Range requests (RFC 9110 §14): [`RangeHeader`] for the `Range` a client asks with,
+//! [`ByteRange`] for one range resolved against the length of the representation, and
+//! [`ContentRange`] for the `Content-Range` of a `206 Partial Content` (or a
+//! `416 Range Not Satisfiable`) answer.
+//!
+//! A single range is served by cutting it out of the source stream with
+//! [`HttpBodyAsStream::with_range`](crate::http_input::HttpBodyAsStream::with_range); several go
+//! out as a `multipart/byteranges` body ([`ByteRangesBody`](crate::body::ByteRangesBody)).
+//!
+//! Like the conditional headers, [`RangeHeader`] and [`ContentRange`] keep their header value as
+//! it goes on the wire, so the client request builder writes a field of them back through
+//! `as_str()`.
+
+use std::str::FromStr;
+
+/// The most ranges [`RangeHeader::resolve`] serves once they are coalesced; a set of more is
+/// answered in full. Each range costs a part of its own, so many small ones cost the server far
+/// more than the bytes they carry (RFC 9110 §14.2).
+pub const MAX_BYTE_RANGES: usize = 16;
+
+/// A range of bytes, `start` to `end` **inclusive** — as `Range` and `Content-Range` count them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ByteRange {
+    start: u64,
+    end: u64,
+}
+
+impl ByteRange {
+    /// `end` is inclusive, so it can not be `u64::MAX`: the length of the range would not fit.
+    pub fn new(start: u64, end: u64) -> Result<Self, String> {
+        if start > end {
+            return Err(format!(
+                "Byte range {}-{} ends before it starts",
+                start, end
+            ));
+        }
+
+        if end == u64::MAX {
+            return Err(format!("Byte range {}-{} is too long", start, end));
+        }
+
+        Ok(Self { start, end })
+    }
+
+    pub fn get_start(&self) -> u64 {
+        self.start
+    }
+
+    /// The last byte of the range, inclusive.
+    pub fn get_end(&self) -> u64 {
+        self.end
+    }
+
+    /// How many bytes the range covers; never `0`.
+    pub fn get_length(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+/// One range of a `Range` header, as the client wrote it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteRangeSpec {
+    /// `500-999`
+    Closed { first: u64, last: u64 },
+    /// `9500-` — to the end of the representation.
+    Open { first: u64 },
+    /// `-500` — the last `length` bytes.
+    Suffix { length: u64 },
+}
+
+impl ByteRangeSpec {
+    /// The range this spec stands for in a representation of `complete_length` bytes; `None`
+    /// when it is not satisfiable there. A range running past the end is cut at the end.
+    pub fn resolve(&self, complete_length: u64) -> Option<ByteRange> {
+        if complete_length == 0 {
+            return None;
+        }
+
+        let last_byte = complete_length - 1;
+
+        let (start, end) = match *self {
+            Self::Closed { first, last } => (first, last.min(last_byte)),
+            Self::Open { first } => (first, last_byte),
+            Self::Suffix { length } => {
+                if length == 0 {
+                    return None;
+                }
+                (complete_length.saturating_sub(length), last_byte)
+            }
+        };
+
+        if start > last_byte {
+            return None;
+        }
+
+        Some(ByteRange { start, end })
+    }
+}
+
+impl std::fmt::Display for ByteRangeSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Closed { first, last } => write!(f, "{}-{}", first, last),
+            Self::Open { first } => write!(f, "{}-", first),
+            Self::Suffix { length } => write!(f, "-{}", length),
+        }
+    }
+}
+
+/// `Range: bytes=0-499, 1000-, -200` — one or several byte ranges. Only the `bytes` unit is
+/// read; a server that gets another one ignores the header and answers in full.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeHeader {
+    specs: Vec<ByteRangeSpec>,
+    header: String,
+}
+
+impl RangeHeader {
+    pub fn from_specs(specs: Vec<ByteRangeSpec>) -> Result<Self, String> {
+        if specs.is_empty() {
+            return Err("A byte range set can not be empty".to_string());
+        }
+
+        for spec in &specs {
+            if let ByteRangeSpec::Closed { first, last } = spec {
+                if first > last {
+                    return Err(format!("Byte range {} ends before it starts", spec));
+                }
+            }
+        }
+
+        let header = format!(
+            "bytes={}",
+            specs
+                .iter()
+                .map(|spec| spec.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        Ok(Self { specs, header })
+    }
+
+    /// `bytes=first-last`
+    pub fn single(first: u64, last: u64) -> Result<Self, String> {
+        Self::from_specs(vec![ByteRangeSpec::Closed { first, last }])
+    }
+
+    pub fn get_specs(&self) -> &[ByteRangeSpec] {
+        &self.specs
+    }
+
+    /// What to answer for a representation of `complete_length` bytes. The ranges that are not
+    /// satisfiable are left out, and the rest are coalesced: sorted, with overlapping and
+    /// adjacent ones merged. With none left the answer is `416`; with more than
+    /// [`MAX_BYTE_RANGES`] left it is the whole representation.
+    pub fn resolve(&self, complete_length: u64) -> RangeOutcome {
+        let mut ranges: Vec<ByteRange> = self
+            .specs
+            .iter()
+            .filter_map(|spec| spec.resolve(complete_length))
+            .collect();
+
+        ranges.sort_unstable_by_key(|range| range.start);
+
+        let mut result: Vec<ByteRange> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match result.last_mut() {
+                // `end` is never `u64::MAX`, so `end + 1` does not overflow.
+                Some(last) if range.start <= last.end + 1 => last.end = last.end.max(range.end),
+                _ => result.push(range),
+            }
+        }
+
+        if result.is_empty() {
+            RangeOutcome::NotSatisfiable
+        } else if result.len() > MAX_BYTE_RANGES {
+            RangeOutcome::Full
+        } else {
+            RangeOutcome::Partial(result)
+        }
+    }
+
+    /// The header value, as it goes on the wire.
+    pub fn as_str(&self) -> &str {
+        self.header.as_str()
+    }
+}
+
+/// What [`RangeHeader::resolve`] decides a `Range` request gets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeOutcome {
+    /// `200 OK` with the whole representation: the range set is too fragmented to be worth
+    /// serving as asked.
+    Full,
+    /// `206 Partial Content` with these ranges — one is cut out of the body, several go out as
+    /// `multipart/byteranges`.
+    Partial(Vec<ByteRange>),
+    /// `416 Range Not Satisfiable` with [`ContentRange::unsatisfied`].
+    NotSatisfiable,
+}
+
+impl RangeOutcome {
+    pub fn get_status_code(&self) -> u16 {
+        match self {
+            Self::Full => 200,
+            Self::Partial(_) => 206,
+            Self::NotSatisfiable => 416,
+        }
+    }
+}
+
+impl FromStr for RangeHeader {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let Some((unit, range_set)) = src.trim().split_once('=') else {
+            return Err(format!("'{}' is not a byte range set", src));
+        };
+
+        if !unit.trim().eq_ignore_ascii_case("bytes") {
+            return Err(format!("Range unit '{}' is not supported", unit.trim()));
+        }
+
+        let mut specs = Vec::new();
+
+        for spec in range_set.split(',').map(str::trim) {
+            if spec.is_empty() {
+                continue;
+            }
+
+            let spec = read_range_spec(spec)
+                .ok_or_else(|| format!("'{}' is not a valid byte range", spec))?;
+            specs.push(spec);
+        }
+
+        Self::from_specs(specs)
+    }
+}
+
+fn read_range_spec(src: &str) -> Option<ByteRangeSpec> {
+    let (first, last) = src.split_once('-')?;
+
+    match (first, last) {
+        ("", length) => Some(ByteRangeSpec::Suffix {
+            length: read_number(length)?,
+        }),
+        (first, "") => Some(ByteRangeSpec::Open {
+            first: read_number(first)?,
+        }),
+        (first, last) => Some(ByteRangeSpec::Closed {
+            first: read_number(first)?,
+            last: read_number(last)?,
+        }),
+    }
+}
+
+/// `1*DIGIT` — no sign, no space; a number past `u64` is not one.
+fn read_number(src: &str) -> Option<u64> {
+    if src.is_empty() || !src.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    src.parse().ok()
+}
+
+/// `Content-Range: bytes 0-499/1234` — which part of the representation a `206` carries, out of
+/// how many bytes (`bytes 0-499/*` when the length is not known). A `416` carries
+/// `bytes */1234` ([`Self::unsatisfied`]) instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentRange {
+    range: Option<ByteRange>,
+    complete_length: Option<u64>,
+    header: String,
+}
+
+impl ContentRange {
+    /// The range has to lie within `complete_length` when that is known.
+    pub fn new(range: ByteRange, complete_length: Option<u64>) -> Result<Self, String> {
+        let header = match complete_length {
+            Some(complete_length) => {
+                if range.end >= complete_length {
+                    return Err(format!(
+                        "Byte range {}-{} is past the end of {} bytes",
+                        range.start, range.end, complete_length
+                    ));
+                }
+                format!("bytes {}-{}/{}", range.start, range.end, complete_length)
+            }
+            None => format!("bytes {}-{}/*", range.start, range.end),
+        };
+
+        Ok(Self {
+            range: Some(range),
+            complete_length,
+            header,
+        })
+    }
+
+    /// `bytes */complete_length`, for a `416 Range Not Satisfiable`.
+    pub fn unsatisfied(complete_length: u64) -> Self {
+        Self {
+            range: None,
+            complete_length: Some(complete_length),
+            header: format!("bytes */{}", complete_length),
+        }
+    }
+
+    /// The range carried; `None` for [`Self::unsatisfied`].
+    pub fn get_range(&self) -> Option<ByteRange> {
+        self.range
+    }
+
+    /// `None` when the sender did not know it (`/*`).
+    pub fn get_complete_length(&self) -> Option<u64> {
+        self.complete_length
+    }
+
+    /// The header value, as it goes on the wire.
+    pub fn as_str(&self) -> &str {
+        self.header.as_str()
+    }
+}
+
+impl FromStr for ContentRange {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let err = || format!("'{}' is not a valid Content-Range", src);
+
+        let src = src.trim();
+        let Some((unit, rest)) = src.split_once(' ') else {
+            return Err(err());
+        };
+
+        if !unit.eq_ignore_ascii_case("bytes") {
+            return Err(format!("Range unit '{}' is not supported", unit));
+        }
+
+        let (range, complete_length) = rest.trim().split_once('/').ok_or_else(err)?;
+
+        let complete_length = match complete_length {
+            "*" => None,
+            complete_length => Some(read_number(complete_length).ok_or_else(err)?),
+        };
+
+        if range == "*" {
+            return complete_length.map(Self::unsatisfied).ok_or_else(err);
+        }
+
+        let (start, end) = range.split_once('-').ok_or_else(err)?;
+        let start = read_number(start).ok_or_else(err)?;
+        let end = read_number(end).ok_or_else(err)?;
+
+        Self::new(ByteRange::new(start, end)?, complete_length)
+    }
+}
+
+macro_rules! impl_display {
+    ($($t:ty),+) => {
+        $(
+            impl std::fmt::Display for $t {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+        )+
+    };
+}
+
+impl_display!(RangeHeader, ContentRange);
+
+#[cfg(feature = "server")]
+mod server {
+    use super::{ContentRange, RangeHeader};
+    use crate::schema::data_types::{DataTypeProvider, HttpDataType};
+
+    macro_rules! impl_data_type {
+        ($($t:ty),+) => {
+            $(
+                impl DataTypeProvider for $t {
+                    fn get_data_type() -> HttpDataType {
+                        HttpDataType::as_string()
+                    }
+                }
+            )+
+        };
+    }
+
+    impl_data_type!(RangeHeader, ContentRange);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: u64, end: u64) -> ByteRange {
+        ByteRange::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn range_headers() {
+        let header: RangeHeader = " Bytes=0-499, 1000-,,-200 ".parse().unwrap();
+        assert_eq!(
+            header.get_specs(),
+            &[
+                ByteRangeSpec::Closed {
+                    first: 0,
+                    last: 499
+                },
+                ByteRangeSpec::Open { first: 1000 },
+                ByteRangeSpec::Suffix { length: 200 },
+            ]
+        );
+        assert_eq!(header.as_str(), "bytes=0-499, 1000-, -200");
+        assert_eq!(header.to_string().parse::<RangeHeader>().unwrap(), header);
+
+        assert_eq!(RangeHeader::single(5, 9).unwrap().as_str(), "bytes=5-9");
+
+        for src in [
+            "",
+            "bytes=",
+            "bytes=,",
+            "items=0-1",
+            "0-499",
+            "bytes=500-499",
+            "bytes=-",
+            "bytes=1-2-3",
+            "bytes=+1-2",
+            "bytes=0 -1",
+            "bytes=18446744073709551616-",
+        ] {
+            assert!(src.parse::<RangeHeader>().is_err(), "{}", src);
+        }
+    }
+
+    fn partial(ranges: Vec<ByteRange>) -> RangeOutcome {
+        RangeOutcome::Partial(ranges)
+    }
+
+    #[test]
+    fn ranges_resolve_against_the_length() {
+        let header: RangeHeader = "bytes=9500-, 0-499, -200".parse().unwrap();
+        assert_eq!(
+            header.resolve(10_000),
+            partial(vec![range(0, 499), range(9500, 9999)])
+        );
+
+        // A suffix longer than the representation is all of it.
+        let header: RangeHeader = "bytes=-500".parse().unwrap();
+        assert_eq!(header.resolve(100), partial(vec![range(0, 99)]));
+
+        // Unsatisfiable ranges are left out; with none left, the answer is 416.
+        let header: RangeHeader = "bytes=50-60, 200-, -0".parse().unwrap();
+        assert_eq!(header.resolve(100), partial(vec![range(50, 60)]));
+        assert_eq!(header.resolve(50), RangeOutcome::NotSatisfiable);
+        assert_eq!(header.resolve(0).get_status_code(), 416);
+
+        // A last byte no length reaches is cut at the end like any other.
+        let header: RangeHeader = "bytes=0-18446744073709551615".parse().unwrap();
+        assert_eq!(
+            header.resolve(u64::MAX),
+            partial(vec![range(0, u64::MAX - 1)])
+        );
+    }
+
+    #[test]
+    fn ranges_are_coalesced_and_capped() {
+        // Overlapping and adjacent ranges merge; a gap keeps them apart.
+        let header: RangeHeader = "bytes=0-499, 400-20000, 30000-30099, 20001-20010, 40000-"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            header.resolve(50_000),
+            partial(vec![
+                range(0, 20010),
+                range(30000, 30099),
+                range(40000, 49999)
+            ])
+        );
+
+        // Many ranges that coalesce into one are served as one.
+        let specs = (0..1000)
+            .map(|i| ByteRangeSpec::Closed { first: i, last: i })
+            .collect();
+        let header = RangeHeader::from_specs(specs).unwrap();
+        assert_eq!(header.resolve(5000), partial(vec![range(0, 999)]));
+
+        // Too many apart: the whole representation, with a 200.
+        let specs = (0..=MAX_BYTE_RANGES as u64)
+            .map(|i| ByteRangeSpec::Closed {
+                first: i * 10,
+                last: i * 10,
+            })
+            .collect();
+        let header = RangeHeader::from_specs(specs).unwrap();
+        assert_eq!(header.resolve(5000), RangeOutcome::Full);
+        assert_eq!(header.resolve(5000).get_status_code(), 200);
+
+        let specs = (0..MAX_BYTE_RANGES as u64)
+            .map(|i| ByteRangeSpec::Closed {
+                first: i * 10,
+                last: i * 10,
+            })
+            .collect();
+        let header = RangeHeader::from_specs(specs).unwrap();
+        assert_eq!(header.resolve(5000).get_status_code(), 206);
+    }
+
+    #[test]
+    fn content_ranges() {
+        let content_range = ContentRange::new(range(0, 499), Some(1234)).unwrap();
+        assert_eq!(content_range.as_str(), "bytes 0-499/1234");
+        assert_eq!(
+            content_range.to_string().parse::<ContentRange>().unwrap(),
+            content_range
+        );
+
+        let unknown: ContentRange = "bytes 100-199/*".parse().unwrap();
+        assert_eq!(unknown.get_range(), Some(range(100, 199)));
+        assert_eq!(unknown.get_complete_length(), None);
+
+        let unsatisfied: ContentRange = "bytes */1234".parse().unwrap();
+        assert_eq!(unsatisfied, ContentRange::unsatisfied(1234));
+        assert_eq!(unsatisfied.get_range(), None);
+
+        assert!(ContentRange::new(range(0, 1234), Some(1234)).is_err());
+        assert!(ByteRange::new(0, u64::MAX).is_err());
+        assert_eq!(range(0, u64::MAX - 1).get_length(), u64::MAX);
+
+        for src in [
+            "bytes */*",
+            "bytes 0-499",
+            "bytes 499-0/1234",
+            "bytes 0-1234/1234",
+            "items 0-1/2",
+            "bytes=0-1/2",
+            "bytes 0-18446744073709551615/*",
+        ] {
+            assert!(src.parse::<ContentRange>().is_err(), "{}", src);
+        }
+    }
+}
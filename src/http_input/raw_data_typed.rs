@@ -80,4 +80,8 @@ impl<T: DeserializeOwned + crate::schema::data_types::DataTypeProvider>
     fn get_data_type() -> crate::schema::data_types::HttpDataType {
         T::get_data_type()
     }
+
+    fn get_body_content_type() -> Option<&'static str> {
+        Some("application/json")
+    }
 }
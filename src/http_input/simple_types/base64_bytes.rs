@@ -0,0 +1,200 @@
+use std::str::FromStr;
+
+/// Binary data carried as standard base64 (RFC 4648 §4) — the OpenAPI `type: string, format:
+/// byte`. The way to put bytes into a query parameter, a header or a JSON member.
+///
+/// Written padded (`aGk=`); read padded or not. The url-safe alphabet, whitespace and non-zero
+/// trailing bits are rejected, so a value has exactly one spelling on the way out.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Base64Bytes {
+    bytes: Vec<u8>,
+    encoded: String,
+}
+
+/// Binary data carried as url-safe base64 (RFC 4648 §5: `-` and `_`, no padding) — the spelling
+/// JWTs and most tokens use, and safe in a URL without percent-encoding. Schema: `type: string,
+/// format: base64url`.
+///
+/// Written unpadded; read padded or not. The standard alphabet's `+` and `/` are rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Base64UrlBytes {
+    bytes: Vec<u8>,
+    encoded: String,
+}
+
+const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+macro_rules! impl_base64_bytes {
+    ($t:ident, $alphabet:expr, $pad:expr, $name:literal) => {
+        impl $t {
+            pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+                let bytes = bytes.into();
+                let encoded = encode(&bytes, $alphabet, $pad);
+                Self { bytes, encoded }
+            }
+
+            /// The encoded text, as it goes on the wire.
+            pub fn as_str(&self) -> &str {
+                self.encoded.as_str()
+            }
+
+            pub fn as_bytes(&self) -> &[u8] {
+                self.bytes.as_slice()
+            }
+
+            pub fn into_bytes(self) -> Vec<u8> {
+                self.bytes
+            }
+        }
+
+        impl FromStr for $t {
+            type Err = String;
+
+            fn from_str(src: &str) -> Result<Self, Self::Err> {
+                match decode(src, $alphabet) {
+                    Some(bytes) => Ok(Self::new(bytes)),
+                    None => Err(format!("'{}' is not {}", src, $name)),
+                }
+            }
+        }
+
+        impl From<Vec<u8>> for $t {
+            fn from(bytes: Vec<u8>) -> Self {
+                Self::new(bytes)
+            }
+        }
+
+        impl From<&[u8]> for $t {
+            fn from(bytes: &[u8]) -> Self {
+                Self::new(bytes)
+            }
+        }
+    };
+}
+
+impl_base64_bytes!(Base64Bytes, STANDARD, true, "base64");
+impl_base64_bytes!(Base64UrlBytes, URL_SAFE, false, "url-safe base64");
+
+impl_string_simple_type!(Base64Bytes, Byte);
+impl_string_simple_type!(Base64UrlBytes, Base64Url);
+
+fn encode(src: &[u8], alphabet: &[u8; 64], pad: bool) -> String {
+    let mut result = String::with_capacity(src.len().div_ceil(3) * 4);
+
+    for chunk in src.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
+        let group = (b0 << 16) | (b1 << 8) | b2;
+
+        // 1 byte → 2 symbols, 2 → 3, 3 → 4.
+        for index in 0..chunk.len() + 1 {
+            let symbol = (group >> (18 - 6 * index)) & 0x3f;
+            result.push(alphabet[symbol as usize] as char);
+        }
+
+        if pad {
+            for _ in chunk.len()..3 {
+                result.push('=');
+            }
+        }
+    }
+
+    result
+}
+
+/// `None` on a symbol outside `alphabet`, a misplaced or miscounted `=`, a length no encoding
+/// produces, or non-zero bits past the last byte (the mark of a value that was not canonically
+/// encoded).
+fn decode(src: &str, alphabet: &[u8; 64]) -> Option<Vec<u8>> {
+    let bytes = src.as_bytes();
+
+    let data = match bytes.iter().position(|b| *b == b'=') {
+        Some(index) => {
+            // Padding only ever completes the last 4-symbol group.
+            if bytes.len() % 4 != 0 || bytes.len() - index > 2 {
+                return None;
+            }
+            if bytes[index..].iter().any(|b| *b != b'=') {
+                return None;
+            }
+            &bytes[..index]
+        }
+        None => bytes,
+    };
+
+    if data.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut result = Vec::with_capacity(data.len() * 3 / 4);
+
+    for chunk in data.chunks(4) {
+        let mut group = 0u32;
+        for (index, c) in chunk.iter().enumerate() {
+            let value = alphabet.iter().position(|a| a == c)? as u32;
+            group |= value << (18 - 6 * index);
+        }
+
+        let byte_count = chunk.len() - 1;
+        let unused_bits = group & (0xff_ffff >> (8 * byte_count));
+        if unused_bits != 0 {
+            return None;
+        }
+
+        for index in 0..byte_count {
+            result.push((group >> (16 - 8 * index)) as u8);
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Base64Bytes, Base64UrlBytes};
+
+    #[test]
+    fn encodes_rfc_4648_test_vectors() {
+        for (src, standard, url_safe) in [
+            ("", "", ""),
+            ("f", "Zg==", "Zg"),
+            ("fo", "Zm8=", "Zm8"),
+            ("foo", "Zm9v", "Zm9v"),
+            ("foob", "Zm9vYg==", "Zm9vYg"),
+            ("fooba", "Zm9vYmE=", "Zm9vYmE"),
+            ("foobar", "Zm9vYmFy", "Zm9vYmFy"),
+        ] {
+            assert_eq!(Base64Bytes::new(src.as_bytes()).as_str(), standard);
+            assert_eq!(Base64UrlBytes::new(src.as_bytes()).as_str(), url_safe);
+
+            assert_eq!(standard.parse::<Base64Bytes>().unwrap().as_bytes(), src.as_bytes());
+            assert_eq!(url_safe.parse::<Base64UrlBytes>().unwrap().as_bytes(), src.as_bytes());
+        }
+    }
+
+    #[test]
+    fn each_variant_keeps_to_its_alphabet() {
+        let bytes = [0xfb, 0xff, 0xbf];
+
+        assert_eq!(Base64Bytes::new(bytes.as_slice()).as_str(), "+/+/");
+        assert_eq!(Base64UrlBytes::new(bytes.as_slice()).as_str(), "-_-_");
+
+        assert!("-_-_".parse::<Base64Bytes>().is_err());
+        assert!("+/+/".parse::<Base64UrlBytes>().is_err());
+    }
+
+    #[test]
+    fn padding_is_optional_on_the_way_in() {
+        assert_eq!("Zg".parse::<Base64Bytes>().unwrap().as_str(), "Zg==");
+        assert_eq!("Zg==".parse::<Base64UrlBytes>().unwrap().as_str(), "Zg");
+    }
+
+    #[test]
+    fn rejects_what_is_not_base64() {
+        for src in ["Z", "Zg=", "Zg===", "Z===", "Zg==Zg==", "=Zg=", "Zh==", "Zm 9v", "Zm9v\n"] {
+            assert!(src.parse::<Base64Bytes>().is_err(), "{:?}", src);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::str::FromStr;
+
+/// A decimal number carried as its verbatim text — `100.00` stays `100.00`, with its scale, and
+/// no digit is lost to an `f64`. Schema: `type: string, format: decimal`.
+///
+/// Accepts an optional sign, digits with an optional fraction, and an optional exponent
+/// (`-12.50`, `0.1`, `1e-3`). A JSON body member may be a string or a bare number; either way the
+/// source text is what is kept. It is always written back out as a JSON string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DecimalString(String);
+
+impl DecimalString {
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0.starts_with('-')
+    }
+}
+
+impl FromStr for DecimalString {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let bytes = src.as_bytes();
+        let mut pos = 0;
+
+        if matches!(bytes.first(), Some(b'-') | Some(b'+')) {
+            pos += 1;
+        }
+
+        let int_digits = count_digits(&bytes[pos..]);
+        pos += int_digits;
+
+        let mut frac_digits = 0;
+        if bytes.get(pos) == Some(&b'.') {
+            pos += 1;
+            frac_digits = count_digits(&bytes[pos..]);
+            if frac_digits == 0 {
+                return Err(format!("'{}' is not a decimal: no digits after '.'", src));
+            }
+            pos += frac_digits;
+        }
+
+        if int_digits == 0 && frac_digits == 0 {
+            return Err(format!("'{}' is not a decimal", src));
+        }
+
+        if matches!(bytes.get(pos), Some(b'e') | Some(b'E')) {
+            pos += 1;
+            if matches!(bytes.get(pos), Some(b'-') | Some(b'+')) {
+                pos += 1;
+            }
+            let exp_digits = count_digits(&bytes[pos..]);
+            if exp_digits == 0 {
+                return Err(format!("'{}' is not a decimal: empty exponent", src));
+            }
+            pos += exp_digits;
+        }
+
+        if pos != bytes.len() {
+            return Err(format!("'{}' is not a decimal", src));
+        }
+
+        Ok(Self(src.to_string()))
+    }
+}
+
+fn count_digits(src: &[u8]) -> usize {
+    src.iter().take_while(|b| b.is_ascii_digit()).count()
+}
+
+impl_string_simple_type!(DecimalString, Decimal);
+
+#[cfg(test)]
+mod tests {
+    use super::DecimalString;
+
+    #[test]
+    fn keeps_the_verbatim_text() {
+        for src in [
+            "100.00",
+            "-12.50",
+            "+3",
+            "0.1",
+            ".5",
+            "1e-3",
+            "2.5E+10",
+            "12345678901234567890.123",
+        ] {
+            let value: DecimalString = src.parse().unwrap();
+            assert_eq!(value.as_str(), src);
+        }
+    }
+
+    #[test]
+    fn rejects_what_is_not_a_decimal() {
+        for src in ["", "-", "1.", "1.2.3", "abc", "1e", "1 ", "0x10", "NaN"] {
+            assert!(src.parse::<DecimalString>().is_err(), "{:?}", src);
+        }
+    }
+
+    #[test]
+    fn json_writes_a_string_and_serde_reads_it_back() {
+        use my_json::json_writer::JsonValueWriter;
+
+        let value: DecimalString = "100.00".parse().unwrap();
+        let mut dest = String::new();
+        value.write(&mut dest);
+        assert_eq!(dest, r#""100.00""#);
+
+        let from_serde: DecimalString = serde_json::from_str(r#""100.00""#).unwrap();
+        assert_eq!(from_serde, value);
+    }
+}
@@ -0,0 +1,124 @@
+use std::str::FromStr;
+
+/// An e-mail address, `local@domain`. Schema: `type: string, format: email`.
+///
+/// The check is the pragmatic one, not the full RFC 5322 grammar (quoted local parts and comments
+/// are legal there and useless in an API): exactly one `@`; a local part of 1–64 characters with
+/// no whitespace, no leading / trailing / doubled dot; a domain of dot-separated labels, each
+/// 1–63 letters, digits or hyphens that neither starts nor ends with a hyphen, at least two of
+/// them. The text is kept as it was sent — case included, since the local part is case-sensitive.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EmailAddress(String);
+
+impl EmailAddress {
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    pub fn get_local_part(&self) -> &str {
+        match self.0.rfind('@') {
+            Some(index) => &self.0[..index],
+            None => "",
+        }
+    }
+
+    pub fn get_domain(&self) -> &str {
+        match self.0.rfind('@') {
+            Some(index) => &self.0[index + 1..],
+            None => "",
+        }
+    }
+}
+
+impl FromStr for EmailAddress {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{}' is not an e-mail address", src);
+
+        let mut parts = src.split('@');
+        let (local, domain) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(local), Some(domain), None) => (local, domain),
+            _ => return Err(invalid()),
+        };
+
+        let local_is_valid = !local.is_empty()
+            && local.len() <= 64
+            && !local.starts_with('.')
+            && !local.ends_with('.')
+            && !local.contains("..")
+            && local.chars().all(is_local_part_char);
+
+        if !local_is_valid {
+            return Err(invalid());
+        }
+
+        let labels: Vec<&str> = domain.split('.').collect();
+
+        let domain_is_valid = labels.len() >= 2
+            && domain.len() <= 253
+            && labels.iter().all(|label| {
+                !label.is_empty()
+                    && label.len() <= 63
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            });
+
+        if !domain_is_valid {
+            return Err(invalid());
+        }
+
+        Ok(Self(src.to_string()))
+    }
+}
+
+/// The unquoted local-part characters: everything printable except the RFC 5322 specials.
+fn is_local_part_char(c: char) -> bool {
+    !c.is_whitespace()
+        && !c.is_control()
+        && !matches!(
+            c,
+            '(' | ')' | ',' | ':' | ';' | '<' | '>' | '[' | ']' | '\\' | '"'
+        )
+}
+
+impl_string_simple_type!(EmailAddress, Email);
+
+#[cfg(test)]
+mod tests {
+    use super::EmailAddress;
+
+    #[test]
+    fn accepts_ordinary_addresses() {
+        let value: EmailAddress = "John.Doe+tag@mail.example.com".parse().unwrap();
+        assert_eq!(value.as_str(), "John.Doe+tag@mail.example.com");
+        assert_eq!(value.get_local_part(), "John.Doe+tag");
+        assert_eq!(value.get_domain(), "mail.example.com");
+    }
+
+    #[test]
+    fn rejects_what_is_not_an_address() {
+        for src in [
+            "",
+            "john",
+            "john@",
+            "@example.com",
+            "john@localhost",
+            "jo hn@example.com",
+            "john@@example.com",
+            "a@b@example.com",
+            ".john@example.com",
+            "jo..hn@example.com",
+            "john@-example.com",
+            "john@example..com",
+            "john@exa_mple.com",
+        ] {
+            assert!(src.parse::<EmailAddress>().is_err(), "{:?}", src);
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+/// An ISO-8601 duration — `P1Y2M10DT2H30M`, `PT0.5S`, `P2W`. Schema: `type: string,
+/// format: duration`.
+///
+/// The text is kept verbatim, and the components are parsed out once. Years and months have no
+/// fixed length, so [`IsoDuration::to_std_duration`] only answers for a duration that has none;
+/// weeks are 7 days and days are 24 hours, as ISO-8601 defines them for durations. Only the last
+/// component present may carry a fraction (`PT1.5H` is valid, `PT1.5H30M` is not); negative
+/// durations are not part of ISO-8601 and are rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IsoDuration {
+    text: String,
+    years: f64,
+    months: f64,
+    weeks: f64,
+    days: f64,
+    hours: f64,
+    minutes: f64,
+    seconds: f64,
+}
+
+impl IsoDuration {
+    /// Builds the canonical `PT…S` spelling of a fixed-length duration.
+    pub fn from_std_duration(duration: Duration) -> Self {
+        let secs = duration.as_secs();
+        let nanos = duration.subsec_nanos();
+
+        let text = if nanos == 0 {
+            format!("PT{}S", secs)
+        } else {
+            let fraction = format!("{:09}", nanos);
+            format!("PT{}.{}S", secs, fraction.trim_end_matches('0'))
+        };
+
+        Self {
+            text,
+            years: 0.0,
+            months: 0.0,
+            weeks: 0.0,
+            days: 0.0,
+            hours: 0.0,
+            minutes: 0.0,
+            seconds: duration.as_secs_f64(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.text.as_str()
+    }
+
+    pub fn get_years(&self) -> f64 {
+        self.years
+    }
+
+    pub fn get_months(&self) -> f64 {
+        self.months
+    }
+
+    pub fn get_weeks(&self) -> f64 {
+        self.weeks
+    }
+
+    pub fn get_days(&self) -> f64 {
+        self.days
+    }
+
+    pub fn get_hours(&self) -> f64 {
+        self.hours
+    }
+
+    pub fn get_minutes(&self) -> f64 {
+        self.minutes
+    }
+
+    pub fn get_seconds(&self) -> f64 {
+        self.seconds
+    }
+
+    /// The fixed length of the duration, or `None` when it has years or months — those depend on
+    /// the date they are counted from.
+    pub fn to_std_duration(&self) -> Option<Duration> {
+        if self.years != 0.0 || self.months != 0.0 {
+            return None;
+        }
+
+        let secs = self.weeks * 7.0 * 86_400.0
+            + self.days * 86_400.0
+            + self.hours * 3_600.0
+            + self.minutes * 60.0
+            + self.seconds;
+
+        Duration::try_from_secs_f64(secs).ok()
+    }
+}
+
+impl FromStr for IsoDuration {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{}' is not an ISO-8601 duration", src);
+
+        let rest = match src.strip_prefix('P') {
+            Some(rest) if !rest.is_empty() => rest,
+            _ => return Err(invalid()),
+        };
+
+        let mut result = Self {
+            text: src.to_string(),
+            years: 0.0,
+            months: 0.0,
+            weeks: 0.0,
+            days: 0.0,
+            hours: 0.0,
+            minutes: 0.0,
+            seconds: 0.0,
+        };
+
+        let (date_part, time_part) = match rest.find('T') {
+            Some(index) => (&rest[..index], Some(&rest[index + 1..])),
+            None => (rest, None),
+        };
+
+        if time_part == Some("") {
+            return Err(invalid());
+        }
+
+        let mut seen_fraction = false;
+        let date_components =
+            read_components(date_part, "YMWD", &mut seen_fraction).ok_or_else(invalid)?;
+        let time_components = read_components(time_part.unwrap_or(""), "HMS", &mut seen_fraction)
+            .ok_or_else(invalid)?;
+
+        if date_components.is_empty() && time_components.is_empty() {
+            return Err(invalid());
+        }
+
+        for (designator, value) in date_components {
+            match designator {
+                'Y' => result.years = value,
+                'M' => result.months = value,
+                'W' => result.weeks = value,
+                _ => result.days = value,
+            }
+        }
+
+        for (designator, value) in time_components {
+            match designator {
+                'H' => result.hours = value,
+                'M' => result.minutes = value,
+                _ => result.seconds = value,
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Reads `<number><designator>` pairs whose designators appear in `order`, each at most once and in
+/// that order. `None` on anything else, including a fraction that is not on the last component.
+fn read_components(src: &str, order: &str, seen_fraction: &mut bool) -> Option<Vec<(char, f64)>> {
+    let mut result = Vec::new();
+    let mut remaining_designators = order;
+    let mut number_start = 0;
+
+    for (index, c) in src.char_indices() {
+        if c.is_ascii_digit() || c == '.' || c == ',' {
+            continue;
+        }
+
+        let number = &src[number_start..index];
+        if number.is_empty() || *seen_fraction {
+            return None;
+        }
+
+        let position = remaining_designators.find(c)?;
+        remaining_designators = &remaining_designators[position + 1..];
+
+        // ISO-8601 allows a comma as the decimal sign.
+        let number = number.replace(',', ".");
+        if number.contains('.') {
+            *seen_fraction = true;
+        }
+
+        result.push((c, number.parse::<f64>().ok()?));
+        number_start = index + c.len_utf8();
+    }
+
+    if number_start != src.len() {
+        return None;
+    }
+
+    Some(result)
+}
+
+impl_string_simple_type!(IsoDuration, Duration);
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::IsoDuration;
+
+    #[test]
+    fn reads_every_component() {
+        let value: IsoDuration = "P1Y2M3W4DT5H6M7.5S".parse().unwrap();
+        assert_eq!(value.as_str(), "P1Y2M3W4DT5H6M7.5S");
+        assert_eq!(value.get_years(), 1.0);
+        assert_eq!(value.get_months(), 2.0);
+        assert_eq!(value.get_weeks(), 3.0);
+        assert_eq!(value.get_days(), 4.0);
+        assert_eq!(value.get_hours(), 5.0);
+        assert_eq!(value.get_minutes(), 6.0);
+        assert_eq!(value.get_seconds(), 7.5);
+        assert_eq!(value.to_std_duration(), None, "years/months have no fixed length");
+    }
+
+    #[test]
+    fn fixed_length_durations_convert() {
+        let value: IsoDuration = "P1DT1H30M".parse().unwrap();
+        assert_eq!(value.to_std_duration(), Some(Duration::from_secs(86_400 + 5_400)));
+
+        let value: IsoDuration = "PT0,5S".parse().unwrap();
+        assert_eq!(value.to_std_duration(), Some(Duration::from_millis(500)));
+
+        let value = IsoDuration::from_std_duration(Duration::from_millis(1_500));
+        assert_eq!(value.as_str(), "PT1.5S");
+        assert_eq!(value.as_str().parse::<IsoDuration>().unwrap(), value);
+    }
+
+    #[test]
+    fn rejects_what_is_not_a_duration() {
+        for src in [
+            "", "P", "PT", "1D", "P1", "P1H", "PT1D", "P1D2Y", "P1Y1Y", "PT1.5H30M", "P-1D",
+            "P1DT",
+        ] {
+            assert!(src.parse::<IsoDuration>().is_err(), "{:?}", src);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! String-carried simple types with a well-known schema `format`: [`DecimalString`], [`Uuid`],
+//! [`Uri`], [`EmailAddress`], [`IsoDuration`], and binary data as [`Base64Bytes`] /
+//! [`Base64UrlBytes`].
+//!
+//! Each one is validated on the way in (`FromStr`) and keeps its text, so it goes out exactly as
+//! it was accepted — the client request builder writes a struct-typed query / header / form field
+//! through `as_str()`, and a body member is a JSON string. The parsers are hand-rolled on purpose:
+//! pulling `uuid`, `url` or a decimal crate into a wasm client for a format check is not worth it.
+//!
+//! Everything shared — `Display`, the my-json writer/reader, serde and the schema — is stamped out
+//! by `impl_string_simple_type!`, so they cannot drift apart on how they travel.
+
+/// Implements the wire surface of a string-carried simple type. The type must have
+/// `as_str(&self) -> &str` and `FromStr<Err = String>`; `$simple_type` is its `HttpSimpleType`.
+/// Declared ahead of the `mod` items below so they see it by textual scope.
+macro_rules! impl_string_simple_type {
+    ($t:ty, $simple_type:ident) => {
+        impl std::fmt::Display for $t {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl my_json::json_writer::JsonValueWriter for $t {
+            const IS_ARRAY: bool = false;
+
+            fn write(&self, dest: &mut String) {
+                my_json::json_writer::JsonValueWriter::write(&self.as_str(), dest);
+            }
+        }
+
+        impl<'s> my_json::json_reader::JsonValueReader<'s> for $t {
+            fn from_json_value(
+                value: &my_json::json_reader::JsonValueRef<'s>,
+            ) -> Result<Self, my_json::json_reader::JsonParseError> {
+                // A string member is read with its escapes resolved; anything else (a bare number,
+                // which is what a decimal may arrive as) by its verbatim source text.
+                let text = match value.as_str() {
+                    Some(text) => text.into_string(),
+                    None => String::from_utf8_lossy(value.as_slice()).into_owned(),
+                };
+
+                text.parse()
+                    .map_err(|err: String| my_json::json_reader::JsonParseError::new(err))
+            }
+        }
+
+        impl serde::Serialize for $t {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $t {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let text = String::deserialize(deserializer)?;
+                text.parse().map_err(serde::de::Error::custom)
+            }
+        }
+
+        #[cfg(feature = "server")]
+        impl crate::schema::data_types::DataTypeProvider for $t {
+            fn get_data_type() -> crate::schema::data_types::HttpDataType {
+                crate::schema::data_types::HttpDataType::SimpleType(
+                    crate::schema::data_types::HttpSimpleType::$simple_type,
+                )
+            }
+        }
+    };
+}
+
+mod base64_bytes;
+mod decimal_string;
+mod email_address;
+mod iso_duration;
+mod uri;
+mod uuid;
+
+pub use base64_bytes::{Base64Bytes, Base64UrlBytes};
+pub use decimal_string::DecimalString;
+pub use email_address::EmailAddress;
+pub use iso_duration::IsoDuration;
+pub use uri::Uri;
+pub use uuid::Uuid;
@@ -0,0 +1,93 @@
+use std::str::FromStr;
+
+/// An absolute URI (RFC 3986): a scheme, a `:`, and the rest. Schema: `type: string, format: uri`.
+///
+/// Checked only as far as a parameter value needs: the scheme is `ALPHA *( ALPHA / DIGIT / "+" /
+/// "-" / "." )`, something follows the `:`, and there is no whitespace or control character
+/// anywhere. The text is kept verbatim — no normalisation, no percent-decoding — so it goes out
+/// exactly as it came in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Uri(String);
+
+impl Uri {
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// The scheme, as written (`https`, `urn`, `mailto`, …).
+    pub fn get_scheme(&self) -> &str {
+        match self.0.find(':') {
+            Some(index) => &self.0[..index],
+            None => "",
+        }
+    }
+}
+
+impl FromStr for Uri {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let colon = match src.find(':') {
+            Some(index) => index,
+            None => return Err(format!("'{}' is not an absolute URI: no scheme", src)),
+        };
+
+        let scheme = &src.as_bytes()[..colon];
+
+        let scheme_is_valid = match scheme.first() {
+            Some(first) => {
+                first.is_ascii_alphabetic()
+                    && scheme
+                        .iter()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, b'+' | b'-' | b'.'))
+            }
+            None => false,
+        };
+
+        if !scheme_is_valid {
+            return Err(format!("'{}' is not an absolute URI: invalid scheme", src));
+        }
+
+        if colon + 1 == src.len() {
+            return Err(format!("'{}' is not an absolute URI: nothing after the scheme", src));
+        }
+
+        if src.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(format!("'{}' is not a URI: contains whitespace", src));
+        }
+
+        Ok(Self(src.to_string()))
+    }
+}
+
+impl_string_simple_type!(Uri, Uri);
+
+#[cfg(test)]
+mod tests {
+    use super::Uri;
+
+    #[test]
+    fn accepts_absolute_uris_verbatim() {
+        for (src, scheme) in [
+            ("https://example.com/a?b=c#d", "https"),
+            ("urn:isbn:0451450523", "urn"),
+            ("mailto:john@example.com", "mailto"),
+            ("git+ssh://host/repo.git", "git+ssh"),
+        ] {
+            let value: Uri = src.parse().unwrap();
+            assert_eq!(value.as_str(), src);
+            assert_eq!(value.get_scheme(), scheme);
+        }
+    }
+
+    #[test]
+    fn rejects_what_is_not_an_absolute_uri() {
+        for src in ["", "/relative/path", "example.com", "1http://x", "https:", "https://a b"] {
+            assert!(src.parse::<Uri>().is_err(), "{:?}", src);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::str::FromStr;
+
+/// A UUID, kept in its canonical spelling: lowercase, hyphenated
+/// (`67e55044-10b1-426f-9247-bb680e5fe0c8`). Schema: `type: string, format: uuid`.
+///
+/// Reads the hyphenated form in either case, and the 32-digit form without hyphens; both are
+/// written back out hyphenated and lowercase. The version / variant bits are not checked — a UUID
+/// is an identifier here, not something this crate generates.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Uuid(String);
+
+impl Uuid {
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        let mut result = String::with_capacity(36);
+
+        for (index, byte) in bytes.iter().enumerate() {
+            if matches!(index, 4 | 6 | 8 | 10) {
+                result.push('-');
+            }
+            result.push(HEX[(byte >> 4) as usize] as char);
+            result.push(HEX[(byte & 0x0f) as usize] as char);
+        }
+
+        Self(result)
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn to_bytes(&self) -> [u8; 16] {
+        let mut result = [0u8; 16];
+        let mut digits = self.0.bytes().filter(|b| *b != b'-');
+
+        for byte in result.iter_mut() {
+            // The text was validated on the way in, so every digit is there and is hex.
+            let hi = hex_value(digits.next().unwrap_or(b'0')).unwrap_or(0);
+            let lo = hex_value(digits.next().unwrap_or(b'0')).unwrap_or(0);
+            *byte = (hi << 4) | lo;
+        }
+
+        result
+    }
+}
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let bytes = src.as_bytes();
+
+        let hyphenated = match bytes.len() {
+            36 => true,
+            32 => false,
+            _ => return Err(format!("'{}' is not a UUID", src)),
+        };
+
+        let mut result = [0u8; 16];
+        let mut digits = Vec::with_capacity(32);
+
+        for (index, c) in bytes.iter().enumerate() {
+            if hyphenated && matches!(index, 8 | 13 | 18 | 23) {
+                if *c != b'-' {
+                    return Err(format!("'{}' is not a UUID", src));
+                }
+                continue;
+            }
+
+            match hex_value(*c) {
+                Some(value) => digits.push(value),
+                None => return Err(format!("'{}' is not a UUID", src)),
+            }
+        }
+
+        for (index, byte) in result.iter_mut().enumerate() {
+            *byte = (digits[index * 2] << 4) | digits[index * 2 + 1];
+        }
+
+        Ok(Self::from_bytes(result))
+    }
+}
+
+impl From<[u8; 16]> for Uuid {
+    fn from(bytes: [u8; 16]) -> Self {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl_string_simple_type!(Uuid, Uuid);
+
+#[cfg(test)]
+mod tests {
+    use super::Uuid;
+
+    #[test]
+    fn canonicalises_every_accepted_spelling() {
+        for src in [
+            "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "67E55044-10B1-426F-9247-BB680E5FE0C8",
+            "67e5504410b1426f9247bb680e5fe0c8",
+        ] {
+            let value: Uuid = src.parse().unwrap();
+            assert_eq!(value.as_str(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+        }
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let value: Uuid = "00112233-4455-6677-8899-aabbccddeeff".parse().unwrap();
+        let bytes = value.to_bytes();
+        assert_eq!(bytes[0], 0x00);
+        assert_eq!(bytes[15], 0xff);
+        assert_eq!(Uuid::from_bytes(bytes), value);
+    }
+
+    #[test]
+    fn rejects_what_is_not_a_uuid() {
+        for src in [
+            "",
+            "67e55044-10b1-426f-9247-bb680e5fe0c",
+            "67e55044_10b1_426f_9247_bb680e5fe0c8",
+            "g7e55044-10b1-426f-9247-bb680e5fe0c8",
+            "67e5504410b1426f9247bb680e5fe0cz",
+        ] {
+            assert!(src.parse::<Uuid>().is_err(), "{:?}", src);
+        }
+    }
+}
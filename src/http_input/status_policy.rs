@@ -0,0 +1,50 @@
+use super::HttpParseError;
+
+/// Which HTTP status a failed parse is answered with.
+///
+/// Every method has a default — [`HttpParseError::status_code`] and the 400 / 422 the schema
+/// documents — so a server overrides only what it answers differently, e.g. a 400 where the default
+/// says 422:
+///
+/// ```
+/// use my_http_utils::http_input::{HttpParseError, HttpParseErrorStatusPolicy};
+///
+/// struct NoUnprocessableEntity;
+///
+/// impl HttpParseErrorStatusPolicy for NoUnprocessableEntity {
+///     fn status_code(&self, error: &HttpParseError) -> u16 {
+///         match error {
+///             HttpParseError::Validation(_) => 400,
+///             _ => error.status_code(),
+///         }
+///     }
+///
+///     fn documented_statuses(&self) -> Vec<(u16, &'static str)> {
+///         vec![(400, "A parameter is missing, malformed or invalid")]
+///     }
+/// }
+///
+/// let error = HttpParseError::Validation("not digits".to_string());
+/// assert_eq!(NoUnprocessableEntity.status_code(&error), 400);
+/// ```
+pub trait HttpParseErrorStatusPolicy {
+    fn status_code(&self, error: &HttpParseError) -> u16 {
+        error.status_code()
+    }
+
+    /// The statuses, with their descriptions, the schema adds to every route that has input
+    /// parameters (`schema::out_results::HttpResult::parse_error_results`). Keep it in line with
+    /// [`Self::status_code`]: these are the answers a client should be ready for.
+    fn documented_statuses(&self) -> Vec<(u16, &'static str)> {
+        vec![
+            (400, "A parameter is missing or can not be parsed"),
+            (422, "A parameter failed its validation"),
+        ]
+    }
+}
+
+/// [`HttpParseErrorStatusPolicy`] with nothing overridden.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultHttpParseErrorStatusPolicy;
+
+impl HttpParseErrorStatusPolicy for DefaultHttpParseErrorStatusPolicy {}
@@ -10,7 +10,7 @@ use super::core::convert_from_str;
 use super::core::data_src::{SRC_FORM_DATA, SRC_HEADER};
 use super::core::json_encoded_data::JsonEncodedValueAsString;
 use super::error::convert_reading_error;
-use super::HttpParseError;
+use super::{HttpParseError, ParseProfile};
 
 /// A single named value read out of an incoming request, before it is converted into a model
 /// field's concrete type. This is the concrete port of the server's `EncodedParamValue` — the
@@ -24,12 +24,22 @@ use super::HttpParseError;
 /// * `FormData` — a `multipart/form-data` part (value or file).
 /// * `Plain` — a header value: taken verbatim (headers are not percent-encoded).
 ///
+/// Every variant carries the [`ParseProfile`] its scalar conversions follow: `Standard` as read,
+/// the field's own profile once the derive applies it ([`Self::with_profile`]).
+///
+/// The enum and its variants are `#[non_exhaustive]`: a value is built with the `from_*`
+/// constructors and matched with `..`, so a source or a field added later breaks no caller.
+///
 /// All the `TryInto<T>` conversions live in [`super::core`] (`mappers`).
+#[non_exhaustive]
 pub enum HttpInputValue<'s> {
+    #[non_exhaustive]
     UrlEncoded {
         value: UrlEncodedValue<'s>,
         src: &'static str,
+        profile: ParseProfile,
     },
+    #[non_exhaustive]
     Json {
         // Owned (not `&'s …`): the value is now built lazily per lookup from `my_json`, borrowing
         // the body bytes directly, so there is no pre-parsed store to hand out a reference into —
@@ -37,21 +47,30 @@ pub enum HttpInputValue<'s> {
         name: &'s str,
         value: JsonEncodedValueAsString<'s>,
         src: &'static str,
+        profile: ParseProfile,
     },
+    #[non_exhaustive]
     FormData {
         name: &'s str,
         value: &'s FormDataItem<'s>,
+        profile: ParseProfile,
     },
+    #[non_exhaustive]
     Plain {
         name: &'s str,
         value: &'s str,
         src: &'static str,
+        profile: ParseProfile,
     },
 }
 
 impl<'s> HttpInputValue<'s> {
     pub fn from_url_encoded(value: UrlEncodedValue<'s>, src: &'static str) -> Self {
-        Self::UrlEncoded { value, src }
+        Self::UrlEncoded {
+            value,
+            src,
+            profile: ParseProfile::Standard,
+        }
     }
 
     /// Wraps a raw header value (no decoding).
@@ -60,6 +79,70 @@ impl<'s> HttpInputValue<'s> {
             name,
             value,
             src: SRC_HEADER,
+            profile: ParseProfile::Standard,
+        }
+    }
+
+    pub fn from_json(
+        name: &'s str,
+        value: JsonEncodedValueAsString<'s>,
+        src: &'static str,
+    ) -> Self {
+        Self::Json {
+            name,
+            value,
+            src,
+            profile: ParseProfile::Standard,
+        }
+    }
+
+    pub fn from_form_data(name: &'s str, value: &'s FormDataItem<'s>) -> Self {
+        Self::FormData {
+            name,
+            value,
+            profile: ParseProfile::Standard,
+        }
+    }
+
+    /// The same value, converted under `profile` from here on.
+    pub fn with_profile(mut self, profile: ParseProfile) -> Self {
+        match &mut self {
+            Self::UrlEncoded { profile: own, .. }
+            | Self::Json { profile: own, .. }
+            | Self::FormData { profile: own, .. }
+            | Self::Plain { profile: own, .. } => *own = profile,
+        }
+        self
+    }
+
+    pub fn get_profile(&self) -> ParseProfile {
+        match self {
+            Self::UrlEncoded { profile, .. }
+            | Self::Json { profile, .. }
+            | Self::FormData { profile, .. }
+            | Self::Plain { profile, .. } => *profile,
+        }
+    }
+
+    /// Whether the value counts as not sent under its profile: a lenient read takes an empty or
+    /// all-whitespace value (a JSON string one included) as absent. A file part is never blank.
+    pub fn is_absent(&self) -> bool {
+        let profile = self.get_profile();
+        if profile != ParseProfile::Lenient {
+            return false;
+        }
+
+        match self {
+            Self::UrlEncoded { value, .. } => value
+                .as_str_or_string()
+                .map(|decoded| profile.is_absent(decoded.as_str()))
+                .unwrap_or(false),
+            Self::Json { value, .. } => value.is_blank(),
+            Self::FormData { value, .. } => match value {
+                FormDataItem::ValueAsString { value, .. } => profile.is_absent(value),
+                FormDataItem::File { .. } => false,
+            },
+            Self::Plain { value, .. } => profile.is_absent(value),
         }
     }
 
@@ -83,7 +166,7 @@ impl<'s> HttpInputValue<'s> {
 
     pub fn as_string(&self) -> Result<String, HttpParseError> {
         match self {
-            Self::UrlEncoded { value, src } => {
+            Self::UrlEncoded { value, src, .. } => {
                 convert_reading_error(value.get_name(), value.as_string(), src)
             }
             Self::Json { value, .. } => value.as_string(),
@@ -92,62 +175,181 @@ impl<'s> HttpInputValue<'s> {
         }
     }
 
+    /// A string-carried value (`Uuid`, `DecimalString`, …); numbers go through
+    /// [`Self::parse_number`].
     pub fn parse<T: FromStr>(&self) -> Result<T, HttpParseError> {
         match self {
-            Self::UrlEncoded { value, src } => {
-                convert_reading_error(value.get_name(), value.parse(), src)
-            }
-            Self::Json { value, .. } => value.parse(),
-            Self::FormData { name, value } => {
-                convert_from_str::to_simple_value(name, value.unwrap_as_string()?, SRC_FORM_DATA)
+            Self::UrlEncoded {
+                value,
+                src,
+                profile,
+            } => {
+                if *profile == ParseProfile::Standard {
+                    return convert_reading_error(value.get_name(), value.parse(), src);
+                }
+
+                let decoded =
+                    convert_reading_error(value.get_name(), value.as_str_or_string(), src)?;
+                convert_from_str::to_simple_value(
+                    value.get_name(),
+                    profile.trim(decoded.as_str()),
+                    src,
+                )
             }
-            Self::Plain { name, value, src } => convert_from_str::to_simple_value(name, value, src),
+            Self::Json { value, profile, .. } => value.parse(*profile),
+            Self::FormData {
+                name,
+                value,
+                profile,
+            } => convert_from_str::to_simple_value(
+                name,
+                profile.trim(value.unwrap_as_string()?),
+                SRC_FORM_DATA,
+            ),
+            Self::Plain {
+                name,
+                value,
+                src,
+                profile,
+            } => convert_from_str::to_simple_value(name, profile.trim(value), src),
+        }
+    }
+
+    /// [`Self::parse`] for a number: only a JSON member tells a number from its string spelling,
+    /// which a strict read refuses.
+    pub fn parse_number<T: FromStr>(&self) -> Result<T, HttpParseError> {
+        match self {
+            Self::Json { value, profile, .. } => value.parse_number(*profile),
+            _ => self.parse(),
         }
     }
 
     pub fn as_bool(&self) -> Result<bool, HttpParseError> {
         match self {
             // Mirrors the server: bool reads the raw (still-escaped) query value — "true"/"1"
-            // etc. contain no escapable characters, so decoding is unnecessary.
-            Self::UrlEncoded { value, src } => convert_from_str::to_bool(value.get_name(), value.value, src),
-            Self::Json { value, .. } => value.as_bool(),
-            Self::FormData { name, value } => {
-                convert_from_str::to_bool(name, value.unwrap_as_string()?, SRC_FORM_DATA)
+            // etc. contain no escapable characters, so decoding is unnecessary. Only a lenient
+            // read decodes, for the whitespace it trims.
+            Self::UrlEncoded {
+                value,
+                src,
+                profile,
+            } => {
+                if *profile != ParseProfile::Lenient {
+                    return convert_from_str::to_bool(value.get_name(), value.value, *profile, src);
+                }
+
+                let decoded =
+                    convert_reading_error(value.get_name(), value.as_str_or_string(), src)?;
+                convert_from_str::to_bool(value.get_name(), decoded.as_str(), *profile, src)
             }
-            Self::Plain { name, value, src } => convert_from_str::to_bool(name, value, src),
+            Self::Json { value, profile, .. } => value.as_bool(*profile),
+            Self::FormData {
+                name,
+                value,
+                profile,
+            } => {
+                convert_from_str::to_bool(name, value.unwrap_as_string()?, *profile, SRC_FORM_DATA)
+            }
+            Self::Plain {
+                name,
+                value,
+                src,
+                profile,
+            } => convert_from_str::to_bool(name, value, *profile, src),
         }
     }
 
     pub fn as_date_time(&self) -> Result<DateTimeAsMicroseconds, HttpParseError> {
         match self {
-            Self::UrlEncoded { value, src } => {
-                let decoded = convert_reading_error(value.get_name(), value.as_str_or_string(), src)?;
-                convert_from_str::to_date_time(value.get_name(), decoded.as_str(), src)
+            Self::UrlEncoded {
+                value,
+                src,
+                profile,
+            } => {
+                let decoded =
+                    convert_reading_error(value.get_name(), value.as_str_or_string(), src)?;
+                convert_from_str::to_date_time(
+                    value.get_name(),
+                    profile.trim(decoded.as_str()),
+                    src,
+                )
             }
-            Self::Json { value, .. } => value.as_date_time(),
-            Self::FormData { name, value } => {
-                convert_from_str::to_date_time(name, value.unwrap_as_string()?, SRC_FORM_DATA)
+            Self::Json { value, profile, .. } => value.as_date_time(*profile),
+            Self::FormData {
+                name,
+                value,
+                profile,
+            } => convert_from_str::to_date_time(
+                name,
+                profile.trim(value.unwrap_as_string()?),
+                SRC_FORM_DATA,
+            ),
+            Self::Plain {
+                name,
+                value,
+                src,
+                profile,
+            } => convert_from_str::to_date_time(name, profile.trim(value), src),
+        }
+    }
+
+    /// [`Self::as_date_time`] for a field with a `format = "…"`: the derive calls this instead of
+    /// `try_into` for such a field.
+    pub fn as_formatted_date_time(
+        &self,
+        format: crate::DateTimeFormat,
+    ) -> Result<DateTimeAsMicroseconds, HttpParseError> {
+        match self {
+            Self::UrlEncoded {
+                value,
+                src,
+                profile,
+            } => {
+                let decoded =
+                    convert_reading_error(value.get_name(), value.as_str_or_string(), src)?;
+                convert_from_str::to_formatted_date_time(
+                    value.get_name(),
+                    profile.trim(decoded.as_str()),
+                    format,
+                    src,
+                )
             }
-            Self::Plain { name, value, src } => convert_from_str::to_date_time(name, value, src),
+            Self::Json { value, profile, .. } => value.as_formatted_date_time(format, *profile),
+            Self::FormData {
+                name,
+                value,
+                profile,
+            } => convert_from_str::to_formatted_date_time(
+                name,
+                profile.trim(value.unwrap_as_string()?),
+                format,
+                SRC_FORM_DATA,
+            ),
+            Self::Plain {
+                name,
+                value,
+                src,
+                profile,
+            } => convert_from_str::to_formatted_date_time(name, profile.trim(value), format, src),
         }
     }
 
     /// Deserialize the value's JSON representation into `T` (used for `Vec<T>` / `HashMap`).
     pub fn deserialize_json<T: DeserializeOwned>(&self) -> Result<T, HttpParseError> {
         match self {
-            Self::UrlEncoded { value, src } => {
+            Self::UrlEncoded { value, src, .. } => {
                 let decoded = convert_reading_error(value.get_name(), value.as_string(), src)?;
                 convert_from_str::to_json_from_slice(value.get_name(), decoded.as_bytes(), src)
             }
             Self::Json { value, .. } => value.deserialize(),
-            Self::FormData { name, value } => convert_from_str::to_json_from_slice(
+            Self::FormData { name, value, .. } => convert_from_str::to_json_from_slice(
                 name,
                 value.unwrap_as_string()?.as_bytes(),
                 SRC_FORM_DATA,
             ),
-            Self::Plain { name, value, src } => {
-                convert_from_str::to_json_from_slice(name, value.as_bytes(), src)
-            }
+            Self::Plain {
+                name, value, src, ..
+            } => convert_from_str::to_json_from_slice(name, value.as_bytes(), src),
         }
     }
 
@@ -0,0 +1,613 @@
+//! A JSON tree, for the few places that have to walk or rebuild a whole document rather than pick
+//! one member out of it with `my_json::j_path` — applying a JSON Patch or a JSON Merge Patch,
+//! canonicalizing a body.
+//!
+//! The document is read by `my-json`, the reader every other body read goes through, one
+//! container at a time (`JsonFirstLineIterator` for an object, `JsonArrayIterator` for an array),
+//! so a body that one read accepts the other does not reject. A number keeps its verbatim text —
+//! no digit is lost to an `f64` on the way through. Errors carry the JSON pointer of the container
+//! being read when the reader gave up, so a client can be told *where* its document is broken,
+//! not just that it is.
+//!
+//! Crate-private: the public face is the types built on top of it.
+
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use my_json::json_reader::{
+    JsonArrayIterator, JsonFirstLineIterator, JsonParseError, JsonValueRef,
+};
+
+/// Deeper than any document an API has a reason to send, and shallow enough that building the
+/// tree cannot be driven into a stack overflow by a body of `[[[[…`.
+pub(crate) const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, Clone)]
+pub(crate) enum JsonNode<'s> {
+    Null,
+    Bool(bool),
+    /// The number's verbatim text.
+    Number(&'s str),
+    String(Cow<'s, str>),
+    Array(Vec<JsonNode<'s>>),
+    /// Members in document order.
+    Object(Vec<(Cow<'s, str>, JsonNode<'s>)>),
+}
+
+/// A document that is not JSON. `pointer` is where the reader was when it gave up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct JsonSyntaxError {
+    pub pointer: String,
+    pub message: String,
+}
+
+impl JsonSyntaxError {
+    fn new(path: &[String], message: impl Into<String>) -> Self {
+        Self {
+            pointer: to_pointer(path),
+            message: message.into(),
+        }
+    }
+
+    fn from_parse_error(path: &[String], err: JsonParseError) -> Self {
+        Self::new(path, parse_error_message(&err))
+    }
+}
+
+/// The text of a `my-json` error, for a message a client reads.
+pub(crate) fn parse_error_message(err: &JsonParseError) -> String {
+    err.to_string()
+}
+
+pub(crate) fn parse(src: &[u8]) -> Result<JsonNode<'_>, JsonSyntaxError> {
+    let mut path = Vec::new();
+    let src = src.trim_ascii();
+
+    match src.first() {
+        Some(b'{' | b'[') => read_container(src, &mut path),
+        // A string has escapes to undo, and only `my-json` undoes them: hand it over as the one
+        // element of an array. The other scalars are their own text.
+        Some(b'"') => {
+            let mut wrapped = Vec::with_capacity(src.len() + 2);
+            wrapped.push(b'[');
+            wrapped.extend_from_slice(src);
+            wrapped.push(b']');
+
+            let items = match read_container(&wrapped, &mut path) {
+                Ok(JsonNode::Array(items)) => items,
+                _ => return Err(JsonSyntaxError::new(&[], "invalid string")),
+            };
+
+            match items.as_slice() {
+                [JsonNode::String(value)] => Ok(JsonNode::String(Cow::Owned(value.to_string()))),
+                _ => Err(JsonSyntaxError::new(&[], "invalid string")),
+            }
+        }
+        Some(_) => read_scalar(src, &path),
+        None => Err(JsonSyntaxError::new(&path, "the document is empty")),
+    }
+}
+
+/// An object or an array out of its verbatim source bytes.
+fn read_container<'s>(
+    src: &'s [u8],
+    path: &mut Vec<String>,
+) -> Result<JsonNode<'s>, JsonSyntaxError> {
+    if path.len() > MAX_DEPTH {
+        return Err(JsonSyntaxError::new(
+            path,
+            "the document is nested too deeply",
+        ));
+    }
+
+    if src.first() == Some(&b'{') {
+        let mut members = Vec::new();
+        let iterator: JsonFirstLineIterator<'s> = src.into();
+
+        while let Some(member) = iterator.get_next() {
+            let (key, value) =
+                member.map_err(|err| JsonSyntaxError::from_parse_error(path, err))?;
+            let key = key
+                .as_str()
+                .map_err(|err| JsonSyntaxError::from_parse_error(path, err))?
+                .into_string();
+
+            path.push(key);
+            let value = read_value(&value, path)?;
+            let key = path.pop().unwrap_or_default();
+            members.push((Cow::Owned(key), value));
+        }
+
+        return Ok(JsonNode::Object(members));
+    }
+
+    let mut items = Vec::new();
+    let iterator: JsonArrayIterator<'s> = src.into();
+
+    while let Some(item) = iterator.get_next() {
+        let item = item.map_err(|err| JsonSyntaxError::from_parse_error(path, err))?;
+
+        path.push(items.len().to_string());
+        let item = read_value(&item, path)?;
+        path.pop();
+        items.push(item);
+    }
+
+    Ok(JsonNode::Array(items))
+}
+
+fn read_value<'s>(
+    value: &JsonValueRef<'s>,
+    path: &mut Vec<String>,
+) -> Result<JsonNode<'s>, JsonSyntaxError> {
+    let raw = value.as_slice();
+
+    match raw.first() {
+        Some(b'{' | b'[') => read_container(raw, path),
+        Some(b'"') => match value.as_str() {
+            Some(text) => Ok(JsonNode::String(Cow::Owned(text.into_string()))),
+            None => Err(JsonSyntaxError::new(path, "invalid string")),
+        },
+        _ => read_scalar(raw, path),
+    }
+}
+
+/// `null`, `true`, `false` or a number, checked against the JSON grammar: the text of a number is
+/// kept as it is, so it must be a JSON number and nothing else.
+fn read_scalar<'s>(raw: &'s [u8], path: &[String]) -> Result<JsonNode<'s>, JsonSyntaxError> {
+    match raw {
+        b"null" => Ok(JsonNode::Null),
+        b"true" => Ok(JsonNode::Bool(true)),
+        b"false" => Ok(JsonNode::Bool(false)),
+        _ => match std::str::from_utf8(raw) {
+            Ok(number) if is_json_number(number) => Ok(JsonNode::Number(number)),
+            _ => Err(JsonSyntaxError::new(
+                path,
+                format!("invalid value '{}'", String::from_utf8_lossy(raw)),
+            )),
+        },
+    }
+}
+
+/// `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`
+fn is_json_number(text: &str) -> bool {
+    fn digits(text: &str) -> usize {
+        text.bytes().take_while(u8::is_ascii_digit).count()
+    }
+
+    let rest = text.strip_prefix('-').unwrap_or(text);
+
+    let rest = match digits(rest) {
+        0 => return false,
+        1 => &rest[1..],
+        _ if rest.starts_with('0') => return false,
+        len => &rest[len..],
+    };
+
+    let rest = match rest.strip_prefix('.') {
+        Some(fraction) => match digits(fraction) {
+            0 => return false,
+            len => &fraction[len..],
+        },
+        None => rest,
+    };
+
+    let rest = match rest.strip_prefix(['e', 'E']) {
+        Some(exponent) => {
+            let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            match digits(exponent) {
+                0 => return false,
+                len => &exponent[len..],
+            }
+        }
+        None => rest,
+    };
+
+    rest.is_empty()
+}
+
+/// The elements of a document that is one JSON array, each as its verbatim source slice, found
+/// one at a time as the iterator is advanced. A syntax error ends the iteration; its pointer is
+/// the index of the element it was found in.
+pub(crate) struct JsonArrayElements<'s> {
+    iterator: JsonArrayIterator<'s>,
+    index: usize,
+    done: bool,
+}
+
+pub(crate) fn array_elements(src: &[u8]) -> Result<JsonArrayElements<'_>, JsonSyntaxError> {
+    let src = src.trim_ascii();
+    if src.first() != Some(&b'[') {
+        return Err(JsonSyntaxError::new(&[], "expected a JSON array"));
+    }
+
+    Ok(JsonArrayElements {
+        iterator: src.into(),
+        index: 0,
+        done: false,
+    })
+}
+
+impl<'s> Iterator for JsonArrayElements<'s> {
+    type Item = Result<&'s [u8], JsonSyntaxError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = match self.iterator.get_next() {
+            Some(Ok(item)) => Ok(item.as_slice()),
+            Some(Err(err)) => Err(JsonSyntaxError::from_parse_error(
+                &[self.index.to_string()],
+                err,
+            )),
+            None => {
+                self.done = true;
+                return None;
+            }
+        };
+
+        self.index += 1;
+        if result.is_err() {
+            self.done = true;
+        }
+
+        Some(result)
+    }
+}
+
+/// The member names of a document that is one JSON object, in document order and with repeats
+/// kept — what checking an object's keys against a model needs. The values are only stepped
+/// over: whoever reads a member checks it.
+pub(crate) fn object_keys(src: &[u8]) -> Result<Vec<Cow<'_, str>>, JsonSyntaxError> {
+    let src = src.trim_ascii();
+    if src.first() != Some(&b'{') {
+        return Err(JsonSyntaxError::new(&[], "expected a JSON object"));
+    }
+
+    let mut keys = Vec::new();
+    let iterator: JsonFirstLineIterator = src.into();
+
+    while let Some(member) = iterator.get_next() {
+        let (key, _) = member.map_err(|err| JsonSyntaxError::from_parse_error(&[], err))?;
+        let key = key
+            .as_str()
+            .map_err(|err| JsonSyntaxError::from_parse_error(&[], err))?;
+        keys.push(Cow::Owned(key.into_string()));
+    }
+
+    Ok(keys)
+}
+
+impl<'s> JsonNode<'s> {
+    /// Compact JSON: no whitespace, members in their stored order.
+    pub(crate) fn write(&self, dest: &mut String) {
+        match self {
+            Self::Null => dest.push_str("null"),
+            Self::Bool(true) => dest.push_str("true"),
+            Self::Bool(false) => dest.push_str("false"),
+            Self::Number(number) => dest.push_str(number),
+            Self::String(value) => write_string(value, dest),
+            Self::Array(items) => {
+                dest.push('[');
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        dest.push(',');
+                    }
+                    item.write(dest);
+                }
+                dest.push(']');
+            }
+            Self::Object(members) => {
+                dest.push('{');
+                for (index, (key, value)) in members.iter().enumerate() {
+                    if index > 0 {
+                        dest.push(',');
+                    }
+                    write_string(key, dest);
+                    dest.push(':');
+                    value.write(dest);
+                }
+                dest.push('}');
+            }
+        }
+    }
+
+    pub(crate) fn to_json_string(&self) -> String {
+        let mut result = String::new();
+        self.write(&mut result);
+        result
+    }
+
+    /// The value a pointer's reference tokens lead to.
+    pub(crate) fn get(&self, tokens: &[String]) -> Option<&JsonNode<'s>> {
+        let mut current = self;
+        for token in tokens {
+            current = match current {
+                Self::Object(members) => &members[member_position(members, token)?].1,
+                Self::Array(items) => items.get(parse_array_index(token)?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    pub(crate) fn get_mut(&mut self, tokens: &[String]) -> Option<&mut JsonNode<'s>> {
+        let mut current = self;
+        for token in tokens {
+            current = match current {
+                Self::Object(members) => {
+                    let position = member_position(members, token)?;
+                    &mut members[position].1
+                }
+                Self::Array(items) => items.get_mut(parse_array_index(token)?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+}
+
+/// Where a member named `key` is. With a duplicated key the last one wins, as it does for most
+/// JSON readers.
+pub(crate) fn member_position(
+    members: &[(Cow<'_, str>, JsonNode<'_>)],
+    key: &str,
+) -> Option<usize> {
+    members.iter().rposition(|(name, _)| name == key)
+}
+
+/// Sets a member in place if it exists, appends it otherwise.
+pub(crate) fn set_member<'s>(
+    members: &mut Vec<(Cow<'s, str>, JsonNode<'s>)>,
+    key: Cow<'s, str>,
+    value: JsonNode<'s>,
+) {
+    match member_position(members, &key) {
+        Some(position) => members[position].1 = value,
+        None => members.push((key, value)),
+    }
+}
+
+/// An array index token: digits with no leading zero. `-` (past the end) is the caller's to
+/// handle, since it only means something to `add`.
+pub(crate) fn parse_array_index(token: &str) -> Option<usize> {
+    if token.is_empty()
+        || (token.len() > 1 && token.starts_with('0'))
+        || !token.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// Splits an RFC 6901 JSON pointer into its unescaped reference tokens. `""` is the whole
+/// document; anything else starts with `/`. `None` when it is not a pointer.
+pub(crate) fn parse_pointer(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+
+    pointer
+        .strip_prefix('/')?
+        .split('/')
+        .map(|token| {
+            let mut result = String::with_capacity(token.len());
+            let mut chars = token.chars();
+            while let Some(c) = chars.next() {
+                if c == '~' {
+                    match chars.next() {
+                        Some('0') => result.push('~'),
+                        Some('1') => result.push('/'),
+                        _ => return None,
+                    }
+                } else {
+                    result.push(c);
+                }
+            }
+            Some(result)
+        })
+        .collect()
+}
+
+pub(crate) fn to_pointer(tokens: &[String]) -> String {
+    let mut result = String::new();
+    for token in tokens {
+        result.push('/');
+        result.push_str(&token.replace('~', "~0").replace('/', "~1"));
+    }
+    result
+}
+
+/// JSON equality as RFC 6902's `test` defines it: numbers by value (`1` = `1.0`), objects
+/// regardless of member order, arrays element by element.
+pub(crate) fn json_eq(left: &JsonNode<'_>, right: &JsonNode<'_>) -> bool {
+    match (left, right) {
+        (JsonNode::Null, JsonNode::Null) => true,
+        (JsonNode::Bool(left), JsonNode::Bool(right)) => left == right,
+        (JsonNode::Number(left), JsonNode::Number(right)) => {
+            left == right
+                || matches!(
+                    (left.parse::<f64>(), right.parse::<f64>()),
+                    (Ok(left), Ok(right)) if left == right
+                )
+        }
+        (JsonNode::String(left), JsonNode::String(right)) => left == right,
+        (JsonNode::Array(left), JsonNode::Array(right)) => {
+            left.len() == right.len() && left.iter().zip(right).all(|(l, r)| json_eq(l, r))
+        }
+        (JsonNode::Object(left), JsonNode::Object(right)) => {
+            left.len() == right.len()
+                && left.iter().all(|(key, value)| {
+                    member_position(right, key)
+                        .is_some_and(|position| json_eq(value, &right[position].1))
+                })
+        }
+        _ => false,
+    }
+}
+
+/// A JSON string literal, escaping only what JSON requires (and the other C0 controls as
+/// lowercase `\u00xx`).
+pub(crate) fn write_string(src: &str, dest: &mut String) {
+    dest.push('"');
+    for c in src.chars() {
+        match c {
+            '"' => dest.push_str("\\\""),
+            '\\' => dest.push_str("\\\\"),
+            '\n' => dest.push_str("\\n"),
+            '\r' => dest.push_str("\\r"),
+            '\t' => dest.push_str("\\t"),
+            '\u{8}' => dest.push_str("\\b"),
+            '\u{c}' => dest.push_str("\\f"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(dest, "\\u{:04x}", c as u32);
+            }
+            c => dest.push(c),
+        }
+    }
+    dest.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{array_elements, json_eq, object_keys, parse, parse_pointer, to_pointer, JsonNode};
+
+    #[test]
+    fn round_trips_compact_json() {
+        let src = r#"{"a":[1,-2.5e3,true,false,null],"b":{"c":"x\"y\\z\n\u0001"},"d":"é😀"}"#;
+        let node = parse(src.as_bytes()).unwrap();
+        assert_eq!(node.to_json_string(), src);
+
+        let spaced = parse(b" { \"a\" : [ 1 , 2 ] } ").unwrap();
+        assert_eq!(spaced.to_json_string(), r#"{"a":[1,2]}"#);
+    }
+
+    #[test]
+    fn unescapes_surrogate_pairs() {
+        let node = parse(r#""\ud83d\ude00\u00e9""#.as_bytes()).unwrap();
+        assert_eq!(node.to_json_string(), "\"😀é\"");
+    }
+
+    #[test]
+    fn syntax_errors_carry_the_pointer() {
+        // The pointer leads to the broken value, or to the container `my-json` gave up in.
+        for (src, pointer) in [
+            (r#"{"a":[1,2,{"b":tru}]}"#, "/a/2/b"),
+            (r#"{"a/b":-}"#, "/a~1b"),
+            (r#"{"a":[01]}"#, "/a/0"),
+            (r#"{"a":1.}"#, "/a"),
+            (r#"nul"#, ""),
+            (r#""#, ""),
+        ] {
+            let err = parse(src.as_bytes()).unwrap_err();
+            assert!(pointer.starts_with(&err.pointer), "{}: {:?}", src, err);
+        }
+    }
+
+    #[test]
+    fn reads_top_level_scalars() {
+        assert_eq!(parse(b" null ").unwrap().to_json_string(), "null");
+        assert_eq!(parse(b"true").unwrap().to_json_string(), "true");
+        assert_eq!(parse(b"-0.5E+2").unwrap().to_json_string(), "-0.5E+2");
+        assert_eq!(parse(br#""a\"b""#).unwrap().to_json_string(), r#""a\"b""#);
+    }
+
+    #[test]
+    fn rejects_deep_nesting() {
+        let src = format!("{}{}", "[".repeat(1_000), "]".repeat(1_000));
+        assert!(parse(src.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn array_elements_are_found_one_by_one() {
+        let src = br#" [ {"a":"x\"]"} , [1,[2]] ,"s",-1.5e2,true , null ] "#;
+        let elements: Vec<&[u8]> = array_elements(src).unwrap().map(Result::unwrap).collect();
+        assert_eq!(
+            elements,
+            [
+                br#"{"a":"x\"]"}"#.as_slice(),
+                b"[1,[2]]",
+                br#""s""#,
+                b"-1.5e2",
+                b"true",
+                b"null"
+            ]
+        );
+
+        assert_eq!(array_elements(b"[]").unwrap().count(), 0);
+        assert!(array_elements(br#"{"a":1}"#).is_err());
+    }
+
+    #[test]
+    fn object_keys_keep_order_and_repeats() {
+        let src = br#" { "b" : {"x":1,"x":2}, "a" : [1], "b":null } "#;
+        let keys = object_keys(src).unwrap();
+        assert_eq!(keys, ["b", "a", "b"]);
+
+        assert!(object_keys(b"{}").unwrap().is_empty());
+        assert!(object_keys(b"[1]").is_err());
+    }
+
+    /// The tree and `my_json::j_path` — what every other body read uses — see the same values.
+    #[test]
+    fn agrees_with_the_path_reader() {
+        for src in [
+            r#"{"s":"plain","e":"x\"y\\z\n\tAé","u":"😀"}"#,
+            r#"{"i":0,"n":-12,"f":1.50,"x":6.02e+23,"big":123456789012345678901234567890}"#,
+            r#" { "t" : true , "f" : false , "z" : null , "o" : {"a":[1,{"b":"c"}]} } "#,
+            r#"{"a":[],"b":{},"c":[[],[{}]],"d":"]}\"{["}"#,
+        ] {
+            let node = parse(src.as_bytes()).unwrap();
+            let JsonNode::Object(members) = &node else {
+                panic!("{} is an object", src);
+            };
+
+            for (key, value) in members {
+                let expected = my_json::j_path::get_value(src.as_bytes(), key)
+                    .unwrap()
+                    .unwrap();
+
+                match value {
+                    JsonNode::String(value) => {
+                        assert_eq!(expected.as_str().unwrap().as_str(), value, "{}", src);
+                    }
+                    JsonNode::Array(_) | JsonNode::Object(_) => {
+                        let reread = parse(expected.as_slice()).unwrap();
+                        assert!(json_eq(value, &reread), "{}", src);
+                    }
+                    _ => {
+                        assert_eq!(
+                            std::str::from_utf8(expected.as_slice()).unwrap(),
+                            value.to_json_string(),
+                            "{}",
+                            src
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pointers_escape_and_unescape() {
+        let tokens = parse_pointer("/a~1b/~0c/0").unwrap();
+        assert_eq!(tokens, ["a/b", "~c", "0"]);
+        assert_eq!(to_pointer(&tokens), "/a~1b/~0c/0");
+
+        assert_eq!(parse_pointer(""), Some(vec![]));
+        assert_eq!(parse_pointer("a"), None);
+        assert_eq!(parse_pointer("/a~2"), None);
+    }
+
+    #[test]
+    fn equality_ignores_member_order_and_number_spelling() {
+        let left = parse(br#"{"a":1,"b":[1.0,"x"]}"#).unwrap();
+        let right = parse(br#"{"b":[1,"x"],"a":1e0}"#).unwrap();
+        assert!(json_eq(&left, &right));
+
+        let other = parse(br#"{"b":["x",1],"a":1}"#).unwrap();
+        assert!(!json_eq(&left, &other));
+    }
+}
@@ -0,0 +1,11 @@
+//! The members of an object structure, written into a `JsonObjectWriter` someone else owns.
+//!
+//! The derive-generated `JsonValueWriter` of an object structure is this plus `build_into`. The
+//! split is what `#[json_flatten]` needs: a flattened structure appends its members to its
+//! parent's writer instead of opening an object of its own.
+
+use my_json::json_writer::JsonObjectWriter;
+
+pub trait JsonObjectMembers {
+    fn write_json_members(&self, obj: JsonObjectWriter) -> JsonObjectWriter;
+}
@@ -1,9 +1,35 @@
-//! The one runtime helper the derive-generated `JsonValueReader` leans on.
+//! The runtime helpers the derive-generated `JsonValueReader` leans on.
 //!
 //! Kept here rather than inlined into the generated code so the absent-vs-null rule lives in one
 //! readable place instead of being copy-pasted into every model.
 
 use my_json::json_reader::{JsonParseError, JsonValueReader};
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::http_input::Patch;
+use crate::DateTimeFormat;
+
+/// An object read out of its own verbatim source bytes (`{…}`), borrowed for `'s`.
+///
+/// The derive-generated `JsonValueReader` of a `MyHttpInputObjectStructure` is a thin shim over
+/// this: it hands over `JsonValueRef::as_slice()`. Having the bytes-in half on its own lets a
+/// caller that already holds an object's slice — an element of a `#[http_body_array]` body — read
+/// it without going through a `JsonValueRef` first.
+///
+/// The two provided methods are what `#[json_flatten]` reads through: a structure flattened into
+/// its parent reads its members out of the parent's bytes, so it must skip its own `#[json_strict]`
+/// check (`from_json_members`) and instead lend its keys to the parent's (`json_member_names`).
+pub trait FromJsonObject<'s>: Sized {
+    fn from_json_object(raw: &'s [u8]) -> Result<Self, JsonParseError>;
+
+    /// Reads the members without checking the object's key set.
+    fn from_json_members(raw: &'s [u8]) -> Result<Self, JsonParseError> {
+        Self::from_json_object(raw)
+    }
+
+    /// Appends the keys this structure reads — those of its flattened members included.
+    fn json_member_names(_names: &mut Vec<&'static str>) {}
+}
 
 /// Reads one named member out of an object's verbatim source slice.
 ///
@@ -25,3 +51,81 @@ pub fn read_json_object_field<'s, T: JsonValueReader<'s>>(
         _ => T::from_absent_json_value(field_name),
     }
 }
+
+/// Reads one named member as a [`Patch`] — the one place absent and `null` are *not* alike.
+///
+/// A tri-state member (`Patch<T>` / `Option<Option<T>>`) exists precisely to tell "not sent"
+/// (leave unchanged) from "sent as `null`" (clear), so it cannot go through
+/// [`read_json_object_field`], which folds the two together.
+pub fn read_json_object_patch_field<'s, T: JsonValueReader<'s>>(
+    raw: &'s [u8],
+    field_name: &str,
+) -> Result<Patch<T>, JsonParseError> {
+    match my_json::j_path::get_value(raw, field_name)? {
+        None => Ok(Patch::Absent),
+        Some(value) if value.is_null() => Ok(Patch::Null),
+        Some(value) => Ok(Patch::Value(T::from_json_value(&value)?)),
+    }
+}
+
+/// Reads a date member in an explicit `format` — a `#[json_format = "…"]` member. A unix format
+/// takes a number or a string of digits, every other one a string.
+pub fn read_json_object_optional_date_time(
+    raw: &[u8],
+    field_name: &str,
+    format: DateTimeFormat,
+) -> Result<Option<DateTimeAsMicroseconds>, JsonParseError> {
+    let value = match my_json::j_path::get_value(raw, field_name)? {
+        Some(value) if !value.is_null() => value,
+        _ => return Ok(None),
+    };
+
+    let text = if value.is_string() {
+        value.as_str().map(|text| text.into_string())
+    } else if value.is_number() && format.is_number() {
+        std::str::from_utf8(value.as_slice()).ok().map(str::to_string)
+    } else {
+        None
+    };
+
+    match text.as_deref().and_then(|text| format.parse(text)) {
+        Some(result) => Ok(Some(result)),
+        None => Err(JsonParseError::new(format!(
+            "member '{}' is not a date in the '{}' format",
+            field_name, format
+        ))),
+    }
+}
+
+/// [`read_json_object_optional_date_time`] for a member that must be there.
+pub fn read_json_object_date_time(
+    raw: &[u8],
+    field_name: &str,
+    format: DateTimeFormat,
+) -> Result<DateTimeAsMicroseconds, JsonParseError> {
+    read_json_object_optional_date_time(raw, field_name, format)?
+        .ok_or_else(|| JsonParseError::new(format!("member '{}' is missing", field_name)))
+}
+
+/// The check a `#[json_strict]` object structure runs before reading its members: every member
+/// name has to be one of `known` (the keys the reader looks up), and none may be repeated — where
+/// the lenient reader would quietly take one of the copies and drop a misspelt key.
+///
+/// Only this object's own level is checked; a nested object is checked by its own reader, if it
+/// is strict as well.
+pub fn check_json_object_keys(raw: &[u8], known: &[&str]) -> Result<(), JsonParseError> {
+    let keys = crate::json_node::object_keys(raw)
+        .map_err(|err| JsonParseError::new(format!("{} at '{}'", err.message, err.pointer)))?;
+
+    for (index, key) in keys.iter().enumerate() {
+        if !known.contains(&key.as_ref()) {
+            return Err(JsonParseError::new(format!("unknown member '{}'", key)));
+        }
+
+        if keys[..index].contains(key) {
+            return Err(JsonParseError::new(format!("repeated member '{}'", key)));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+//! An object the derive-generated client writer has already built, appended as a member of its
+//! enclosing object — the envelope a `#[http_body(path = "…")]` field implies.
+//!
+//! `JsonObjectWriter::build` hands back the finished text, and `write_ref` wants a
+//! `JsonValueWriter`; this wrapper connects the two without parsing the text again.
+
+use my_json::json_writer::JsonValueWriter;
+
+pub struct JsonRawObject(pub String);
+
+impl JsonValueWriter for JsonRawObject {
+    const IS_ARRAY: bool = false;
+
+    fn write(&self, dest: &mut String) {
+        dest.push_str(self.0.as_str());
+    }
+}
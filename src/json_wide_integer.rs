@@ -0,0 +1,41 @@
+//! The 128-bit integer carrier the derive-generated object writer / reader go through.
+//!
+//! `my-json` has no `JsonValueWriter` / `JsonValueReader` for `i128` / `u128`, and neither trait
+//! nor type is this crate's to implement the other for. A nested `i128` field is therefore
+//! written and read through this wrapper, which keeps the number as a bare JSON number with every
+//! digit — the reader takes the member's verbatim text, never an `f64`.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use my_json::json_reader::{JsonParseError, JsonValueReader, JsonValueRef};
+use my_json::json_writer::JsonValueWriter;
+
+pub struct JsonWideInteger<T>(pub T);
+
+impl<T: Display> JsonValueWriter for JsonWideInteger<T> {
+    const IS_ARRAY: bool = false;
+
+    fn write(&self, dest: &mut String) {
+        dest.push_str(self.0.to_string().as_str());
+    }
+}
+
+impl<'s, T: FromStr> JsonValueReader<'s> for JsonWideInteger<T> {
+    fn from_json_value(value: &JsonValueRef<'s>) -> Result<Self, JsonParseError> {
+        // A number is its verbatim text; a string member (`"…"`) is accepted too, the way a
+        // 128-bit id is often quoted for the sake of JavaScript readers.
+        let text = match value.as_str() {
+            Some(text) => text.into_string(),
+            None => String::from_utf8_lossy(value.as_slice()).into_owned(),
+        };
+
+        match text.parse() {
+            Ok(result) => Ok(Self(result)),
+            Err(_) => Err(JsonParseError::new(format!(
+                "'{}' is not a 128-bit integer",
+                text
+            ))),
+        }
+    }
+}
@@ -20,14 +20,44 @@ pub use path_and_query_key_case_insensitive::*;
 mod path_and_query_parser;
 pub use path_and_query_parser::*;
 pub mod body;
+pub mod error_code;
 pub mod form_data_reader;
 pub mod schema;
 
 // The runtime half of the derive-generated `JsonValueReader`. At the crate root because the
-// generated code names it as `my_http_utils::read_json_object_field`, exactly as it names
-// `my_http_utils::my_json::…` for the writer.
+// generated code names it as `my_http_utils::read_json_object_field` (and `…_patch_field` for a
+// tri-state member), exactly as it names `my_http_utils::my_json::…` for the writer.
 mod json_object_reader;
-pub use json_object_reader::read_json_object_field;
+pub use json_object_reader::{
+    check_json_object_keys, read_json_object_date_time, read_json_object_field,
+    read_json_object_optional_date_time, read_json_object_patch_field, FromJsonObject,
+};
+
+// A borrowed JSON tree, for what walks a whole document (`JsonPatch` / `JsonMergePatch`).
+mod json_node;
+
+// Same reason: the generated object writer / reader name it for an `i128` / `u128` field.
+mod json_wide_integer;
+pub use json_wide_integer::JsonWideInteger;
+
+// And for the envelope object a `#[http_body(path = "…")]` field is written into.
+mod json_raw_object;
+pub use json_raw_object::JsonRawObject;
+
+// And for the members a `#[json_flatten]` field writes into its parent.
+mod json_object_members;
+pub use json_object_members::JsonObjectMembers;
+
+// The `format = "…"` of a date field, named by the generated writers, readers and schema.
+mod date_time_format;
+pub use date_time_format::{DateTimeFormat, JsonFormattedDateTime};
+
+// RFC 8785 canonical JSON, for a body that is signed or hashed.
+mod canonical_json;
+pub use canonical_json::{to_canonical_json, write_canonical_json, CanonicalJsonError};
+
+// HMAC / SHA-2, shared by what signs a request and what verifies one.
+mod crypto;
 
 /// Re-exported so the derive-generated client body builder can reach `JsonObjectWriter` via a
 /// fully-qualified `my_http_utils::my_json::…` path (consumers don't depend on `my-json` directly).
@@ -48,7 +78,8 @@ pub use serde;
 pub mod http_input;
 
 /// Derive & attribute macros for HTTP request models (`MyHttpInput`, `MyHttpObjectStructure`,
-/// `MyHttpInputObjectStructure`, `MyHttpStringEnum`, `MyHttpIntegerEnum`, `http_input_field`).
+/// `MyHttpInputObjectStructure`, `MyHttpStringEnum`, `MyHttpIntegerEnum`, `MyHttpFlagsEnum`,
+/// `http_input_field`).
 ///
 /// This is the single supported entry point: consumers use them via `my_http_utils::macros::…`
 /// rather than depending on the proc-macro crate directly. The generated code targets
@@ -1,2 +1,6 @@
 mod request_builder;
 pub use request_builder::*;
+mod aws_sigv4;
+pub use aws_sigv4::*;
+mod http_signature;
+pub use http_signature::*;
@@ -7,23 +7,91 @@
 //! `UrlBuilder` it already shares with `my-http-utils`. Nothing here knows about any transport,
 //! so it all compiles under wasm.
 
+use my_json::json_reader::JsonParseError;
+use my_json::json_writer::{JsonObjectWriter, JsonValueWriter};
+
 use crate::body::HttpRequestBody;
+use crate::error_code::*;
 use crate::UrlBuilder;
 
 /// Error produced while building a request — e.g. a field failed its `validator`.
-#[derive(Debug, Clone)]
+///
+/// [`Self::code`] is the stable code of the failure and `to_json` / [`Self::from_json`] its JSON
+/// form (see [`crate::error_code`]). An error is built by the constructor of its kind
+/// ([`Self::new`], [`Self::canonical_body`], [`Self::signature`]); [`Self::get_kind`] tells them
+/// apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HttpRequestBuildError {
     pub field: String,
     pub reason: String,
+    kind: HttpRequestBuildErrorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpRequestBuildErrorKind {
+    /// A field `validator` rejected the outgoing value.
+    Validation,
+    /// [`THttpRequestBuilder::get_canonical_json_body`] found no canonical form of the body.
+    CanonicalBody,
+    /// The request could not be signed, e.g. a covered header is not in it.
+    Signature,
 }
 
 impl HttpRequestBuildError {
+    /// A field `validator` rejected the outgoing value.
     pub fn new(field: impl Into<String>, reason: impl Into<String>) -> Self {
         Self {
             field: field.into(),
             reason: reason.into(),
+            kind: HttpRequestBuildErrorKind::Validation,
+        }
+    }
+
+    pub fn canonical_body(reason: impl Into<String>) -> Self {
+        Self {
+            field: "body".to_string(),
+            reason: reason.into(),
+            kind: HttpRequestBuildErrorKind::CanonicalBody,
         }
     }
+
+    pub fn signature(reason: impl Into<String>) -> Self {
+        Self {
+            field: "signature".to_string(),
+            reason: reason.into(),
+            kind: HttpRequestBuildErrorKind::Signature,
+        }
+    }
+
+    pub fn get_kind(&self) -> HttpRequestBuildErrorKind {
+        self.kind
+    }
+
+    /// The stable code of the failure — one of the [`crate::error_code`] constants.
+    pub fn code(&self) -> &'static str {
+        match self.kind {
+            HttpRequestBuildErrorKind::Validation => E_VALIDATION,
+            HttpRequestBuildErrorKind::CanonicalBody => E_CANONICAL_BODY,
+            HttpRequestBuildErrorKind::Signature => E_SIGNATURE,
+        }
+    }
+
+    /// Reads the JSON `to_json` wrote back into the same error.
+    pub fn from_json(raw: &[u8]) -> Result<Self, JsonParseError> {
+        let code = read_member(raw, "code")?;
+        let kind = match code.as_str() {
+            E_VALIDATION => HttpRequestBuildErrorKind::Validation,
+            E_CANONICAL_BODY => HttpRequestBuildErrorKind::CanonicalBody,
+            E_SIGNATURE => HttpRequestBuildErrorKind::Signature,
+            other => return Err(unknown_code("HttpRequestBuildError", other)),
+        };
+
+        Ok(Self {
+            field: read_member(raw, "field")?,
+            reason: read_member(raw, "reason")?,
+            kind,
+        })
+    }
 }
 
 impl std::fmt::Display for HttpRequestBuildError {
@@ -32,6 +100,21 @@ impl std::fmt::Display for HttpRequestBuildError {
     }
 }
 
+impl JsonValueWriter for HttpRequestBuildError {
+    const IS_ARRAY: bool = false;
+
+    fn write(&self, dest: &mut String) {
+        JsonObjectWriter::new()
+            .write_ref("code", &self.code())
+            .write_ref("message", &self.to_string())
+            .write_ref("field", &self.field)
+            .write_ref("reason", &self.reason)
+            .build_into(dest);
+    }
+}
+
+impl_error_json!(HttpRequestBuildError);
+
 impl std::error::Error for HttpRequestBuildError {}
 
 /// Sink for request headers. A transport (e.g. fl-url's header collection) implements this,
@@ -59,8 +142,9 @@ pub trait RandomStringGenerator {
 ///
 /// Each method applies the field directives (`trim`, `to_lowercase`/`to_uppercase`,
 /// `validator`) to the outgoing value and returns [`HttpRequestBuildError`] if a `validator`
-/// rejects it. All methods are required — `#[derive(MyHttpInput)]` always generates every one
+/// rejects it. All three are required — `#[derive(MyHttpInput)]` always generates every one
 /// (empty where the model has no such fields), so a model can never silently miss a part.
+/// [`get_canonical_json_body`](Self::get_canonical_json_body) is provided on top of them.
 pub trait THttpRequestBuilder {
     fn fill_url(&self, url_builder: &mut UrlBuilder) -> Result<(), HttpRequestBuildError>;
 
@@ -73,4 +157,33 @@ pub trait THttpRequestBuilder {
     fn get_body<TRnd: RandomStringGenerator>(self) -> Result<HttpRequestBody, HttpRequestBuildError>
     where
         Self: Sized;
+
+    /// The RFC 8785 canonical bytes of the model's JSON body — what a client signs and sends, and
+    /// a server, canonicalizing the body it received with [`crate::to_canonical_json`], hashes to
+    /// the same digest. Fails for a model whose body is not JSON, and for one with an integer an
+    /// IEEE 754 double does not hold exactly (see [`crate::to_canonical_json`]).
+    ///
+    /// The body is written as [`get_body`](Self::get_body) writes it, then parsed again and
+    /// rewritten in canonical form.
+    fn get_canonical_json_body(self) -> Result<Vec<u8>, HttpRequestBuildError>
+    where
+        Self: Sized,
+    {
+        match self.get_body::<NoBoundary>()? {
+            HttpRequestBody::Json(data) => crate::to_canonical_json(&data)
+                .map_err(|err| HttpRequestBuildError::canonical_body(err.to_string())),
+            _ => Err(HttpRequestBuildError::canonical_body(
+                "the body is not JSON, so it has no canonical form",
+            )),
+        }
+    }
+}
+
+/// Only a form-data body asks for a boundary, and a form-data body is never canonicalized.
+struct NoBoundary;
+
+impl RandomStringGenerator for NoBoundary {
+    fn generate_random_string(_len: usize) -> String {
+        String::new()
+    }
 }
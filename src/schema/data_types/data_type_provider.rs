@@ -3,8 +3,15 @@ use std::collections::{BTreeMap, HashMap};
 use rust_extensions::date_time::DateTimeAsMicroseconds;
 
 use super::{ArrayElement, HttpDataType, HttpObjectStructure, HttpSimpleType};
+use crate::http_input::core::StrictJson;
+use crate::schema::in_parameters::HttpSecurityScheme;
 
 pub trait DataTypeProvider {
+    /// The members a `#[http_input(strict_body)]` check lets through in a value of this type —
+    /// an object structure's own, built at compile time. Anything else is only checked for
+    /// repeated keys.
+    const STRICT_JSON: &'static StrictJson = &StrictJson::Any;
+
     fn get_data_type() -> HttpDataType;
 
     fn get_http_data_structure() -> HttpObjectStructure {
@@ -14,17 +21,29 @@ pub trait DataTypeProvider {
     fn get_generic_type() -> Option<String> {
         None
     }
+
+    /// The media type of a `#[http_body_raw]` body of this type, when it has a specific one
+    /// (`application/json-patch+json` for a `JsonPatch`). `None` leaves it to the renderer.
+    fn get_body_content_type() -> Option<&'static str> {
+        None
+    }
+
+    /// The security scheme a header of this type is a credential of (`Basic` for a
+    /// `BasicAuthCredentials`). `None` leaves it a plain header parameter.
+    fn get_security_scheme() -> Option<HttpSecurityScheme> {
+        None
+    }
 }
 
 impl DataTypeProvider for u8 {
     fn get_data_type() -> HttpDataType {
-        HttpDataType::SimpleType(HttpSimpleType::Byte)
+        HttpDataType::SimpleType(HttpSimpleType::UInt8)
     }
 }
 
 impl DataTypeProvider for i8 {
     fn get_data_type() -> HttpDataType {
-        HttpDataType::SimpleType(HttpSimpleType::Byte)
+        HttpDataType::SimpleType(HttpSimpleType::Int8)
     }
 }
 
@@ -76,6 +95,18 @@ impl DataTypeProvider for isize {
     }
 }
 
+impl DataTypeProvider for i128 {
+    fn get_data_type() -> HttpDataType {
+        HttpDataType::SimpleType(HttpSimpleType::Int128)
+    }
+}
+
+impl DataTypeProvider for u128 {
+    fn get_data_type() -> HttpDataType {
+        HttpDataType::SimpleType(HttpSimpleType::UInt128)
+    }
+}
+
 impl DataTypeProvider for f32 {
     fn get_data_type() -> HttpDataType {
         HttpDataType::SimpleType(HttpSimpleType::Double)
@@ -113,6 +144,8 @@ impl DataTypeProvider for DateTimeAsMicroseconds {
 }
 
 impl<T: DataTypeProvider> DataTypeProvider for Vec<T> {
+    const STRICT_JSON: &'static StrictJson = &StrictJson::ArrayOf(T::STRICT_JSON);
+
     fn get_data_type() -> HttpDataType {
         let data_type = T::get_data_type();
         match data_type {
@@ -133,6 +166,8 @@ impl<T: DataTypeProvider> DataTypeProvider for Vec<T> {
 }
 
 impl<TValue: DataTypeProvider> DataTypeProvider for HashMap<String, TValue> {
+    const STRICT_JSON: &'static StrictJson = &StrictJson::DictionaryOf(TValue::STRICT_JSON);
+
     fn get_data_type() -> HttpDataType {
         match TValue::get_data_type() {
             HttpDataType::SimpleType(tp) => {
@@ -146,6 +181,8 @@ impl<TValue: DataTypeProvider> DataTypeProvider for HashMap<String, TValue> {
 }
 
 impl<TValue: DataTypeProvider> DataTypeProvider for BTreeMap<String, TValue> {
+    const STRICT_JSON: &'static StrictJson = &StrictJson::DictionaryOf(TValue::STRICT_JSON);
+
     fn get_data_type() -> HttpDataType {
         match TValue::get_data_type() {
             HttpDataType::SimpleType(tp) => {
@@ -1,7 +1,9 @@
 use super::HttpDataType;
 #[derive(Clone, Debug)]
 pub struct HttpEnumCase {
-    pub id: i16,
+    // i64: an integer enum's ids are often protocol codes, and a flag set's bits go well past
+    // the 16 an `i16` could hold.
+    pub id: i64,
     pub value: &'static str,
     pub description: &'static str,
 }
@@ -9,6 +11,9 @@ pub struct HttpEnumCase {
 pub enum EnumType {
     Integer,
     String,
+    /// A bit-flag set (`MyHttpFlagsEnum`): the value is an integer that ORs any number of the
+    /// cases together, and each case's `id` is the single bit it stands for.
+    Flags,
 }
 #[derive(Clone, Debug)]
 pub struct HttpEnumStructure {
@@ -7,6 +7,9 @@ pub struct HttpField {
     pub name: String,
     pub data_type: HttpDataType,
     pub required: bool,
+    /// `null` is a meaningful value: a tri-state (`Patch<T>` / `Option<Option<T>>`) member, which
+    /// may also be omitted.
+    pub nullable: bool,
 }
 
 impl HttpField {
@@ -15,6 +18,16 @@ impl HttpField {
             name: name.to_string(),
             data_type,
             required,
+            nullable: false,
+        }
+    }
+
+    pub fn new_nullable(name: &str, data_type: HttpDataType) -> Self {
+        Self {
+            name: name.to_string(),
+            data_type,
+            required: false,
+            nullable: true,
         }
     }
 
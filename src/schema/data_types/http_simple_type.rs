@@ -5,12 +5,28 @@ pub enum HttpSimpleType {
     Float,
     Double,
     String,
+    // OpenAPI's `byte`: a base64-encoded string (`Base64Bytes`), not a one-byte integer.
     Byte,
+    // `Base64UrlBytes`: the RFC 4648 §5 alphabet, no padding.
+    Base64Url,
     Binary,
     Boolean,
     Date,
     DateTime,
     Password,
+    // `i8` / `u8`.
+    Int8,
+    UInt8,
+    // 128-bit integers: written as a bare JSON number, so a reader that keeps the verbatim text
+    // (as this crate's does) gets every digit.
+    Int128,
+    UInt128,
+    // String-carried types with a well-known `format`.
+    Decimal,
+    Uuid,
+    Uri,
+    Email,
+    Duration,
 }
 
 impl HttpSimpleType {
@@ -22,11 +38,21 @@ impl HttpSimpleType {
             HttpSimpleType::Double => "double",
             HttpSimpleType::String => "string",
             HttpSimpleType::Byte => "byte",
+            HttpSimpleType::Base64Url => "base64url",
             HttpSimpleType::Binary => "binary",
             HttpSimpleType::Boolean => "boolean",
             HttpSimpleType::Date => "date",
             HttpSimpleType::DateTime => "date-time",
             HttpSimpleType::Password => "password",
+            HttpSimpleType::Int8 => "int8",
+            HttpSimpleType::UInt8 => "uint8",
+            HttpSimpleType::Int128 => "int128",
+            HttpSimpleType::UInt128 => "uint128",
+            HttpSimpleType::Decimal => "decimal",
+            HttpSimpleType::Uuid => "uuid",
+            HttpSimpleType::Uri => "uri",
+            HttpSimpleType::Email => "email",
+            HttpSimpleType::Duration => "duration",
         }
     }
 
@@ -37,12 +63,22 @@ impl HttpSimpleType {
             HttpSimpleType::Float => "number",
             HttpSimpleType::Double => "number",
             HttpSimpleType::String => "string",
-            HttpSimpleType::Byte => "integer",
+            HttpSimpleType::Byte => "string",
+            HttpSimpleType::Base64Url => "string",
             HttpSimpleType::Binary => "string",
             HttpSimpleType::Boolean => "boolean",
             HttpSimpleType::Date => "string",
             HttpSimpleType::DateTime => "string",
             HttpSimpleType::Password => "string",
+            HttpSimpleType::Int8 => "integer",
+            HttpSimpleType::UInt8 => "integer",
+            HttpSimpleType::Int128 => "integer",
+            HttpSimpleType::UInt128 => "integer",
+            HttpSimpleType::Decimal => "string",
+            HttpSimpleType::Uuid => "string",
+            HttpSimpleType::Uri => "string",
+            HttpSimpleType::Email => "string",
+            HttpSimpleType::Duration => "string",
         }
     }
 
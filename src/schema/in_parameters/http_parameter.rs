@@ -1,6 +1,7 @@
+use crate::http_input::ParseProfile;
 use crate::schema::data_types::{ArrayElement, HttpField, HttpSimpleType};
 
-use super::HttpParameterInputSource;
+use super::{HttpParameterInputSource, HttpSecurityScheme};
 
 pub enum NonBodyParameter {
     SimpleType(HttpSimpleType),
@@ -12,9 +13,44 @@ pub struct HttpInputParameter {
     pub field: HttpField,
     pub description: String,
     pub source: HttpParameterInputSource,
+    /// The body's media type, for a `BodyRaw` parameter whose type declares one
+    /// (`DataTypeProvider::get_body_content_type`). Always `None` for every other source.
+    pub content_type: Option<&'static str>,
+    /// How a scalar value of the parameter is read: the field's `parse_profile`, else the model's
+    /// `#[http_input(parse_profile)]`, else `Standard`.
+    pub parse_profile: ParseProfile,
+    security_scheme: Option<HttpSecurityScheme>,
 }
 
 impl HttpInputParameter {
+    /// A parameter with no body media type, the `Standard` profile and no security scheme.
+    pub fn new(
+        field: HttpField,
+        description: impl Into<String>,
+        source: HttpParameterInputSource,
+    ) -> Self {
+        Self {
+            field,
+            description: description.into(),
+            source,
+            content_type: None,
+            parse_profile: ParseProfile::Standard,
+            security_scheme: None,
+        }
+    }
+
+    /// The security scheme a header's type declares (`DataTypeProvider::get_security_scheme`):
+    /// such a parameter is a credential, described under `securitySchemes`.
+    pub fn with_security_scheme(mut self, security_scheme: Option<HttpSecurityScheme>) -> Self {
+        self.security_scheme = security_scheme;
+        self
+    }
+
+    /// Always `None` for a parameter that is not a header.
+    pub fn get_security_scheme(&self) -> Option<HttpSecurityScheme> {
+        self.security_scheme
+    }
+
     pub fn is_body_reader(&self) -> bool {
         matches!(self.source, HttpParameterInputSource::BodyModel)
     }
@@ -23,6 +59,10 @@ impl HttpInputParameter {
         matches!(self.source, HttpParameterInputSource::FormData)
     }
 
+    pub fn is_security(&self) -> bool {
+        self.security_scheme.is_some()
+    }
+
     pub fn is_file_to_upload_from_body(&self) -> bool {
         if self.field.is_file_upload() {
             if let HttpParameterInputSource::BodyRaw = self.source {
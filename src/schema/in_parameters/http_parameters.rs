@@ -7,6 +7,7 @@ pub struct HttpParameters {
     non_body_params: Option<Vec<HttpInputParameter>>,
     body_params: Option<Vec<HttpInputParameter>>,
     form_data_params: Option<Vec<HttpInputParameter>>,
+    security_params: Option<Vec<HttpInputParameter>>,
 }
 
 impl HttpParameters {
@@ -16,6 +17,7 @@ impl HttpParameters {
                 non_body_params: None,
                 body_params: None,
                 form_data_params: None,
+                security_params: None,
             };
         }
 
@@ -24,9 +26,13 @@ impl HttpParameters {
         let mut non_body_params = LazyVec::new();
         let mut body_params = LazyVec::new();
         let mut form_data_params = LazyVec::new();
+        let mut security_params = LazyVec::new();
 
         for param in params {
-            if param.source.is_body() {
+            // A credential is described by its security scheme, not listed with the headers.
+            if param.is_security() {
+                security_params.add(param);
+            } else if param.source.is_body() {
                 body_params.add(param);
             } else if param.source.is_form_data() {
                 form_data_params.add(param);
@@ -41,6 +47,7 @@ impl HttpParameters {
             body_params: body_params.get_result(),
             non_body_params: non_body_params.get_result(),
             form_data_params: form_data_params.get_result(),
+            security_params: security_params.get_result(),
         }
     }
 
@@ -56,6 +63,11 @@ impl HttpParameters {
         self.form_data_params.as_ref()
     }
 
+    /// The credentials of the request, each with its `security_scheme`.
+    pub fn get_security_params(&self) -> Option<&Vec<HttpInputParameter>> {
+        self.security_params.as_ref()
+    }
+
     pub fn is_single_body_parameter(&self) -> Option<&HttpInputParameter> {
         let params = self.body_params.as_ref()?;
 
@@ -0,0 +1,112 @@
+use my_json::json_writer::{JsonObjectWriter, JsonValueWriter};
+
+use super::{HttpApiKeyLocation, HttpSecurityScheme};
+
+/// One way to authenticate a request: every scheme in it is needed. A model or a route lists the
+/// ways it accepts, and a request has to satisfy one of them — `[api key, bearer]` is "an API key
+/// or a bearer token", one requirement of both is "an API key and a bearer token".
+///
+/// A model declares its list with `#[http_security(…)]` (the generated `get_security_requirements`);
+/// a route may declare its own, which replaces the model's ([`Self::for_operation`]).
+///
+/// As a `JsonValueWriter` it is an entry of an operation's `security` array
+/// (`{"bearerAuth":[]}`); [`HttpSecuritySchemes`] is the matching `components/securitySchemes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpSecurityRequirement {
+    pub schemes: Vec<HttpSecurityScheme>,
+}
+
+impl HttpSecurityRequirement {
+    pub fn new(schemes: Vec<HttpSecurityScheme>) -> Self {
+        Self { schemes }
+    }
+
+    pub fn basic() -> Self {
+        Self::new(vec![HttpSecurityScheme::Basic])
+    }
+
+    pub fn bearer() -> Self {
+        Self::new(vec![HttpSecurityScheme::Bearer])
+    }
+
+    pub fn api_key_header(name: &'static str) -> Self {
+        Self::new(vec![HttpSecurityScheme::ApiKey {
+            location: HttpApiKeyLocation::Header,
+            name,
+        }])
+    }
+
+    pub fn api_key_query(name: &'static str) -> Self {
+        Self::new(vec![HttpSecurityScheme::ApiKey {
+            location: HttpApiKeyLocation::Query,
+            name,
+        }])
+    }
+
+    /// The requirements an operation documents and enforces: the route's own when it declares
+    /// any, the model's otherwise.
+    pub fn for_operation<'s>(route: &'s [Self], model: &'s [Self]) -> &'s [Self] {
+        if route.is_empty() {
+            model
+        } else {
+            route
+        }
+    }
+
+    /// What a client has to send to meet this requirement.
+    pub fn describe(&self) -> String {
+        self.schemes
+            .iter()
+            .map(|scheme| scheme.describe())
+            .collect::<Vec<_>>()
+            .join(" and ")
+    }
+}
+
+impl JsonValueWriter for HttpSecurityRequirement {
+    const IS_ARRAY: bool = false;
+
+    fn write(&self, dest: &mut String) {
+        // A scheme of these types has no scopes, so each one is named with an empty list.
+        let no_scopes: Vec<String> = Vec::new();
+        let mut obj = JsonObjectWriter::new();
+        for scheme in &self.schemes {
+            obj = obj.write_ref(scheme.get_name().as_str(), &no_scopes);
+        }
+        obj.build_into(dest);
+    }
+}
+
+/// The `components/securitySchemes` object of a set of requirements: every scheme they name, once.
+#[derive(Debug, Clone, Default)]
+pub struct HttpSecuritySchemes(Vec<HttpSecurityScheme>);
+
+impl HttpSecuritySchemes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, requirements: &[HttpSecurityRequirement]) {
+        for scheme in requirements.iter().flat_map(|r| r.schemes.iter()) {
+            if !self.0.contains(scheme) {
+                self.0.push(*scheme);
+            }
+        }
+    }
+
+    pub fn get_schemes(&self) -> &[HttpSecurityScheme] {
+        self.0.as_slice()
+    }
+}
+
+impl JsonValueWriter for HttpSecuritySchemes {
+    const IS_ARRAY: bool = false;
+
+    fn write(&self, dest: &mut String) {
+        let mut obj = JsonObjectWriter::new();
+        for scheme in &self.0 {
+            obj = obj.write_ref(scheme.get_name().as_str(), scheme);
+        }
+        obj.build_into(dest);
+    }
+}
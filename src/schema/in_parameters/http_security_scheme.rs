@@ -0,0 +1,95 @@
+use my_json::json_writer::{JsonObjectWriter, JsonValueWriter};
+
+/// How a request authenticates — an OpenAPI `components/securitySchemes` entry. A field whose type
+/// declares one (`DataTypeProvider::get_security_scheme`) is described by it, not as a parameter;
+/// a model or a route requires one through an [`super::HttpSecurityRequirement`].
+///
+/// As a `JsonValueWriter` it is the scheme object itself (`{"type":"http","scheme":"bearer"}`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpSecurityScheme {
+    /// `Authorization: Basic …` (`BasicAuthCredentials`).
+    Basic,
+    /// `Authorization: Bearer …` (`BearerToken`).
+    Bearer,
+    /// A key in the named header or query parameter.
+    ApiKey {
+        location: HttpApiKeyLocation,
+        name: &'static str,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpApiKeyLocation {
+    Header,
+    Query,
+}
+
+impl HttpApiKeyLocation {
+    /// The OpenAPI `in` of the key.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Header => "header",
+            Self::Query => "query",
+        }
+    }
+}
+
+impl HttpSecurityScheme {
+    /// The key of the scheme under `components/securitySchemes`, which an operation's `security`
+    /// refers to. An API key is named after where it is sent, so two keys never share an entry.
+    pub fn get_name(&self) -> String {
+        match self {
+            Self::Basic => "basicAuth".to_string(),
+            Self::Bearer => "bearerAuth".to_string(),
+            Self::ApiKey { location, name } => format!("apiKey_{}_{}", location.as_str(), name),
+        }
+    }
+
+    /// The OpenAPI `type` of the scheme.
+    pub fn get_type(&self) -> &'static str {
+        match self {
+            Self::Basic | Self::Bearer => "http",
+            Self::ApiKey { .. } => "apiKey",
+        }
+    }
+
+    /// The OpenAPI `scheme` of an `http` scheme.
+    pub fn get_scheme(&self) -> Option<&'static str> {
+        match self {
+            Self::Basic => Some("basic"),
+            Self::Bearer => Some("bearer"),
+            Self::ApiKey { .. } => None,
+        }
+    }
+
+    /// What a client has to send, as a missing-credentials error puts it.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Basic => "basic credentials".to_string(),
+            Self::Bearer => "a bearer token".to_string(),
+            Self::ApiKey {
+                location: HttpApiKeyLocation::Header,
+                name,
+            } => format!("an API key in header '{}'", name),
+            Self::ApiKey {
+                location: HttpApiKeyLocation::Query,
+                name,
+            } => format!("an API key in query parameter '{}'", name),
+        }
+    }
+}
+
+impl JsonValueWriter for HttpSecurityScheme {
+    const IS_ARRAY: bool = false;
+
+    fn write(&self, dest: &mut String) {
+        let obj = JsonObjectWriter::new().write_ref("type", &self.get_type());
+        let obj = match self {
+            Self::Basic | Self::Bearer => obj.write_if_some_ref("scheme", &self.get_scheme()),
+            Self::ApiKey { location, name } => obj
+                .write_ref("in", &location.as_str())
+                .write_ref("name", name),
+        };
+        obj.build_into(dest);
+    }
+}
@@ -1,6 +1,10 @@
 mod http_parameter;
 mod http_parameter_src;
 mod http_parameters;
+mod http_security_requirement;
+mod http_security_scheme;
 pub use http_parameter::*;
 pub use http_parameter_src::*;
 pub use http_parameters::*;
+pub use http_security_requirement::*;
+pub use http_security_scheme::*;
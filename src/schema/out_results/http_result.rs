@@ -1,4 +1,6 @@
+use crate::http_input::HttpParseErrorStatusPolicy;
 use crate::schema::data_types::{HttpDataType, HttpObjectStructure};
+use crate::schema::in_parameters::HttpInputParameter;
 
 use super::IntoHttpResult;
 
@@ -10,6 +12,31 @@ pub struct HttpResult {
     pub data_type: HttpDataType,
 }
 
+impl HttpResult {
+    /// The failed-parse responses of a route, as `policy` documents them: one per
+    /// [`HttpParseErrorStatusPolicy::documented_statuses`], answered with the error text. A route
+    /// without input parameters has nothing to fail on, so it gets none.
+    pub fn parse_error_results(
+        policy: &impl HttpParseErrorStatusPolicy,
+        input_params: &[HttpInputParameter],
+    ) -> Vec<HttpResult> {
+        if input_params.is_empty() {
+            return Vec::new();
+        }
+
+        policy
+            .documented_statuses()
+            .into_iter()
+            .map(|(http_code, description)| HttpResult {
+                http_code,
+                nullable: false,
+                description: description.to_string(),
+                data_type: HttpDataType::as_string(),
+            })
+            .collect()
+    }
+}
+
 impl IntoHttpResult for HttpObjectStructure {
     fn into_http_result_object(
         self,
@@ -1,5 +1,10 @@
 use std::{str::Utf8Error, string::FromUtf8Error};
 
+use my_json::json_reader::JsonParseError;
+use my_json::json_writer::{JsonObjectWriter, JsonValueWriter};
+
+use crate::error_code::*;
+
 #[derive(Debug)]
 pub struct UrlDecodeError {
     pub msg: String,
@@ -20,3 +25,61 @@ impl From<FromUtf8Error> for UrlDecodeError {
         }
     }
 }
+
+impl UrlDecodeError {
+    /// Always [`crate::error_code::E_URL_DECODE`].
+    pub fn code(&self) -> &'static str {
+        E_URL_DECODE
+    }
+
+    /// Reads the JSON `to_json` wrote back into the same error.
+    pub fn from_json(raw: &[u8]) -> Result<Self, JsonParseError> {
+        let code = read_member(raw, "code")?;
+        if code != E_URL_DECODE {
+            return Err(unknown_code("UrlDecodeError", &code));
+        }
+
+        Ok(Self {
+            msg: read_member(raw, "detail")?,
+        })
+    }
+}
+
+impl std::fmt::Display for UrlDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.msg.as_str())
+    }
+}
+
+impl JsonValueWriter for UrlDecodeError {
+    const IS_ARRAY: bool = false;
+
+    fn write(&self, dest: &mut String) {
+        JsonObjectWriter::new()
+            .write_ref("code", &self.code())
+            .write_ref("message", &self.to_string())
+            .write_ref("detail", &self.msg)
+            .build_into(dest);
+    }
+}
+
+impl_error_json!(UrlDecodeError);
+
+#[cfg(test)]
+mod tests {
+    use super::UrlDecodeError;
+
+    #[test]
+    fn round_trips_through_json() {
+        let err = UrlDecodeError {
+            msg: "bad escape \"%zz\"".to_string(),
+        };
+
+        let json = err.to_json();
+        let read = UrlDecodeError::from_json(json.as_bytes()).unwrap();
+        assert_eq!(read.msg, err.msg);
+        assert_eq!(read.to_json(), json);
+
+        assert!(UrlDecodeError::from_json(br#"{"code":"E_VALIDATION","detail":"x"}"#).is_err());
+    }
+}
@@ -1,8 +1,14 @@
+use my_json::json_reader::JsonParseError;
+use my_json::json_writer::{JsonObjectWriter, JsonValueWriter};
+
+use crate::error_code::*;
 use crate::url_decoder::UrlDecodeError;
 
 #[derive(Debug)]
 pub enum ReadingEncodedDataError {
+    /// The name of the parameter.
     RequiredParameterIsMissing(String),
+    /// The decoded value that did not parse.
     CanNotParseValue(String),
     UrlDecodeError(UrlDecodeError),
 }
@@ -12,3 +18,82 @@ impl From<UrlDecodeError> for ReadingEncodedDataError {
         Self::UrlDecodeError(src)
     }
 }
+
+impl ReadingEncodedDataError {
+    /// The stable code of the failure — one of the [`crate::error_code`] constants.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::RequiredParameterIsMissing(_) => E_REQUIRED_MISSING,
+            Self::CanNotParseValue(_) => E_CANNOT_PARSE,
+            Self::UrlDecodeError(_) => E_URL_DECODE,
+        }
+    }
+
+    /// Reads the JSON `to_json` wrote back into the same error.
+    pub fn from_json(raw: &[u8]) -> Result<Self, JsonParseError> {
+        let code = read_member(raw, "code")?;
+        let result = match code.as_str() {
+            E_REQUIRED_MISSING => Self::RequiredParameterIsMissing(read_member(raw, "name")?),
+            E_CANNOT_PARSE => Self::CanNotParseValue(read_member(raw, "value")?),
+            E_URL_DECODE => Self::UrlDecodeError(UrlDecodeError {
+                msg: read_member(raw, "detail")?,
+            }),
+            other => return Err(unknown_code("ReadingEncodedDataError", other)),
+        };
+        Ok(result)
+    }
+}
+
+impl std::fmt::Display for ReadingEncodedDataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RequiredParameterIsMissing(name) => {
+                write!(f, "Required parameter '{}' is missing", name)
+            }
+            Self::CanNotParseValue(value) => write!(f, "Can not parse value '{}'", value),
+            Self::UrlDecodeError(err) => write!(f, "Url decode error: {}", err),
+        }
+    }
+}
+
+impl JsonValueWriter for ReadingEncodedDataError {
+    const IS_ARRAY: bool = false;
+
+    fn write(&self, dest: &mut String) {
+        let obj = JsonObjectWriter::new()
+            .write_ref("code", &self.code())
+            .write_ref("message", &self.to_string());
+
+        let obj = match self {
+            Self::RequiredParameterIsMissing(name) => obj.write_ref("name", name),
+            Self::CanNotParseValue(value) => obj.write_ref("value", value),
+            Self::UrlDecodeError(err) => obj.write_ref("detail", &err.msg),
+        };
+
+        obj.build_into(dest);
+    }
+}
+
+impl_error_json!(ReadingEncodedDataError);
+
+#[cfg(test)]
+mod tests {
+    use super::ReadingEncodedDataError;
+    use crate::url_decoder::UrlDecodeError;
+
+    #[test]
+    fn round_trips_through_json() {
+        for err in [
+            ReadingEncodedDataError::RequiredParameterIsMissing("n".to_string()),
+            ReadingEncodedDataError::CanNotParseValue("abc".to_string()),
+            ReadingEncodedDataError::UrlDecodeError(UrlDecodeError {
+                msg: "bad escape".to_string(),
+            }),
+        ] {
+            let json = err.to_json();
+            let read = ReadingEncodedDataError::from_json(json.as_bytes()).unwrap();
+            assert_eq!(read.code(), err.code());
+            assert_eq!(read.to_json(), json);
+        }
+    }
+}
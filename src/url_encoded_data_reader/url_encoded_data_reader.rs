@@ -50,6 +50,11 @@ impl<'s> UrlEncodedDataReader<'s> {
         result
     }
 
+    /// The names in the body, in order and with repeats kept.
+    pub fn get_names(&self) -> impl Iterator<Item = &str> {
+        self.query_string.iter().map(|item| item.get_name())
+    }
+
     pub fn get_raw(&self) -> &str {
         self.src
     }
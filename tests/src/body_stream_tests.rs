@@ -629,4 +629,179 @@ fn _checks() {
     _assert_send_sync::<HttpBodyReader>();
     _assert_send_sync::<my_http_utils::http_input::HttpBodyStreamSender>();
     _assert_send_sync::<UploadHttpInput>();
+    _assert_send_sync::<my_http_utils::body::StreamContentDigest>();
+}
+
+// ---- 13. Content-Digest of a streamed body ----------------------------------
+//
+// The client hashes the body as the transport drains it; the server has the reader check the
+// digest the request came with, so the read that would end a tampered body fails instead.
+
+async fn send_all(sender: my_http_utils::http_input::HttpBodyStreamSender, chunks: Vec<Vec<u8>>) {
+    for chunk in chunks {
+        assert!(sender.send_chunk(chunk).await);
+    }
+    sender.finish();
+}
+
+#[tokio::test]
+async fn a_streamed_body_arrives_with_the_digest_it_was_sent_with() {
+    use my_http_utils::body::DigestAlgorithm;
+    use my_http_utils::http_input::core::read_content_digest;
+
+    // client
+    let (sender, stream) = HttpBodyAsStream::create(4, None);
+    let (stream, digest) = stream.with_content_digest(DigestAlgorithm::Sha256);
+    let model = UploadHttpInput {
+        file_name: "report.bin".to_string(),
+        overwrite: false,
+        body: stream,
+    };
+    tokio::spawn(send_all(sender, vec![b"aaa".to_vec(), b"bbb".to_vec()]));
+
+    let outgoing = take_stream(model.get_body::<Rnd>().unwrap());
+    let sent = outgoing
+        .get_body_reader()
+        .unwrap()
+        .read_to_end(None)
+        .await
+        .unwrap();
+    let content_digest = digest.get().unwrap().to_header_value();
+    assert_eq!(
+        content_digest,
+        "sha-256=:LOEJ6dD6+CCyQ04WYpeTTmF3tlq5lR28PiBMrUaJs5w=:"
+    );
+
+    // server
+    let (sender, stream) = HttpBodyAsStream::create(4, None);
+    let request = FakeStreamRequest::default()
+        .header("X-File-Name", "report.bin")
+        .header("Content-Digest", &content_digest)
+        .stream(stream);
+    let model = UploadHttpInput::parse(&request).unwrap();
+    tokio::spawn(send_all(sender, vec![sent]));
+
+    let expected = read_content_digest(&request).unwrap();
+    let reader = model
+        .body
+        .verify_content_digest(expected)
+        .get_body_reader()
+        .unwrap();
+    assert_eq!(reader.read_to_end(None).await.unwrap(), b"aaabbb".to_vec());
+}
+
+#[tokio::test]
+async fn a_streamed_body_that_does_not_match_its_digest_fails_at_the_end() {
+    use my_http_utils::http_input::core::read_content_digest;
+
+    let (sender, stream) = HttpBodyAsStream::create(4, None);
+    let request = FakeStreamRequest::default()
+        .header("X-File-Name", "report.bin")
+        .header(
+            "Content-Digest",
+            "sha-256=:LOEJ6dD6+CCyQ04WYpeTTmF3tlq5lR28PiBMrUaJs5w=:",
+        )
+        .stream(stream);
+    let model = UploadHttpInput::parse(&request).unwrap();
+    tokio::spawn(send_all(sender, vec![b"aaa".to_vec(), b"bbc".to_vec()]));
+
+    let mut reader = model
+        .body
+        .verify_content_digest(read_content_digest(&request).unwrap())
+        .get_body_reader()
+        .unwrap();
+
+    // Every chunk is handed out as it comes; only the end of the body is refused.
+    assert_eq!(
+        poll_once(&mut reader).await.unwrap().unwrap(),
+        b"aaa".to_vec()
+    );
+    assert_eq!(
+        poll_once(&mut reader).await.unwrap().unwrap(),
+        b"bbc".to_vec()
+    );
+    let err = poll_once(&mut reader).await.unwrap().unwrap_err();
+    assert_eq!(err, HttpParseError::body_digest_mismatch());
+    assert_eq!(err.code(), "E_BODY_DIGEST_MISMATCH");
+
+    // The error is the end of the body, not something every later poll runs into again.
+    assert!(poll_once(&mut reader).await.is_none());
+}
+
+// ---- 14. one range of a body, cut out of the stream of the whole ------------
+
+// The server side of a `206 Partial Content`: the stream of the whole representation goes in, the
+// range comes out, and the pump is told to stop as soon as the range is filled.
+
+#[tokio::test]
+async fn a_ranged_stream_hands_out_only_the_range() {
+    use my_http_utils::http_input::ByteRange;
+
+    let (sender, stream) = HttpBodyAsStream::create(4, Some(16));
+    let stream = stream.with_range(ByteRange::new(5, 10).unwrap());
+    assert_eq!(stream.get_content_length(), Some(6));
+
+    tokio::spawn(send_all(
+        sender,
+        vec![
+            b"0123".to_vec(),
+            b"4567".to_vec(),
+            b"89ab".to_vec(),
+            b"cdef".to_vec(),
+        ],
+    ));
+
+    let reader = stream.get_body_reader().unwrap();
+    assert_eq!(reader.get_content_length(), Some(6));
+    assert_eq!(
+        reader.get_next_chunk().await.unwrap(),
+        Some(b"567".to_vec())
+    );
+    assert_eq!(
+        reader.get_next_chunk().await.unwrap(),
+        Some(b"89a".to_vec())
+    );
+    assert_eq!(reader.get_next_chunk().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn a_filled_range_stops_the_pump() {
+    use my_http_utils::http_input::ByteRange;
+
+    let (sender, stream) = HttpBodyAsStream::create(1, None);
+    let stream = stream.with_range(ByteRange::new(2, 3).unwrap());
+
+    let pump = tokio::spawn(async move {
+        let mut sent = 0;
+        while sender.send_chunk(vec![b'x']).await {
+            sent += 1;
+            if sent == 1000 {
+                break;
+            }
+        }
+        sent
+    });
+
+    let reader = stream.get_body_reader().unwrap();
+    assert_eq!(reader.read_to_end(None).await.unwrap(), b"xx".to_vec());
+    assert!(pump.await.unwrap() < 1000);
+}
+
+#[tokio::test]
+async fn a_source_shorter_than_the_range_is_a_truncated_body() {
+    use my_http_utils::http_input::ByteRange;
+
+    let (sender, stream) = HttpBodyAsStream::create(4, None);
+    let mut reader = stream
+        .with_range(ByteRange::new(2, 9).unwrap())
+        .get_body_reader()
+        .unwrap();
+    tokio::spawn(send_all(sender, vec![b"0123".to_vec()]));
+
+    assert_eq!(
+        poll_once(&mut reader).await.unwrap().unwrap(),
+        b"23".to_vec()
+    );
+    let err = poll_once(&mut reader).await.unwrap().unwrap_err();
+    assert_eq!(err, HttpParseError::body_stream_truncated());
 }
@@ -532,4 +532,63 @@ mod tests {
         };
         assert_eq!(build_url("http://h", &m), "http://h?q=x");
     }
+
+    // ---- AWS SigV4: signing what a model builds ----
+
+    #[derive(MyHttpInput)]
+    struct PutObjectModel {
+        #[http_path(name = "bucket", description = "")]
+        bucket: String,
+        #[http_path(name = "key", description = "")]
+        key: String,
+        #[http_header(name = "x-amz-storage-class", description = "")]
+        storage_class: String,
+        #[http_body_raw(description = "")]
+        body: Vec<u8>,
+    }
+
+    #[test]
+    fn aws_sigv4_signs_a_model_request() {
+        use my_http_utils::schema::client::{AwsSigV4Request, AwsSigV4Signer};
+        use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+        let model = PutObjectModel {
+            bucket: "photos".to_string(),
+            key: "cat one.jpg".to_string(),
+            storage_class: "STANDARD".to_string(),
+            body: b"meow".to_vec(),
+        };
+
+        let mut url = UrlBuilder::new("http://localhost:9000");
+        model.fill_url(&mut url).unwrap();
+        let mut request = AwsSigV4Request::new("PUT", &url);
+        model.fill_headers(&mut request).unwrap();
+        let body = model.get_body::<FixedRnd>().unwrap();
+        request.set_body(&body);
+
+        let signer = AwsSigV4Signer::new("minioadmin", "minioadmin", "us-east-1", "s3");
+        // 2024-02-29T13:45:07Z
+        let signed = signer.sign(&request, DateTimeAsMicroseconds::new(1_709_214_307_000_000));
+
+        assert_eq!(
+            request.get_headers(),
+            [("x-amz-storage-class".to_string(), "STANDARD".to_string())]
+        );
+        assert_eq!(signed.amz_date, "20240229T134507Z");
+        assert_eq!(
+            signed.content_sha256,
+            "404cdd7bc109c432f8cc2443b45bcfe95980f5107215c645236e577929ac3e52"
+        );
+        assert_eq!(
+            signed.authorization,
+            "AWS4-HMAC-SHA256 Credential=minioadmin/20240229/us-east-1/s3/aws4_request, \
+             SignedHeaders=host;x-amz-content-sha256;x-amz-date;x-amz-storage-class, \
+             Signature=6dd80ead940ab4792c15152b3b5fe833c639f85540ba0acc7012bebb4de1a532"
+        );
+
+        let mut headers = Collector::default();
+        signed.fill_headers(&mut headers);
+        assert_eq!(headers.0[0].0, "Authorization");
+        assert_eq!(headers.0.len(), 3);
+    }
 }
@@ -14,6 +14,9 @@ struct FakeRequest {
     path: Vec<(String, String)>,
     body: Vec<u8>,
     content_type: Option<String>,
+    method: Option<String>,
+    path_and_query: Option<String>,
+    peer_ip: Option<std::net::IpAddr>,
 }
 
 impl FakeRequest {
@@ -34,6 +37,15 @@ impl FakeRequest {
         self.body = body.into();
         self
     }
+    fn target(mut self, method: &str, path_and_query: &str) -> Self {
+        self.method = Some(method.to_string());
+        self.path_and_query = Some(path_and_query.to_string());
+        self
+    }
+    fn peer(mut self, ip: &str) -> Self {
+        self.peer_ip = Some(ip.parse().unwrap());
+        self
+    }
 }
 
 impl THttpRequest for FakeRequest {
@@ -58,6 +70,15 @@ impl THttpRequest for FakeRequest {
     fn get_content_type(&self) -> Option<&str> {
         self.content_type.as_deref()
     }
+    fn get_method(&self) -> Option<&str> {
+        self.method.as_deref()
+    }
+    fn get_path_and_query(&self) -> Option<&str> {
+        self.path_and_query.as_deref()
+    }
+    fn get_peer_ip(&self) -> Option<std::net::IpAddr> {
+        self.peer_ip
+    }
 }
 
 fn only_digits(v: &str) -> Result<(), String> {
@@ -1237,6 +1258,220 @@ fn nested_enum_with_an_escaped_case_value_round_trips() {
     }
 }
 
+// ---- forward-compatible enums: `other`, `alias`, `case_insensitive` ---------------------------
+//
+// A partner adding a status string must not make the whole response unreadable. The `other` case
+// keeps the unknown text verbatim, so it also writes back out unchanged.
+
+#[derive(Debug, Clone, PartialEq, MyHttpStringEnum)]
+enum PartnerStatus {
+    #[http_enum_case(id = "0", value = "active", description = "Active", case_insensitive)]
+    Active,
+    #[http_enum_case(id = "1", value = "cancelled", alias = "canceled, CXL", description = "Cancelled")]
+    Cancelled,
+    #[http_enum_case(other, description = "A status this build does not know yet")]
+    Unknown(String),
+}
+
+#[derive(Debug, MyHttpInputObjectStructure)]
+struct WithPartnerStatus {
+    status: PartnerStatus,
+}
+
+#[derive(MyHttpInput)]
+struct PartnerStatusBody {
+    #[http_body(name = "n", description = "Nested")]
+    n: WithPartnerStatus,
+}
+
+#[derive(MyHttpInput)]
+struct PartnerStatusQuery {
+    #[http_query(name = "status", description = "")]
+    status: PartnerStatus,
+}
+
+#[test]
+fn other_case_keeps_an_unknown_value_verbatim() {
+    let parsed: PartnerStatus = serde_json::from_str(r#""suspended""#).unwrap();
+    assert_eq!(parsed, PartnerStatus::Unknown("suspended".to_string()));
+
+    // `as_str` hands back the original text, so serde writes what it read.
+    assert_eq!(parsed.as_str(), "suspended");
+    assert_eq!(serde_json::to_string(&parsed).unwrap(), r#""suspended""#);
+}
+
+#[test]
+fn other_case_round_trips_through_the_real_parse() {
+    let sent = PartnerStatusBody {
+        n: WithPartnerStatus {
+            status: PartnerStatus::Unknown("on-hold".to_string()),
+        },
+    };
+
+    let parsed = PartnerStatusBody::parse(&round_trip(sent)).unwrap();
+    assert_eq!(parsed.n.status, PartnerStatus::Unknown("on-hold".to_string()));
+}
+
+#[test]
+fn other_case_catches_an_unknown_query_value() {
+    // `TryFrom<HttpInputValue>`: the same lookup as serde, so no CanNotParseValue either.
+    let request = FakeRequest::default().query("status=Suspended");
+    let parsed = PartnerStatusQuery::parse(&request).unwrap();
+    assert_eq!(parsed.status, PartnerStatus::Unknown("Suspended".to_string()));
+}
+
+#[test]
+fn aliases_and_case_insensitive_matching() {
+    for (wire, expected) in [
+        ("active", PartnerStatus::Active),
+        ("ACTIVE", PartnerStatus::Active),
+        ("0", PartnerStatus::Active),
+        ("cancelled", PartnerStatus::Cancelled),
+        ("canceled", PartnerStatus::Cancelled),
+        ("CXL", PartnerStatus::Cancelled),
+        ("1", PartnerStatus::Cancelled),
+        // `Cancelled` is not `case_insensitive`: a different casing is not it.
+        ("Canceled", PartnerStatus::Unknown("Canceled".to_string())),
+    ] {
+        let json = format!(r#""{}""#, wire);
+        let from_serde: PartnerStatus = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_serde, expected, "serde, {}", wire);
+
+        let request = FakeRequest::default().query(&format!("status={}", wire));
+        let from_query = PartnerStatusQuery::parse(&request).unwrap().status;
+        assert_eq!(from_query, expected, "query, {}", wire);
+    }
+
+    // An alias is accepted on the way in, but only the value is ever written.
+    assert_eq!(PartnerStatus::Cancelled.as_str(), "cancelled");
+}
+
+#[test]
+fn alias_on_an_enum_without_other_still_rejects_unknown_values() {
+    #[derive(Debug, PartialEq, MyHttpStringEnum)]
+    enum Strict {
+        #[http_enum_case(id = "0", value = "on", alias = "enabled", description = "On", case_insensitive)]
+        On,
+    }
+
+    assert_eq!(serde_json::from_str::<Strict>(r#""Enabled""#).unwrap(), Strict::On);
+
+    let err = serde_json::from_str::<Strict>(r#""off""#).unwrap_err();
+    assert!(err.to_string().contains("off"), "unhelpful error: {}", err);
+}
+
+// ---- bit-flag sets: `MyHttpFlagsEnum` + `HttpFlags<T>` ----------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, MyHttpFlagsEnum)]
+enum Permission {
+    #[http_enum_case(id = "1", value = "read", description = "Read")]
+    Read,
+    #[http_enum_case(id = "2", value = "write", description = "Write")]
+    Write,
+    // Past what the old `i16` id could describe.
+    #[http_enum_case(id = "1099511627776", value = "admin", description = "Admin")]
+    Admin,
+}
+
+#[derive(MyHttpInput)]
+struct FlagsModel {
+    #[http_query(name = "q", description = "")]
+    q: my_http_utils::http_input::HttpFlags<Permission>,
+    #[http_header(name = "X-Perm", description = "")]
+    h: my_http_utils::http_input::HttpFlags<Permission>,
+    #[http_body(name = "b", description = "")]
+    b: my_http_utils::http_input::HttpFlags<Permission>,
+}
+
+#[test]
+fn flags_parse_from_an_integer_or_a_name_list_on_every_source() {
+    let request = FakeRequest::default()
+        .query("q=read%2Cwrite")
+        .header("X-Perm", "3")
+        .body("application/json", r#"{"b":["write","read"]}"#);
+
+    let model = FlagsModel::parse(&request).unwrap();
+    let expected = Permission::Read | Permission::Write;
+
+    assert_eq!(model.q, expected);
+    assert_eq!(model.h, expected);
+    assert_eq!(model.b, expected);
+
+    for body in [r#"{"b":3}"#, r#"{"b":"3"}"#, r#"{"b":"read, write"}"#] {
+        let request = FakeRequest::default()
+            .query("q=1")
+            .header("X-Perm", "read")
+            .body("application/json", body);
+
+        assert_eq!(FlagsModel::parse(&request).unwrap().b, expected, "{}", body);
+    }
+}
+
+#[test]
+fn flags_reject_an_unknown_name_or_bit() {
+    for query in ["q=read,delete", "q=4"] {
+        let request = FakeRequest::default()
+            .query(query)
+            .header("X-Perm", "1")
+            .body("application/json", r#"{"b":1}"#);
+
+        let err = FlagsModel::parse(&request).err().unwrap();
+        assert!(
+            matches!(err, HttpParseError::CanNotParseValue { ref name, .. } if name == "q"),
+            "{}: {:?}",
+            query,
+            err
+        );
+    }
+}
+
+#[test]
+fn flags_client_writes_the_canonical_integer_and_round_trips() {
+    use my_http_utils::schema::client::THttpRequestBuilder;
+
+    let sent = FlagsModel {
+        q: Permission::Admin | Permission::Read,
+        h: Permission::Write.into(),
+        b: my_http_utils::http_input::HttpFlags::empty(),
+    };
+
+    // However the set was built, the wire form is the one integer.
+    let mut url = my_http_utils::UrlBuilder::new("http://h");
+    sent.fill_url(&mut url).unwrap();
+    assert_eq!(url.to_string(), "http://h?q=1099511627777");
+    let header = sent.h.as_str().to_string();
+    assert_eq!(header, "2");
+
+    let request = round_trip(sent)
+        .query("q=1099511627777")
+        .header("X-Perm", &header);
+    assert_eq!(request.body, br#"{"b":0}"#.to_vec());
+
+    let parsed = FlagsModel::parse(&request).unwrap();
+    assert!(parsed.q.contains(Permission::Admin) && parsed.q.contains(Permission::Read));
+    assert_eq!(parsed.q.get_names(), vec!["read", "admin"]);
+    assert_eq!(parsed.h, my_http_utils::http_input::HttpFlags::from(Permission::Write));
+    assert!(parsed.b.is_empty());
+}
+
+#[test]
+fn flags_schema_documents_each_bit() {
+    use my_http_utils::schema::data_types::{DataTypeProvider, EnumType, HttpDataType};
+
+    match my_http_utils::http_input::HttpFlags::<Permission>::get_data_type() {
+        HttpDataType::Enum(structure) => {
+            assert!(matches!(structure.enum_type, EnumType::Flags));
+            let bits: Vec<(i64, &str)> =
+                structure.cases.iter().map(|c| (c.id, c.value)).collect();
+            assert_eq!(
+                bits,
+                vec![(1, "read"), (2, "write"), (1099511627776, "admin")]
+            );
+        }
+        other => panic!("expected an enum, got {:?}", other),
+    }
+}
+
 // ---- #[json_name] and the serde-free path ------------------------------------------------------
 
 /// `#[json_name]` is this crate's own way to name a field on the wire. A `#[http_body]` object is
@@ -1363,3 +1598,2537 @@ fn shape_neutral_serde_params_are_still_accepted() {
         r#"{"cardNumber":1,"withDefault":2,"lastName":"x","EXPLICIT":3}"#
     );
 }
+
+// ---- 128-bit integers and string-carried simple types ------------------------------------------
+
+use my_http_utils::http_input::{DecimalString, EmailAddress, IsoDuration, Uri, Uuid};
+
+#[derive(Debug, MyHttpInputObjectStructure)]
+struct Ledger {
+    id: u128,
+    balance: i128,
+    previous: Option<i128>,
+    amount: DecimalString,
+}
+
+#[derive(MyHttpInput)]
+struct SimpleTypesModel {
+    #[http_query(name = "id", description = "")]
+    id: Uuid,
+    #[http_query(name = "big", description = "")]
+    big: u128,
+    #[http_query(name = "ttl", description = "")]
+    ttl: Option<IsoDuration>,
+    #[http_header(name = "X-Callback", description = "")]
+    callback: Uri,
+    #[http_body(name = "email", description = "")]
+    email: EmailAddress,
+    #[http_body(name = "ledger", description = "")]
+    ledger: Ledger,
+}
+
+#[test]
+fn simple_types_parse_from_every_source() {
+    let request = FakeRequest::default()
+        .query("id=67E55044-10B1-426F-9247-BB680E5FE0C8&big=340282366920938463463374607431768211455&ttl=PT1H30M")
+        .header("X-Callback", "https://example.com/hook?a=1")
+        .body(
+            "application/json",
+            r#"{"email":"john@example.com","ledger":{"id":"340282366920938463463374607431768211455","balance":-170141183460469231731687303715884105728,"amount":100.00}}"#,
+        );
+
+    let model = SimpleTypesModel::parse(&request).unwrap();
+
+    assert_eq!(model.id.as_str(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    assert_eq!(model.big, u128::MAX);
+    assert_eq!(
+        model.ttl.unwrap().to_std_duration(),
+        Some(std::time::Duration::from_secs(5_400))
+    );
+    assert_eq!(model.callback.get_scheme(), "https");
+    assert_eq!(model.email.get_domain(), "example.com");
+    assert_eq!(model.ledger.id, u128::MAX);
+    assert_eq!(model.ledger.balance, i128::MIN);
+    assert_eq!(model.ledger.previous, None);
+    // Read from a bare number, the decimal keeps its scale.
+    assert_eq!(model.ledger.amount.as_str(), "100.00");
+}
+
+#[test]
+fn simple_types_reject_a_malformed_value_by_name() {
+    let request = FakeRequest::default()
+        .query("id=not-a-uuid&big=1")
+        .header("X-Callback", "https://example.com")
+        .body(
+            "application/json",
+            r#"{"email":"john@example.com","ledger":{"id":1,"balance":1,"amount":"1"}}"#,
+        );
+
+    let err = SimpleTypesModel::parse(&request).err().unwrap();
+    assert!(
+        matches!(err, HttpParseError::CanNotParseValue { ref name, .. } if name == "id"),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn wide_integers_keep_every_digit_in_a_nested_object() {
+    use my_http_utils::my_json::json_writer::JsonValueWriter;
+
+    let ledger = Ledger {
+        id: u128::MAX,
+        balance: i128::MIN,
+        previous: Some(-1),
+        amount: "-12.50".parse().unwrap(),
+    };
+
+    let mut written = String::new();
+    ledger.write(&mut written);
+
+    assert_eq!(
+        written,
+        r#"{"id":340282366920938463463374607431768211455,"balance":-170141183460469231731687303715884105728,"previous":-1,"amount":"-12.50"}"#
+    );
+}
+
+#[test]
+fn simple_types_schema_formats() {
+    use my_http_utils::schema::data_types::{DataTypeProvider, HttpDataType, HttpSimpleType};
+
+    for (data_type, format) in [
+        (i128::get_data_type(), "int128"),
+        (u128::get_data_type(), "uint128"),
+        (DecimalString::get_data_type(), "decimal"),
+        (Uuid::get_data_type(), "uuid"),
+        (Uri::get_data_type(), "uri"),
+        (EmailAddress::get_data_type(), "email"),
+        (IsoDuration::get_data_type(), "duration"),
+    ] {
+        match data_type {
+            HttpDataType::SimpleType(simple_type) => {
+                assert_eq!(simple_type.as_format(), format)
+            }
+            other => panic!("expected a simple type, got {:?}", other),
+        }
+    }
+
+    assert_eq!(HttpSimpleType::Int128.as_swagger_type(), "integer");
+    assert_eq!(HttpSimpleType::Uuid.as_swagger_type(), "string");
+}
+
+// ---- binary data as base64 -----------------------------------------------------------------------
+
+use my_http_utils::http_input::{Base64Bytes, Base64UrlBytes};
+
+#[derive(MyHttpInput)]
+struct Base64Model {
+    #[http_query(name = "sig", description = "")]
+    sig: Base64Bytes,
+    #[http_header(name = "X-Token", description = "")]
+    token: Base64UrlBytes,
+    #[http_body(name = "payload", description = "")]
+    payload: Base64Bytes,
+    #[http_body(name = "extra", description = "")]
+    extra: Option<Base64UrlBytes>,
+}
+
+#[test]
+fn base64_client_encodes_and_server_decodes_on_every_source() {
+    use my_http_utils::schema::client::THttpRequestBuilder;
+
+    let bytes = vec![0xfb, 0xff, 0xbf, 0x00];
+    let sent = Base64Model {
+        sig: Base64Bytes::new(bytes.clone()),
+        token: Base64UrlBytes::new(bytes.clone()),
+        payload: Base64Bytes::new(b"hello".as_slice()),
+        extra: None,
+    };
+
+    // `+`, `/` and `=` survive the query string percent-encoded.
+    let mut url = my_http_utils::UrlBuilder::new("http://h");
+    sent.fill_url(&mut url).unwrap();
+    assert_eq!(url.to_string(), "http://h?sig=%2B%2F%2B%2FAA%3D%3D");
+    let header = sent.token.as_str().to_string();
+    assert_eq!(header, "-_-_AA");
+
+    let request = round_trip(sent)
+        .query("sig=%2B%2F%2B%2FAA%3D%3D")
+        .header("X-Token", &header);
+    assert_eq!(request.body, br#"{"payload":"aGVsbG8="}"#.to_vec());
+
+    let parsed = Base64Model::parse(&request).unwrap();
+    assert_eq!(parsed.sig.as_bytes(), bytes.as_slice());
+    assert_eq!(parsed.token.as_bytes(), bytes.as_slice());
+    assert_eq!(parsed.payload.as_bytes(), b"hello");
+    assert!(parsed.extra.is_none());
+}
+
+#[test]
+fn base64_rejects_the_other_alphabet_by_name() {
+    let request = FakeRequest::default()
+        .query("sig=AA%3D%3D")
+        .header("X-Token", "AA")
+        .body("application/json", r#"{"payload":"aGVsbG8=","extra":"+/+/"}"#);
+
+    let err = Base64Model::parse(&request).err().unwrap();
+    assert!(
+        matches!(err, HttpParseError::CanNotParseValue { ref name, .. } if name == "extra"),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn byte_format_means_base64_and_single_byte_integers_are_integers() {
+    use my_http_utils::schema::data_types::{DataTypeProvider, HttpDataType};
+
+    for (data_type, swagger_type, format) in [
+        (Base64Bytes::get_data_type(), "string", "byte"),
+        (Base64UrlBytes::get_data_type(), "string", "base64url"),
+        (u8::get_data_type(), "integer", "uint8"),
+        (i8::get_data_type(), "integer", "int8"),
+    ] {
+        match data_type {
+            HttpDataType::SimpleType(simple_type) => {
+                assert_eq!(simple_type.as_swagger_type(), swagger_type);
+                assert_eq!(simple_type.as_format(), format);
+            }
+            other => panic!("expected a simple type, got {:?}", other),
+        }
+    }
+}
+
+// ---- tri-state members for PATCH: `Patch<T>` / `Option<Option<T>>` ------------------------------
+
+use my_http_utils::http_input::Patch;
+
+#[derive(Debug, MyHttpInputObjectStructure)]
+struct ProfilePatch {
+    bio: Patch<String>,
+    age: Option<Option<u32>>,
+}
+
+#[derive(MyHttpInput)]
+struct UpdateUser {
+    #[http_body(name = "name", description = "")]
+    name: Patch<String>,
+    #[http_body(name = "nick", description = "")]
+    nick: Option<Option<String>>,
+    #[http_body(name = "profile", description = "")]
+    profile: Option<ProfilePatch>,
+}
+
+#[test]
+fn patch_tells_absent_from_null_from_a_value() {
+    let request = FakeRequest::default().body(
+        "application/json",
+        r#"{"name":null,"nick":"jd","profile":{"bio":"hi","age":null}}"#,
+    );
+
+    let model = UpdateUser::parse(&request).unwrap();
+    assert_eq!(model.name, Patch::Null);
+    assert_eq!(model.nick, Some(Some("jd".to_string())));
+
+    let profile = model.profile.unwrap();
+    assert_eq!(profile.bio, Patch::Value("hi".to_string()));
+    assert_eq!(profile.age, Some(None));
+
+    let request = FakeRequest::default().body("application/json", r#"{"profile":{}}"#);
+    let model = UpdateUser::parse(&request).unwrap();
+    assert_eq!(model.name, Patch::Absent);
+    assert_eq!(model.nick, None);
+
+    let profile = model.profile.unwrap();
+    assert_eq!(profile.bio, Patch::Absent);
+    assert_eq!(profile.age, None);
+}
+
+#[test]
+fn patch_writer_sends_null_and_omits_absent() {
+    let sent = UpdateUser {
+        name: Patch::Null,
+        nick: None,
+        profile: Some(ProfilePatch {
+            bio: Patch::Absent,
+            age: Some(Some(42)),
+        }),
+    };
+
+    let request = round_trip(sent);
+    assert_eq!(
+        request.body,
+        br#"{"name":null,"profile":{"age":42}}"#.to_vec()
+    );
+
+    let parsed = UpdateUser::parse(&request).unwrap();
+    assert_eq!(parsed.name, Patch::Null);
+    assert_eq!(parsed.nick, None);
+    assert_eq!(parsed.profile.unwrap().age, Some(Some(42)));
+
+    let sent = UpdateUser {
+        name: Patch::Value("John".to_string()),
+        nick: Some(None),
+        profile: None,
+    };
+    assert_eq!(
+        round_trip(sent).body,
+        br#"{"name":"John","nick":null}"#.to_vec()
+    );
+}
+
+#[test]
+fn patch_schema_is_nullable_and_not_required() {
+    use my_http_utils::schema::data_types::DataTypeProvider;
+
+    for param in UpdateUser::get_input_params() {
+        let nullable = param.field.name != "profile";
+        assert_eq!(param.field.nullable, nullable, "{}", param.field.name);
+        assert!(!param.field.required, "{}", param.field.name);
+    }
+
+    for field in ProfilePatch::get_http_data_structure().main.fields {
+        assert!(field.nullable && !field.required, "{}", field.name);
+    }
+}
+
+// ---- patch documents: `JsonPatch` / `JsonMergePatch` raw bodies ---------------------------------
+
+use my_http_utils::http_input::{JsonMergePatch, JsonPatch};
+
+#[derive(Debug, PartialEq, MyHttpInputObjectStructure)]
+struct Account {
+    name: String,
+    email: Option<String>,
+    tags: Vec<String>,
+}
+
+#[derive(MyHttpInput)]
+struct MergePatchAccount {
+    #[http_body_raw(description = "Merge patch for the account")]
+    body: JsonMergePatch,
+}
+
+#[derive(MyHttpInput)]
+struct JsonPatchAccount {
+    #[http_body_raw(description = "JSON Patch for the account")]
+    body: JsonPatch,
+}
+
+fn account() -> Account {
+    Account {
+        name: "John".to_string(),
+        email: Some("john@example.com".to_string()),
+        tags: vec!["a".to_string()],
+    }
+}
+
+#[test]
+fn merge_patch_body_applies_to_a_model() {
+    let request = FakeRequest::default().body(
+        "application/merge-patch+json",
+        r#"{"name":"Jane","email":null}"#,
+    );
+    let model = MergePatchAccount::parse(&request).unwrap();
+
+    let patched = model.body.apply_to(&account()).unwrap();
+    assert_eq!(
+        patched,
+        Account {
+            name: "Jane".to_string(),
+            email: None,
+            tags: vec!["a".to_string()],
+        }
+    );
+}
+
+#[test]
+fn json_patch_body_applies_to_a_model() {
+    let request = FakeRequest::default().body(
+        "application/json-patch+json",
+        r#"[{"op":"test","path":"/name","value":"John"},{"op":"add","path":"/tags/-","value":"b"}]"#,
+    );
+    let model = JsonPatchAccount::parse(&request).unwrap();
+
+    let patched = model.body.apply_to(&account()).unwrap();
+    assert_eq!(patched.tags, vec!["a".to_string(), "b".to_string()]);
+
+    // A patch that does not apply names the target path; one that breaks the model says so.
+    let failing = JsonPatch::new(br#"[{"op":"remove","path":"/nope"}]"#.to_vec()).unwrap();
+    assert_eq!(failing.apply_to(&account()).unwrap_err().get_pointer(), "/nope");
+
+    let breaking = JsonPatch::new(br#"[{"op":"remove","path":"/name"}]"#.to_vec()).unwrap();
+    assert!(breaking.apply_to(&account()).is_err());
+}
+
+#[test]
+fn a_malformed_patch_body_is_rejected_with_its_pointer() {
+    let request = FakeRequest::default().body(
+        "application/json-patch+json",
+        r#"[{"op":"add","path":"/name","value":"x"},{"op":"shuffle","path":"/tags"}]"#,
+    );
+
+    match JsonPatchAccount::parse(&request) {
+        Err(HttpParseError::InvalidBodyFormat(message)) => {
+            assert!(message.contains("'/1/op'"), "{}", message)
+        }
+        Err(other) => panic!("expected InvalidBodyFormat, got {:?}", other),
+        Ok(_) => panic!("expected InvalidBodyFormat, got a model"),
+    }
+}
+
+#[test]
+fn patch_bodies_declare_their_media_types() {
+    let params = MergePatchAccount::get_input_params();
+    assert_eq!(params[0].content_type, Some("application/merge-patch+json"));
+
+    let params = JsonPatchAccount::get_input_params();
+    assert_eq!(params[0].content_type, Some("application/json-patch+json"));
+    assert!(params[0].field.data_type.is_array());
+
+    let params = QueryAuditInput::get_input_params();
+    assert_eq!(params[0].content_type, Some("application/json"));
+
+    let params = UpdateUser::get_input_params();
+    assert!(params.iter().all(|param| param.content_type.is_none()));
+}
+
+#[test]
+fn patch_bodies_are_sent_verbatim_under_their_media_type() {
+    use my_http_utils::schema::client::THttpRequestBuilder;
+
+    let patch = br#"[{"op":"remove","path":"/email"}]"#.to_vec();
+    let body = JsonPatchAccount {
+        body: JsonPatch::new(patch.clone()).unwrap(),
+    }
+    .get_body::<NoRnd>()
+    .unwrap();
+
+    match body {
+        my_http_utils::body::HttpRequestBody::Raw { data, content_type } => {
+            assert_eq!(data, patch);
+            assert_eq!(content_type, Some("application/json-patch+json"));
+        }
+        _ => panic!("expected a raw body"),
+    }
+}
+
+// ---- `#[http_body_array]`: a body whose root is a JSON array ------------------------------------
+
+#[derive(Debug, PartialEq, MyHttpInputObjectStructure)]
+struct OrderLine {
+    #[json_name("sku")]
+    stock_keeping_unit: String,
+    quantity: u32,
+}
+
+#[derive(MyHttpInput)]
+struct PlaceOrderLines {
+    #[http_path(name = "orderId", description = "")]
+    order_id: String,
+    #[http_body_array(description = "Lines to add")]
+    lines: Vec<OrderLine>,
+}
+
+fn order_line(sku: &str, quantity: u32) -> OrderLine {
+    OrderLine {
+        stock_keeping_unit: sku.to_string(),
+        quantity,
+    }
+}
+
+#[test]
+fn array_body_round_trips_through_the_json_name_keys() {
+    let sent = PlaceOrderLines {
+        order_id: "o-1".to_string(),
+        lines: vec![order_line("A-1", 2), order_line("B-2", 1)],
+    };
+
+    let request = round_trip(sent).path("orderId", "o-1");
+    assert_eq!(
+        request.body,
+        br#"[{"sku":"A-1","quantity":2},{"sku":"B-2","quantity":1}]"#.to_vec()
+    );
+
+    let parsed = PlaceOrderLines::parse(&request).unwrap();
+    assert_eq!(parsed.order_id, "o-1");
+    assert_eq!(parsed.lines, vec![order_line("A-1", 2), order_line("B-2", 1)]);
+    assert!(PlaceOrderLines::READS_BODY);
+
+    let request = FakeRequest::default()
+        .path("orderId", "o-1")
+        .body("application/json", " [ ] ");
+    assert!(PlaceOrderLines::parse(&request).unwrap().lines.is_empty());
+}
+
+#[test]
+fn array_body_errors_name_the_element() {
+    for (body, pointer) in [
+        (r#"[{"sku":"A-1","quantity":2},{"sku":"B-2"}]"#, "'/1'"),
+        (r#"[{"sku":"A-1","quantity":2},7]"#, "'/1'"),
+        (r#"[{"sku":"A-1","quantity":2},{"sku":}]"#, "'/1'"),
+        (r#"{"sku":"A-1","quantity":2}"#, "''"),
+    ] {
+        let request = FakeRequest::default()
+            .path("orderId", "o-1")
+            .body("application/json", body);
+
+        match PlaceOrderLines::parse(&request) {
+            Err(HttpParseError::InvalidBodyFormat(message)) => {
+                assert!(message.contains(pointer), "{}: {}", body, message);
+                assert!(!message.contains("JsonParseError"), "{}: {}", body, message);
+            }
+            Err(other) => panic!("{}: expected InvalidBodyFormat, got {:?}", body, other),
+            Ok(_) => panic!("{}: expected InvalidBodyFormat, got a model", body),
+        }
+    }
+}
+
+#[test]
+fn array_body_schema_is_an_array_of_the_element() {
+    let params = PlaceOrderLines::get_input_params();
+    let body = params.iter().find(|param| param.field.name == "lines").unwrap();
+
+    assert!(matches!(
+        body.source,
+        my_http_utils::schema::in_parameters::HttpParameterInputSource::BodyRaw
+    ));
+    assert_eq!(body.content_type, Some("application/json"));
+
+    match &body.field.data_type {
+        HttpDataType::ArrayOf(my_http_utils::schema::data_types::ArrayElement::Object(object)) => {
+            let names: Vec<&str> = object.main.fields.iter().map(|f| f.name.as_str()).collect();
+            assert_eq!(names, ["sku", "quantity"]);
+        }
+        other => panic!("expected an array of objects, got {:?}", other),
+    }
+}
+
+// ---- `#[http_body(path = "…")]`: members inside envelope objects --------------------------------
+
+#[derive(MyHttpInput)]
+struct PartnerWebhook {
+    #[http_body(description = "Delivery id")]
+    id: String,
+    #[http_body(path = "data.type", description = "")]
+    kind: String,
+    #[http_body(path = "data.attributes.email", description = "Partner e-mail")]
+    email: String,
+    #[http_body(path = "data.attributes.name", description = "")]
+    name: Option<String>,
+}
+
+#[test]
+fn body_path_writes_and_reads_the_same_envelopes() {
+    let sent = PartnerWebhook {
+        id: "w-1".to_string(),
+        kind: "user".to_string(),
+        email: "a@b.c".to_string(),
+        name: None,
+    };
+
+    let request = round_trip(sent);
+    assert_eq!(
+        request.body,
+        br#"{"id":"w-1","data":{"type":"user","attributes":{"email":"a@b.c"}}}"#.to_vec()
+    );
+
+    let parsed = PartnerWebhook::parse(&request).unwrap();
+    assert_eq!(parsed.id, "w-1");
+    assert_eq!(parsed.kind, "user");
+    assert_eq!(parsed.email, "a@b.c");
+    assert_eq!(parsed.name, None);
+
+    // Members the model does not name, in any order, are skipped on the way down.
+    let request = FakeRequest::default().body(
+        "application/json",
+        r#"{"data":{"attributes":{"extra":[1,{"email":"no"}],"name":"Ann","email":"x@y.z"},"type":"user"},"id":"w-2"}"#,
+    );
+    let parsed = PartnerWebhook::parse(&request).unwrap();
+    assert_eq!(parsed.email, "x@y.z");
+    assert_eq!(parsed.name.as_deref(), Some("Ann"));
+}
+
+#[derive(MyHttpInput)]
+struct PartnerWebhookUpdate {
+    #[http_body(description = "Delivery id")]
+    id: String,
+    #[http_body(path = "data.meta.source", description = "")]
+    source: Option<String>,
+    #[http_body(path = "data.attributes.name", description = "")]
+    name: Option<String>,
+}
+
+#[test]
+fn body_path_leaves_out_an_envelope_with_nothing_in_it() {
+    let sent = PartnerWebhookUpdate {
+        id: "w-1".to_string(),
+        source: None,
+        name: Some("Ann".to_string()),
+    };
+    let request = round_trip(sent);
+    assert_eq!(
+        request.body,
+        br#"{"id":"w-1","data":{"attributes":{"name":"Ann"}}}"#.to_vec()
+    );
+
+    let sent = PartnerWebhookUpdate {
+        id: "w-1".to_string(),
+        source: None,
+        name: None,
+    };
+    let request = round_trip(sent);
+    assert_eq!(request.body, br#"{"id":"w-1"}"#.to_vec());
+
+    let parsed = PartnerWebhookUpdate::parse(&request).unwrap();
+    assert_eq!(parsed.source, None);
+    assert_eq!(parsed.name, None);
+}
+
+#[test]
+fn body_path_reports_the_whole_path_when_missing() {
+    for body in [
+        r#"{"id":"w-1","data":{"type":"user"}}"#,
+        r#"{"id":"w-1","data":{"type":"user","attributes":null}}"#,
+        r#"{"id":"w-1","data":{"type":"user","attributes":"email"}}"#,
+    ] {
+        let request = FakeRequest::default().body("application/json", body);
+        match PartnerWebhook::parse(&request) {
+            Err(HttpParseError::RequiredParameterIsMissing { name, src }) => {
+                assert_eq!(name, "data.attributes.email", "{}", body);
+                assert_eq!(src, "BodyJson", "{}", body);
+            }
+            Err(other) => panic!("{}: expected RequiredParameterIsMissing, got {:?}", body, other),
+            Ok(_) => panic!("{}: expected RequiredParameterIsMissing, got a model", body),
+        }
+    }
+}
+
+#[test]
+fn body_path_in_a_form_is_one_flat_key() {
+    let request = FakeRequest::default().body(
+        "application/x-www-form-urlencoded",
+        "id=w-1&data.type=user&data.attributes.email=a%40b.c",
+    );
+
+    let parsed = PartnerWebhook::parse(&request).unwrap();
+    assert_eq!(parsed.kind, "user");
+    assert_eq!(parsed.email, "a@b.c");
+}
+
+#[test]
+fn body_path_schema_describes_the_envelope() {
+    let params = PartnerWebhook::get_input_params();
+    let names: Vec<&str> = params.iter().map(|p| p.field.name.as_str()).collect();
+    assert_eq!(names, ["id", "data"]);
+
+    let data = &params[1];
+    assert!(data.field.required);
+    assert_eq!(data.description, "data.attributes.email: Partner e-mail");
+
+    let object = match &data.field.data_type {
+        HttpDataType::Object(object) => object,
+        other => panic!("expected an object, got {:?}", other),
+    };
+    assert_eq!(object.main.struct_id, "PartnerWebhookData");
+    let names: Vec<&str> = object.main.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["type", "attributes"]);
+
+    match &object.main.fields[1].data_type {
+        HttpDataType::Object(attributes) => {
+            assert_eq!(attributes.main.struct_id, "PartnerWebhookDataAttributes");
+            let fields: Vec<(&str, bool)> = attributes
+                .main
+                .fields
+                .iter()
+                .map(|f| (f.name.as_str(), f.required))
+                .collect();
+            assert_eq!(fields, [("email", true), ("name", false)]);
+        }
+        other => panic!("expected an object, got {:?}", other),
+    }
+}
+
+// ---- strict bodies: `#[http_input(strict_body)]` / `#[json_strict]` ------------------------------
+
+#[derive(Debug, PartialEq, MyHttpInputObjectStructure)]
+struct StrictCard {
+    #[json_name("cardNumber")]
+    card_number: String,
+    exp_month: String,
+}
+
+#[derive(MyHttpInput)]
+#[http_input(strict_body)]
+struct StrictPayment {
+    #[http_path(name = "id", description = "")]
+    id: String,
+    #[http_body(description = "Amount")]
+    amount: String,
+    #[http_body(description = "Card")]
+    card: Option<StrictCard>,
+    #[http_body(path = "meta.source", description = "")]
+    source: Option<String>,
+}
+
+fn strict_card() -> StrictCard {
+    StrictCard {
+        card_number: "4111".to_string(),
+        exp_month: "12".to_string(),
+    }
+}
+
+#[test]
+fn strict_body_accepts_exactly_the_declared_keys() {
+    let sent = StrictPayment {
+        id: "p-1".to_string(),
+        amount: "10.00".to_string(),
+        card: Some(strict_card()),
+        source: Some("web".to_string()),
+    };
+
+    let parsed = StrictPayment::parse(&round_trip(sent).path("id", "p-1")).unwrap();
+    assert_eq!(parsed.amount, "10.00");
+    assert_eq!(parsed.card, Some(strict_card()));
+    assert_eq!(parsed.source.as_deref(), Some("web"));
+
+    // A null or absent optional member is still fine.
+    let request = FakeRequest::default()
+        .path("id", "p-1")
+        .body("application/json", r#"{"amount":"1","card":null}"#);
+    assert!(StrictPayment::parse(&request).is_ok());
+}
+
+#[test]
+fn strict_body_names_the_offending_member() {
+    for (body, expected) in [
+        (r#"{"amount":"1","amout":"1"}"#, "unknown member at '/amout'"),
+        (r#"{"amount":"1","amount":"2"}"#, "repeated member at '/amount'"),
+        (
+            r#"{"amount":"1","card":{"cardNumber":"4","exp_month":"1","cvv":"1"}}"#,
+            "unknown member at '/card/cvv'",
+        ),
+        (
+            r#"{"amount":"1","card":{"cardNumber":"4","cardNumber":"5","exp_month":"1"}}"#,
+            "repeated member at '/card/cardNumber'",
+        ),
+        (
+            r#"{"amount":"1","meta":{"source":"web","sorce":"x"}}"#,
+            "unknown member at '/meta/sorce'",
+        ),
+    ] {
+        let request = FakeRequest::default()
+            .path("id", "p-1")
+            .body("application/json", body);
+
+        match StrictPayment::parse(&request) {
+            Err(HttpParseError::InvalidBodyFormat(message)) => {
+                assert_eq!(message, expected, "{}", body)
+            }
+            Err(other) => panic!("{}: expected InvalidBodyFormat, got {:?}", body, other),
+            Ok(_) => panic!("{}: expected InvalidBodyFormat, got a model", body),
+        }
+    }
+
+    // The lenient default takes the same body without a word.
+    let request = FakeRequest::default().body(
+        "application/json",
+        r#"{"challengeId":"c1","challengeld":"c2"}"#,
+    );
+    assert!(PayInput::parse(&request).is_ok());
+}
+
+#[test]
+fn strict_body_checks_a_form_name_by_name() {
+    let form = |body: &str| {
+        FakeRequest::default()
+            .path("id", "p-1")
+            .body("application/x-www-form-urlencoded", body)
+    };
+
+    let parsed = StrictPayment::parse(&form("amount=1&meta.source=web")).unwrap();
+    assert_eq!(parsed.source.as_deref(), Some("web"));
+
+    for (body, expected) in [
+        ("amount=1&amout=1", "unknown member at '/amout'"),
+        ("amount=1&amount=2", "repeated member at '/amount'"),
+        ("amount=1&meta.sorce=web", "unknown member at '/meta.sorce'"),
+    ] {
+        match StrictPayment::parse(&form(body)) {
+            Err(HttpParseError::InvalidBodyFormat(message)) => {
+                assert_eq!(message, expected, "{}", body)
+            }
+            Err(other) => panic!("{}: expected InvalidBodyFormat, got {:?}", body, other),
+            Ok(_) => panic!("{}: expected InvalidBodyFormat, got a model", body),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, MyHttpInputObjectStructure)]
+#[json_strict]
+struct StrictLine {
+    sku: String,
+    quantity: u32,
+}
+
+#[derive(MyHttpInput)]
+struct StrictLines {
+    #[http_body_array(description = "")]
+    lines: Vec<StrictLine>,
+}
+
+#[test]
+fn json_strict_object_rejects_unknown_and_repeated_members() {
+    let request = FakeRequest::default().body(
+        "application/json",
+        r#"[{"sku":"A","quantity":1},{"quantity":2,"sku":"B"}]"#,
+    );
+    assert_eq!(StrictLines::parse(&request).unwrap().lines.len(), 2);
+
+    for (body, expected) in [
+        (r#"[{"sku":"A","quantity":1,"qty":1}]"#, "unknown member 'qty'"),
+        (r#"[{"sku":"A","sku":"B","quantity":1}]"#, "repeated member 'sku'"),
+    ] {
+        let request = FakeRequest::default().body("application/json", body);
+        match StrictLines::parse(&request) {
+            Err(HttpParseError::InvalidBodyFormat(message)) => {
+                assert!(message.contains(expected), "{}: {}", body, message);
+                assert!(message.contains("'/0'"), "{}: {}", body, message);
+            }
+            Err(other) => panic!("{}: expected InvalidBodyFormat, got {:?}", body, other),
+            Ok(_) => panic!("{}: expected InvalidBodyFormat, got a model", body),
+        }
+    }
+}
+
+// ---- `#[json_flatten]` / `#[json_default]` / `#[json_skip_if]` ------------------------------------
+
+#[derive(Debug, PartialEq, MyHttpInputObjectStructure)]
+struct AuditStamp {
+    #[json_name("createdBy")]
+    created_by: String,
+    #[json_default]
+    revision: u32,
+}
+
+fn default_currency() -> String {
+    "EUR".to_string()
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+#[derive(Debug, PartialEq, MyHttpInputObjectStructure)]
+#[json_strict]
+struct Invoice {
+    number: String,
+    #[json_default = "default_currency"]
+    currency: String,
+    #[json_default]
+    #[json_skip_if = "is_zero"]
+    discount: u32,
+    #[json_flatten]
+    audit: AuditStamp,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+#[derive(MyHttpInput)]
+struct CreateInvoice {
+    #[http_body(description = "Invoice")]
+    invoice: Invoice,
+}
+
+fn invoice(discount: u32) -> Invoice {
+    Invoice {
+        number: "7".to_string(),
+        currency: "USD".to_string(),
+        discount,
+        audit: AuditStamp {
+            created_by: "ann".to_string(),
+            revision: 2,
+        },
+        note: None,
+    }
+}
+
+#[test]
+fn flattened_members_are_written_and_read_inline() {
+    let request = round_trip(CreateInvoice {
+        invoice: invoice(0),
+    });
+    assert_eq!(
+        request.body,
+        br#"{"invoice":{"number":"7","currency":"USD","createdBy":"ann","revision":2}}"#.to_vec()
+    );
+    assert_eq!(CreateInvoice::parse(&request).unwrap().invoice, invoice(0));
+
+    let request = round_trip(CreateInvoice {
+        invoice: invoice(5),
+    });
+    assert_eq!(
+        request.body,
+        br#"{"invoice":{"number":"7","currency":"USD","discount":5,"createdBy":"ann","revision":2}}"#
+            .to_vec()
+    );
+    assert_eq!(CreateInvoice::parse(&request).unwrap().invoice, invoice(5));
+}
+
+#[test]
+fn json_default_fills_absent_and_null_members() {
+    for body in [
+        r#"{"invoice":{"number":"7","createdBy":"ann"}}"#,
+        r#"{"invoice":{"number":"7","currency":null,"discount":null,"createdBy":"ann"}}"#,
+    ] {
+        let request = FakeRequest::default().body("application/json", body);
+        let parsed = CreateInvoice::parse(&request).unwrap().invoice;
+        assert_eq!(parsed.currency, "EUR", "{}", body);
+        assert_eq!(parsed.discount, 0, "{}", body);
+        assert_eq!(parsed.audit.revision, 0, "{}", body);
+        assert_eq!(parsed.note, None, "{}", body);
+    }
+
+    // A member of the flattened structure with no default is still required.
+    let request = FakeRequest::default().body("application/json", r#"{"invoice":{"number":"7"}}"#);
+    assert!(CreateInvoice::parse(&request).is_err());
+}
+
+#[test]
+fn json_strict_counts_flattened_keys_as_known() {
+    let request = FakeRequest::default().body(
+        "application/json",
+        r#"{"invoice":{"number":"7","createdBy":"ann","revision":1,"note":"n"}}"#,
+    );
+    let parsed = CreateInvoice::parse(&request).unwrap().invoice;
+    assert_eq!(parsed.audit.revision, 1);
+    assert_eq!(parsed.note.as_deref(), Some("n"));
+
+    let request = FakeRequest::default().body(
+        "application/json",
+        r#"{"invoice":{"number":"7","createdBy":"ann","audit":{}}}"#,
+    );
+    match CreateInvoice::parse(&request) {
+        Err(HttpParseError::InvalidBodyFormat(message)) => {
+            assert!(message.contains("unknown member 'audit'"), "{}", message)
+        }
+        Err(other) => panic!("expected InvalidBodyFormat, got {:?}", other),
+        Ok(_) => panic!("expected InvalidBodyFormat, got a model"),
+    }
+}
+
+#[test]
+fn flattened_and_defaulted_members_in_the_schema() {
+    use my_http_utils::schema::data_types::DataTypeProvider;
+
+    let fields: Vec<(String, bool)> = Invoice::get_http_data_structure()
+        .main
+        .fields
+        .into_iter()
+        .map(|f| (f.name, f.required))
+        .collect();
+
+    let expected = [
+        ("number", true),
+        ("currency", false),
+        ("discount", false),
+        ("createdBy", true),
+        ("revision", false),
+        ("note", false),
+    ];
+    let fields: Vec<(&str, bool)> = fields.iter().map(|(n, r)| (n.as_str(), *r)).collect();
+    assert_eq!(fields, expected);
+}
+
+// ---- canonical JSON (RFC 8785) -------------------------------------------------------------------
+
+#[test]
+fn canonical_body_is_the_same_on_both_sides() {
+    use my_http_utils::schema::client::THttpRequestBuilder;
+
+    let sent = CreateInvoice {
+        invoice: invoice(5),
+    }
+    .get_canonical_json_body()
+    .unwrap();
+    assert_eq!(
+        sent,
+        br#"{"invoice":{"createdBy":"ann","currency":"USD","discount":5,"number":"7","revision":2}}"#
+            .to_vec()
+    );
+
+    // Another writer's spelling of the same body: other order, whitespace, other number forms.
+    let received = br#"{ "invoice" : { "revision" : 2.0, "number" : "7", "discount" : 5e0,
+        "currency" : "USD", "createdBy" : "ann" } }"#;
+    assert_eq!(my_http_utils::to_canonical_json(received).unwrap(), sent);
+
+    assert_eq!(
+        my_http_utils::write_canonical_json(&invoice(5)).unwrap(),
+        br#"{"createdBy":"ann","currency":"USD","discount":5,"number":"7","revision":2}"#.to_vec()
+    );
+}
+
+#[derive(MyHttpInput)]
+struct CreateLedgerEntry {
+    #[http_body(description = "Entry id")]
+    id: u64,
+}
+
+#[test]
+fn canonical_body_refuses_an_integer_a_double_would_round() {
+    use my_http_utils::schema::client::THttpRequestBuilder;
+
+    let sent = CreateLedgerEntry { id: 1 << 53 }
+        .get_canonical_json_body()
+        .unwrap();
+    assert_eq!(sent, br#"{"id":9007199254740992}"#.to_vec());
+
+    let err = CreateLedgerEntry { id: (1 << 53) + 1 }
+        .get_canonical_json_body()
+        .unwrap_err();
+    assert_eq!(err.code(), "E_CANONICAL_BODY");
+    assert_eq!(
+        err.reason,
+        "integer can not be held exactly by an IEEE 754 double at '/id'"
+    );
+}
+
+// ---- per-field date-time formats: `format = "…"` / `#[json_format = "…"]` -----------------------
+
+use my_http_utils::schema::data_types::ArrayElement;
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+// 2024-02-29T13:45:07.123456Z
+const SHIPPED_AT: i64 = 1_709_214_307_123_456;
+
+#[derive(MyHttpInputObjectStructure)]
+struct Shipment {
+    #[json_format = "unix_ms"]
+    shipped_at: DateTimeAsMicroseconds,
+    #[json_format = "%d/%m/%Y %H:%M"]
+    delivered_at: Option<DateTimeAsMicroseconds>,
+}
+
+#[derive(MyHttpInput)]
+struct ListShipments {
+    #[http_query(name = "since", description = "", format = "unix_s")]
+    since: DateTimeAsMicroseconds,
+    #[http_query(name = "until", description = "", format = "date", default = "2030-01-01")]
+    until: DateTimeAsMicroseconds,
+    #[http_query(name = "days", description = "", format = "date")]
+    days: Vec<DateTimeAsMicroseconds>,
+    #[http_header(name = "X-As-Of", description = "", format = "%Y%m%d%H%M%S")]
+    as_of: Option<DateTimeAsMicroseconds>,
+    #[http_body(name = "expiresAt", description = "", format = "unix_us")]
+    expires_at: DateTimeAsMicroseconds,
+    #[http_body(name = "shipment", description = "")]
+    shipment: Shipment,
+}
+
+fn list_shipments() -> ListShipments {
+    let moment = DateTimeAsMicroseconds::new(SHIPPED_AT);
+    ListShipments {
+        since: moment,
+        until: DateTimeAsMicroseconds::new(SHIPPED_AT + 86_400_000_000),
+        days: vec![moment, DateTimeAsMicroseconds::new(SHIPPED_AT + 86_400_000_000)],
+        as_of: Some(moment),
+        expires_at: moment,
+        shipment: Shipment {
+            shipped_at: moment,
+            delivered_at: Some(moment),
+        },
+    }
+}
+
+#[test]
+fn formatted_dates_are_written_in_their_format() {
+    use my_http_utils::schema::client::THttpRequestBuilder;
+
+    let sent = list_shipments();
+
+    let mut url = my_http_utils::UrlBuilder::new("http://h");
+    sent.fill_url(&mut url).unwrap();
+    assert_eq!(
+        url.to_string(),
+        "http://h?since=1709214307&until=2024-03-01&days=2024-02-29&days=2024-03-01"
+    );
+
+    let request = round_trip(sent);
+    assert_eq!(
+        request.body,
+        br#"{"expiresAt":1709214307123456,"shipment":{"shipped_at":1709214307123,"delivered_at":"29/02/2024 13:45"}}"#
+            .to_vec()
+    );
+}
+
+#[test]
+fn formatted_dates_are_read_in_their_format() {
+    let request = round_trip(list_shipments())
+        .query("since=1709214307&days=2024-02-29&days=2024-03-01")
+        .header("X-As-Of", "20240229134507");
+    let parsed = ListShipments::parse(&request).unwrap();
+
+    assert_eq!(parsed.since.unix_microseconds, 1_709_214_307_000_000);
+    // Absent: the default, spelled in the field's format.
+    assert_eq!(parsed.until.unix_microseconds, 1_893_456_000_000_000);
+    let days: Vec<i64> = parsed.days.iter().map(|d| d.unix_microseconds).collect();
+    assert_eq!(days, [1_709_164_800_000_000, 1_709_251_200_000_000]);
+    assert_eq!(
+        parsed.as_of.map(|d| d.unix_microseconds),
+        Some(1_709_214_307_000_000)
+    );
+    assert_eq!(parsed.expires_at.unix_microseconds, SHIPPED_AT);
+    assert_eq!(
+        parsed.shipment.shipped_at.unix_microseconds,
+        1_709_214_307_123_000
+    );
+    assert_eq!(
+        parsed.shipment.delivered_at.map(|d| d.unix_microseconds),
+        Some(1_709_214_300_000_000)
+    );
+}
+
+#[test]
+fn formatted_dates_reject_other_spellings() {
+    let body = r#"{"expiresAt":1,"shipment":{"shipped_at":1}}"#;
+    let parse = |query: &str, body: &str| {
+        ListShipments::parse(
+            &FakeRequest::default()
+                .query(query)
+                .body("application/json", body),
+        )
+    };
+
+    assert!(parse("since=1", body).is_ok());
+    // A unix member may also come as a string of digits.
+    assert!(parse("since=1", r#"{"expiresAt":"1","shipment":{"shipped_at":"1"}}"#).is_ok());
+
+    // The spelling a date without a format would have used is not this field's.
+    assert!(parse("since=2024-02-29T13:45:07Z", body).is_err());
+    assert!(parse("since=1&days=2024-2-29", body).is_err());
+    assert!(parse(
+        "since=1",
+        r#"{"expiresAt":"2024-02-29T13:45:07Z","shipment":{"shipped_at":1}}"#
+    )
+    .is_err());
+    assert!(parse("since=1", r#"{"expiresAt":1,"shipment":{"shipped_at":1.5}}"#).is_err());
+    assert!(parse(
+        "since=1",
+        r#"{"expiresAt":1,"shipment":{"shipped_at":1,"delivered_at":"2024-02-29 13:45"}}"#
+    )
+    .is_err());
+}
+
+#[derive(MyHttpInput)]
+struct UploadReport {
+    #[http_form_data(name = "day", description = "", format = "%d.%m.%Y")]
+    day: DateTimeAsMicroseconds,
+}
+
+#[test]
+fn formatted_date_in_form_data() {
+    let boundary = "TESTBOUNDARY";
+    let body = format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"day\"\r\n\r\n29.02.2024\r\n--{b}--\r\n",
+        b = boundary
+    );
+    let request = FakeRequest::default().body(
+        &format!("multipart/form-data; boundary={}", boundary),
+        body.into_bytes(),
+    );
+    let model = UploadReport::parse(&request).unwrap();
+    assert_eq!(model.day.unix_microseconds, 1_709_164_800_000_000);
+}
+
+#[test]
+fn formatted_dates_in_the_schema() {
+    let params = ListShipments::get_input_params();
+    let field = |name: &str| {
+        params
+            .iter()
+            .map(|p| &p.field)
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("no parameter {}", name))
+    };
+
+    assert!(matches!(
+        field("since").data_type,
+        HttpDataType::SimpleType(HttpSimpleType::Long)
+    ));
+    assert!(matches!(
+        field("until").data_type,
+        HttpDataType::SimpleType(HttpSimpleType::Date)
+    ));
+    assert!(!field("until").required);
+    assert!(matches!(
+        field("days").data_type,
+        HttpDataType::ArrayOf(ArrayElement::SimpleType(HttpSimpleType::Date))
+    ));
+    assert!(matches!(
+        field("X-As-Of").data_type,
+        HttpDataType::SimpleType(HttpSimpleType::String)
+    ));
+    assert!(matches!(
+        field("expiresAt").data_type,
+        HttpDataType::SimpleType(HttpSimpleType::Long)
+    ));
+
+    let fields = Shipment::get_http_data_structure().main.fields;
+    assert!(matches!(
+        fields[0].data_type,
+        HttpDataType::SimpleType(HttpSimpleType::Long)
+    ));
+    assert!(matches!(
+        fields[1].data_type,
+        HttpDataType::SimpleType(HttpSimpleType::String)
+    ));
+}
+
+// ---- parse profiles: `#[http_input(parse_profile = "…")]` / `parse_profile = "…"` ---------------
+
+use my_http_utils::http_input::ParseProfile;
+
+#[derive(MyHttpInput)]
+#[http_input(parse_profile = "lenient")]
+struct LenientSearch {
+    #[http_query(name = "page", description = "")]
+    page: Option<u32>,
+    #[http_query(name = "limit", description = "", default = "20")]
+    limit: u32,
+    #[http_query(name = "archived", description = "")]
+    archived: bool,
+    #[http_header(name = "X-Dry-Run", description = "")]
+    dry_run: Option<bool>,
+    #[http_query(name = "exact", description = "", parse_profile = "standard")]
+    exact: Option<bool>,
+}
+
+#[test]
+fn lenient_profile_takes_loose_spellings() {
+    let request = FakeRequest::default()
+        .query("page=&limit=%2030%20&archived=Yes")
+        .header("X-Dry-Run", " off ");
+    let parsed = LenientSearch::parse(&request).unwrap();
+    assert_eq!(parsed.page, None);
+    assert_eq!(parsed.limit, 30);
+    assert!(parsed.archived);
+    assert_eq!(parsed.dry_run, Some(false));
+
+    // Blank is absent: the default, or a missing parameter when there is none.
+    let parsed =
+        LenientSearch::parse(&FakeRequest::default().query("limit=%20&archived=on")).unwrap();
+    assert_eq!(parsed.limit, 20);
+    match LenientSearch::parse(&FakeRequest::default().query("archived=")) {
+        Err(HttpParseError::RequiredParameterIsMissing { name, .. }) => {
+            assert_eq!(name, "archived")
+        }
+        other => panic!("expected a missing parameter, got {:?}", other.err()),
+    }
+
+    // The field's own profile wins over the model's.
+    assert!(LenientSearch::parse(&FakeRequest::default().query("archived=1&exact=yes")).is_err());
+}
+
+#[derive(MyHttpInput)]
+#[http_input(parse_profile = "strict")]
+struct StrictTransfer {
+    #[http_body(name = "amount", description = "")]
+    amount: i64,
+    #[http_body(name = "instant", description = "")]
+    instant: bool,
+    #[http_body(name = "reference", description = "", parse_profile = "lenient")]
+    reference: Option<u64>,
+}
+
+#[derive(MyHttpInput)]
+struct StandardTransfer {
+    #[http_body(name = "amount", description = "")]
+    amount: i64,
+    #[http_body(name = "instant", description = "")]
+    instant: bool,
+}
+
+#[test]
+fn strict_profile_takes_only_exact_json_scalars() {
+    let parse =
+        |body: &str| StrictTransfer::parse(&FakeRequest::default().body("application/json", body));
+
+    let parsed = parse(r#"{"amount":42,"instant":true,"reference":" 7 "}"#).unwrap();
+    assert_eq!(parsed.amount, 42);
+    assert!(parsed.instant);
+    assert_eq!(parsed.reference, Some(7));
+    assert_eq!(
+        parse(r#"{"amount":42,"instant":false,"reference":""}"#)
+            .unwrap()
+            .reference,
+        None
+    );
+
+    for body in [
+        r#"{"amount":"42","instant":true}"#,
+        r#"{"amount":42,"instant":"true"}"#,
+        r#"{"amount":42,"instant":1}"#,
+    ] {
+        assert!(parse(body).is_err(), "{}", body);
+    }
+
+    // The same spellings a standard model accepts.
+    let request = FakeRequest::default().body("application/json", r#"{"amount":"42","instant":1}"#);
+    let parsed = StandardTransfer::parse(&request).unwrap();
+    assert_eq!(parsed.amount, 42);
+    assert!(parsed.instant);
+}
+
+#[derive(MyHttpInput)]
+struct NewsletterSignup {
+    #[http_form_data(name = "age", description = "", parse_profile = "lenient")]
+    age: Option<u8>,
+    #[http_form_data(name = "subscribe", description = "", parse_profile = "lenient")]
+    subscribe: bool,
+    #[http_form_data(name = "consent", description = "", parse_profile = "strict")]
+    consent: bool,
+}
+
+#[test]
+fn profiles_apply_to_form_fields() {
+    let boundary = "TESTBOUNDARY";
+    let request = |consent: &str| {
+        let body = format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"age\"\r\n\r\n \r\n\
+             --{b}\r\nContent-Disposition: form-data; name=\"subscribe\"\r\n\r\non\r\n\
+             --{b}\r\nContent-Disposition: form-data; name=\"consent\"\r\n\r\n{c}\r\n--{b}--\r\n",
+            b = boundary,
+            c = consent
+        );
+        FakeRequest::default().body(
+            &format!("multipart/form-data; boundary={}", boundary),
+            body.into_bytes(),
+        )
+    };
+
+    let parsed = NewsletterSignup::parse(&request("true")).unwrap();
+    assert_eq!(parsed.age, None);
+    assert!(parsed.subscribe);
+    assert!(parsed.consent);
+
+    assert!(NewsletterSignup::parse(&request("True")).is_err());
+}
+
+fn parse_profile_of(
+    params: &[my_http_utils::schema::in_parameters::HttpInputParameter],
+    name: &str,
+) -> ParseProfile {
+    params
+        .iter()
+        .find(|p| p.field.name == name)
+        .map(|p| p.parse_profile)
+        .unwrap_or_else(|| panic!("no parameter {}", name))
+}
+
+#[test]
+fn parse_profiles_in_the_schema() {
+    let search = LenientSearch::get_input_params();
+    assert_eq!(parse_profile_of(&search, "page"), ParseProfile::Lenient);
+    assert_eq!(
+        parse_profile_of(&search, "X-Dry-Run"),
+        ParseProfile::Lenient
+    );
+    assert_eq!(parse_profile_of(&search, "exact"), ParseProfile::Standard);
+
+    let transfer = StrictTransfer::get_input_params();
+    assert_eq!(parse_profile_of(&transfer, "amount"), ParseProfile::Strict);
+    assert_eq!(
+        parse_profile_of(&transfer, "reference"),
+        ParseProfile::Lenient
+    );
+
+    let signup = NewsletterSignup::get_input_params();
+    assert_eq!(parse_profile_of(&signup, "consent"), ParseProfile::Strict);
+
+    let shipments = ListShipments::get_input_params();
+    assert_eq!(
+        parse_profile_of(&shipments, "since"),
+        ParseProfile::Standard
+    );
+}
+
+// ---- stable error codes and the JSON form of an error --------------------------------------------
+
+#[test]
+fn parse_error_crosses_the_wire_as_json() {
+    let err = RequiredQuery::parse(&FakeRequest::default().query("n=abc")).unwrap_err();
+    assert_eq!(err.code(), my_http_utils::error_code::E_CANNOT_PARSE);
+
+    let json = err.to_json();
+    assert_eq!(
+        json,
+        r#"{"code":"E_CANNOT_PARSE","message":"Can not parse 'n' value 'abc' from QueryString","name":"n","src":"QueryString","value":"abc"}"#
+    );
+    assert_eq!(HttpParseError::from_json(json.as_bytes()).unwrap(), err);
+
+    let missing = RequiredQuery::parse(&FakeRequest::default()).unwrap_err();
+    assert_eq!(missing.code(), "E_REQUIRED_MISSING");
+    assert_eq!(
+        HttpParseError::from_json(missing.to_json().as_bytes()).unwrap(),
+        missing
+    );
+}
+
+#[test]
+fn build_error_crosses_the_wire_as_json() {
+    use my_http_utils::schema::client::{
+        HttpRequestBuildError, HttpRequestBuildErrorKind, THttpRequestBuilder,
+    };
+
+    let err = RequiredQuery { n: 1 }
+        .get_canonical_json_body()
+        .unwrap_err();
+    assert_eq!(err.get_kind(), HttpRequestBuildErrorKind::CanonicalBody);
+    assert_eq!(err.code(), "E_CANONICAL_BODY");
+    assert_eq!(
+        HttpRequestBuildError::from_json(err.to_json().as_bytes()).unwrap(),
+        err
+    );
+
+    let rejected = HttpRequestBuildError::new("code", "not digits");
+    assert_eq!(rejected.code(), "E_VALIDATION");
+    assert_eq!(
+        HttpRequestBuildError::from_json(rejected.to_json().as_bytes()).unwrap(),
+        rejected
+    );
+}
+
+// ---- the HTTP status of a failed parse -----------------------------------------------------------
+
+struct BadRequestOnly;
+
+impl my_http_utils::http_input::HttpParseErrorStatusPolicy for BadRequestOnly {
+    fn status_code(&self, _error: &HttpParseError) -> u16 {
+        400
+    }
+
+    fn documented_statuses(&self) -> Vec<(u16, &'static str)> {
+        vec![(400, "Bad request")]
+    }
+}
+
+#[test]
+fn a_policy_overrides_the_default_status() {
+    use my_http_utils::http_input::{
+        DefaultHttpParseErrorStatusPolicy, HttpParseErrorStatusPolicy,
+    };
+
+    let err = AllSources::parse(
+        &FakeRequest::default()
+            .path("id", "u1")
+            .query("n=1&tags=x&code=12a&s=x&color=red")
+            .header("X-Api-Key", "K")
+            .body("application/json", r#"{"amount":1.0}"#),
+    )
+    .unwrap_err();
+    assert_eq!(err.status_code(), 422);
+    assert_eq!(DefaultHttpParseErrorStatusPolicy.status_code(&err), 422);
+    assert_eq!(BadRequestOnly.status_code(&err), 400);
+}
+
+#[test]
+fn routes_with_parameters_document_the_parse_failures() {
+    use my_http_utils::http_input::DefaultHttpParseErrorStatusPolicy;
+    use my_http_utils::schema::out_results::HttpResult;
+
+    let results = HttpResult::parse_error_results(
+        &DefaultHttpParseErrorStatusPolicy,
+        &RequiredQuery::get_input_params(),
+    );
+    let codes: Vec<u16> = results.iter().map(|r| r.http_code).collect();
+    assert_eq!(codes, vec![400, 422]);
+
+    let results = HttpResult::parse_error_results(&BadRequestOnly, &AllSources::get_input_params());
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].description, "Bad request");
+
+    assert!(HttpResult::parse_error_results(&DefaultHttpParseErrorStatusPolicy, &[]).is_empty());
+}
+
+// ---- `Authorization` headers: `BasicAuthCredentials` / `BearerToken` ----------------------------
+
+use my_http_utils::http_input::{BasicAuthCredentials, BearerToken};
+
+/// What the client writes as headers is what the server reads them from.
+impl my_http_utils::schema::client::HeaderBuilder for FakeRequest {
+    fn add_header(&mut self, name: &str, value: &str) {
+        self.headers.push((name.to_string(), value.to_string()));
+    }
+}
+
+#[derive(Debug, MyHttpInput)]
+struct AdminLogin {
+    #[http_header(name = "Authorization", description = "Admin credentials")]
+    credentials: BasicAuthCredentials,
+    #[http_query(name = "tenant", description = "")]
+    tenant: String,
+}
+
+#[derive(Debug, MyHttpInput)]
+struct ListOrders {
+    #[http_header(name = "Authorization", description = "Access token")]
+    token: Option<BearerToken>,
+}
+
+#[test]
+fn authorization_headers_client_server_round_trip() {
+    use my_http_utils::schema::client::THttpRequestBuilder;
+
+    let mut request = FakeRequest::default().query("tenant=t1");
+    AdminLogin {
+        credentials: BasicAuthCredentials::new("Aladdin", "open sesame").unwrap(),
+        tenant: "t1".to_string(),
+    }
+    .fill_headers(&mut request)
+    .unwrap();
+    assert_eq!(
+        request.headers,
+        vec![(
+            "Authorization".to_string(),
+            "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==".to_string()
+        )]
+    );
+
+    let parsed = AdminLogin::parse(&request).unwrap();
+    assert_eq!(parsed.credentials.get_user(), "Aladdin");
+    assert_eq!(parsed.credentials.get_password(), "open sesame");
+
+    let parsed =
+        ListOrders::parse(&FakeRequest::default().header("Authorization", "Bearer abc.def"))
+            .unwrap();
+    assert_eq!(parsed.token.unwrap().get_token(), "abc.def");
+    assert!(ListOrders::parse(&FakeRequest::default())
+        .unwrap()
+        .token
+        .is_none());
+}
+
+#[test]
+fn a_malformed_credential_does_not_leak_into_the_error() {
+    let request = FakeRequest::default()
+        .query("tenant=t1")
+        .header("Authorization", "Bearer s3cret");
+    let err = AdminLogin::parse(&request).unwrap_err();
+    assert_eq!(
+        err,
+        HttpParseError::cannot_parse("Authorization", "Header", "<redacted>")
+    );
+    assert!(!err.to_string().contains("s3cret"));
+
+    let parsed =
+        ListOrders::parse(&FakeRequest::default().header("Authorization", "Bearer s3cret"))
+            .unwrap();
+    assert!(!format!("{:?}", parsed).contains("s3cret"));
+}
+
+#[test]
+fn credentials_are_security_schemes_not_headers() {
+    use my_http_utils::schema::in_parameters::{HttpParameters, HttpSecurityScheme};
+
+    let params = AdminLogin::get_input_params();
+    let credentials = params
+        .iter()
+        .find(|p| p.field.name == "Authorization")
+        .unwrap();
+    assert_eq!(credentials.get_security_scheme(), Some(HttpSecurityScheme::Basic));
+
+    let parameters = HttpParameters::from(params);
+    let security = parameters.get_security_params().unwrap();
+    assert_eq!(security.len(), 1);
+    assert_eq!(
+        security[0].get_security_scheme().unwrap().get_scheme(),
+        Some("basic")
+    );
+    assert!(parameters
+        .get_non_body_params()
+        .unwrap()
+        .iter()
+        .all(|p| p.field.name == "tenant"));
+
+    let bearer = ListOrders::get_input_params();
+    assert_eq!(bearer[0].get_security_scheme(), Some(HttpSecurityScheme::Bearer));
+    assert_eq!(bearer[0].get_security_scheme().unwrap().get_type(), "http");
+}
+// ---- security requirements: `#[http_security(…)]` -----------------------------------------------
+
+use my_http_utils::schema::in_parameters::{HttpSecurityRequirement, HttpSecuritySchemes};
+
+#[derive(Debug, MyHttpInput)]
+#[http_security(api_key_header = "X-Api-Key")]
+#[http_security(bearer)]
+struct ListInvoices {
+    #[http_query(name = "page", description = "")]
+    page: Option<u32>,
+}
+
+#[derive(Debug, MyHttpInput)]
+#[http_security(basic, api_key_query = "tenant_key")]
+struct ExportInvoices {
+    #[http_query(name = "tenant_key", description = "")]
+    tenant_key: String,
+}
+
+#[test]
+fn a_model_requires_one_of_its_security_requirements() {
+    let parsed = ListInvoices::parse(&FakeRequest::default().header("X-Api-Key", "k1")).unwrap();
+    assert_eq!(parsed.page, None);
+
+    let request = FakeRequest::default()
+        .query("page=2")
+        .header("Authorization", "Bearer abc");
+    assert_eq!(ListInvoices::parse(&request).unwrap().page, Some(2));
+
+    for request in [
+        FakeRequest::default(),
+        FakeRequest::default().header("X-Api-Key", " "),
+        FakeRequest::default().header("Authorization", "Basic dTpw"),
+    ] {
+        let err = ListInvoices::parse(&request).unwrap_err();
+        assert_eq!(
+            err,
+            HttpParseError::Unauthorized(
+                "expected an API key in header 'X-Api-Key' or a bearer token".to_string()
+            )
+        );
+        assert_eq!(err.status_code(), 401);
+    }
+}
+
+#[test]
+fn every_scheme_of_a_requirement_is_needed() {
+    let request = FakeRequest::default()
+        .query("tenant_key=t1")
+        .header("Authorization", "Basic dTpw");
+    assert_eq!(ExportInvoices::parse(&request).unwrap().tenant_key, "t1");
+
+    let err = ExportInvoices::parse(&FakeRequest::default().query("tenant_key=t1")).unwrap_err();
+    assert_eq!(
+        err,
+        HttpParseError::Unauthorized(
+            "expected basic credentials and an API key in query parameter 'tenant_key'".to_string()
+        )
+    );
+
+    // The credentials are checked before any field, so a missing key is not a missing parameter.
+    let err = ExportInvoices::parse(&FakeRequest::default().header("Authorization", "Basic dTpw"))
+        .unwrap_err();
+    assert!(matches!(err, HttpParseError::Unauthorized(_)));
+
+    // A blank key is no key.
+    for query in ["tenant_key=", "tenant_key=%20%20"] {
+        let request = FakeRequest::default()
+            .query(query)
+            .header("Authorization", "Basic dTpw");
+        let err = ExportInvoices::parse(&request).unwrap_err();
+        assert!(matches!(err, HttpParseError::Unauthorized(_)), "{}", query);
+    }
+}
+
+#[test]
+fn security_requirements_in_the_schema() {
+    use my_http_utils::my_json::json_writer::JsonValueWriter;
+
+    let requirements = ListInvoices::get_security_requirements();
+    assert_eq!(
+        requirements,
+        vec![
+            HttpSecurityRequirement::api_key_header("X-Api-Key"),
+            HttpSecurityRequirement::bearer(),
+        ]
+    );
+
+    let mut security = String::new();
+    requirements[0].write(&mut security);
+    assert_eq!(security, r#"{"apiKey_header_X-Api-Key":[]}"#);
+
+    let mut schemes = HttpSecuritySchemes::new();
+    schemes.add(&requirements);
+    schemes.add(&ExportInvoices::get_security_requirements());
+    schemes.add(&[HttpSecurityRequirement::bearer()]);
+    assert_eq!(schemes.get_schemes().len(), 4);
+
+    let mut components = String::new();
+    schemes.write(&mut components);
+    assert_eq!(
+        components,
+        concat!(
+            r#"{"apiKey_header_X-Api-Key":{"type":"apiKey","in":"header","name":"X-Api-Key"},"#,
+            r#""bearerAuth":{"type":"http","scheme":"bearer"},"#,
+            r#""basicAuth":{"type":"http","scheme":"basic"},"#,
+            r#""apiKey_query_tenant_key":{"type":"apiKey","in":"query","name":"tenant_key"}}"#,
+        )
+    );
+
+    assert!(AdminLogin::get_security_requirements().is_empty());
+}
+
+#[test]
+fn a_route_replaces_the_model_requirements() {
+    use my_http_utils::http_input::core::check_security;
+
+    let model = ListInvoices::get_security_requirements();
+    let route = [HttpSecurityRequirement::basic()];
+
+    assert_eq!(HttpSecurityRequirement::for_operation(&[], &model), model);
+    let requirements = HttpSecurityRequirement::for_operation(&route, &model);
+    assert_eq!(requirements, route);
+
+    let request = FakeRequest::default().header("X-Api-Key", "k1");
+    assert!(check_security(&request, &model).is_ok());
+    assert_eq!(
+        check_security(&request, requirements),
+        Err(HttpParseError::Unauthorized(
+            "expected basic credentials".to_string()
+        ))
+    );
+    assert!(check_security(&request, &[]).is_ok());
+}
+// ---- `JwtClaims<T>`: HMAC-verified JWT claims in `Authorization` --------------------------------
+
+use my_http_utils::http_input::{JwtAlgorithm, JwtClaims, JwtHeader, JwtVerifier};
+
+const GATEWAY_KEY: &[u8] = b"gateway-secret";
+
+/// 2100-01-01: far enough out for a token that has to stay valid.
+const NOT_EXPIRING: u64 = 4_102_444_800;
+
+struct GatewayVerifier;
+
+impl JwtVerifier for GatewayVerifier {
+    fn get_key(&self, header: &JwtHeader) -> Option<Vec<u8>> {
+        match header.kid.as_deref() {
+            Some("gateway") => Some(GATEWAY_KEY.to_vec()),
+            _ => None,
+        }
+    }
+
+    fn get_audience(&self) -> Option<&str> {
+        Some("profile")
+    }
+}
+
+/// Every test registers the same verifier, so the order they run in does not matter.
+fn register_gateway_verifier() {
+    my_http_utils::http_input::register_jwt_verifier(GatewayVerifier);
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct GatewayClaims {
+    sub: String,
+    roles: Vec<String>,
+    aud: String,
+    exp: u64,
+}
+
+impl GatewayClaims {
+    fn new(aud: &str, exp: u64) -> Self {
+        Self {
+            sub: "ann".to_string(),
+            roles: vec!["admin".to_string()],
+            aud: aud.to_string(),
+            exp,
+        }
+    }
+}
+
+#[derive(Debug, MyHttpInput)]
+struct GetProfile {
+    #[http_header(name = "Authorization", description = "Gateway token")]
+    token: JwtClaims<GatewayClaims>,
+}
+
+fn sign_gateway_token(claims: GatewayClaims, key: &[u8]) -> JwtClaims<GatewayClaims> {
+    JwtClaims::sign(claims, JwtAlgorithm::HS256, Some("gateway"), key).unwrap()
+}
+
+#[test]
+fn jwt_claims_client_server_round_trip() {
+    use my_http_utils::schema::client::THttpRequestBuilder;
+    use my_http_utils::schema::in_parameters::HttpSecurityScheme;
+
+    register_gateway_verifier();
+
+    let token = sign_gateway_token(GatewayClaims::new("profile", NOT_EXPIRING), GATEWAY_KEY);
+    let mut request = FakeRequest::default();
+    GetProfile { token }.fill_headers(&mut request).unwrap();
+    assert_eq!(request.headers[0].0, "Authorization");
+    assert!(request.headers[0].1.starts_with("Bearer eyJ"));
+
+    let parsed = GetProfile::parse(&request).unwrap();
+    assert_eq!(
+        parsed.token.get_claims(),
+        &GatewayClaims::new("profile", NOT_EXPIRING)
+    );
+    assert_eq!(parsed.token.get_header().alg, JwtAlgorithm::HS256);
+
+    let params = GetProfile::get_input_params();
+    assert_eq!(params[0].get_security_scheme(), Some(HttpSecurityScheme::Bearer));
+}
+
+#[test]
+fn a_token_that_does_not_verify_is_forbidden() {
+    register_gateway_verifier();
+
+    let cases = [
+        (
+            sign_gateway_token(GatewayClaims::new("profile", NOT_EXPIRING), b"other-secret"),
+            "Authorization: JWT signature does not match",
+        ),
+        (
+            sign_gateway_token(GatewayClaims::new("profile", 1_000_000_000), GATEWAY_KEY),
+            "Authorization: JWT has expired",
+        ),
+        (
+            sign_gateway_token(GatewayClaims::new("billing", NOT_EXPIRING), GATEWAY_KEY),
+            "Authorization: JWT is not issued for this audience",
+        ),
+    ];
+
+    for (token, expected) in cases {
+        let request = FakeRequest::default().header("Authorization", token.as_str());
+        let err = GetProfile::parse(&request).unwrap_err();
+        assert_eq!(err, HttpParseError::Forbidden(expected.to_string()));
+        assert_eq!(err.status_code(), 403);
+        assert!(!err.to_string().contains(token.get_token()));
+    }
+
+    let err = GetProfile::parse(&FakeRequest::default()).unwrap_err();
+    assert!(matches!(
+        err,
+        HttpParseError::RequiredParameterIsMissing { .. }
+    ));
+}
+// ---- HTTP message signatures (RFC 9421): sign on the client, verify on the server --------------
+
+use my_http_utils::http_input::core::HttpSignatureVerifier;
+use my_http_utils::schema::client::{
+    HeaderBuilder, HttpSignatureComponent, HttpSignatureHeaders, HttpSignatureParams,
+    HttpSignatureRequest,
+};
+use my_http_utils::UrlBuilder;
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+const WEBHOOK_KEY: &[u8] = b"webhook-secret";
+const SIGNED_AT: i64 = 1_700_000_000;
+const ORDER_DIGEST: &str = "sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:";
+
+fn webhook_key(key_id: Option<&str>) -> Option<Vec<u8>> {
+    match key_id {
+        Some("orders") => Some(WEBHOOK_KEY.to_vec()),
+        _ => None,
+    }
+}
+
+fn seconds(value: i64) -> DateTimeAsMicroseconds {
+    DateTimeAsMicroseconds::new(value * 1_000_000)
+}
+
+fn sign_order_webhook(components: Vec<HttpSignatureComponent>) -> HttpSignatureHeaders {
+    let mut url = UrlBuilder::new("https://hooks.example.com");
+    url.append_path_segment("orders");
+    url.append_path_segment("42");
+    url.append_query_param("expand", Some("items"));
+
+    let mut request = HttpSignatureRequest::new("POST", &url);
+    request.add_header("Content-Type", "application/json");
+    request.add_header("Content-Digest", ORDER_DIGEST);
+
+    let params = HttpSignatureParams::new(components)
+        .with_created(SIGNED_AT)
+        .with_key_id("orders");
+    request
+        .sign_hmac_sha256("sig1", &params, WEBHOOK_KEY)
+        .unwrap()
+}
+
+fn order_webhook_components() -> Vec<HttpSignatureComponent> {
+    vec![
+        HttpSignatureComponent::Method,
+        HttpSignatureComponent::Authority,
+        HttpSignatureComponent::Path,
+        HttpSignatureComponent::Query,
+        HttpSignatureComponent::header("Content-Type"),
+        HttpSignatureComponent::header("Content-Digest"),
+    ]
+}
+
+fn received(signed: &HttpSignatureHeaders, method: &str, path_and_query: &str) -> FakeRequest {
+    FakeRequest::default()
+        .target(method, path_and_query)
+        .header("Host", "Hooks.Example.com")
+        .header("Content-Type", "application/json")
+        .header("Content-Digest", ORDER_DIGEST)
+        .header("Signature", &signed.signature)
+        .header("Signature-Input", &signed.signature_input)
+}
+
+#[test]
+fn a_signed_request_verifies() {
+    let signed = sign_order_webhook(order_webhook_components());
+
+    let params = HttpSignatureVerifier::new()
+        .with_label("sig1")
+        .require(HttpSignatureComponent::header("content-digest"))
+        .with_max_age_seconds(300)
+        .verify(
+            &received(&signed, "POST", "/orders/42?expand=items"),
+            webhook_key,
+            seconds(SIGNED_AT + 10),
+        )
+        .unwrap();
+
+    assert_eq!(params.key_id.as_deref(), Some("orders"));
+    assert_eq!(params.created, Some(SIGNED_AT));
+    assert_eq!(params.components, order_webhook_components());
+}
+
+#[test]
+fn a_request_that_differs_from_the_signed_one_is_forbidden() {
+    let signed = sign_order_webhook(order_webhook_components());
+    let verifier = HttpSignatureVerifier::new();
+    let verify = |request: FakeRequest| {
+        verifier
+            .verify(&request, webhook_key, seconds(SIGNED_AT))
+            .unwrap_err()
+    };
+
+    let mismatch = HttpParseError::Forbidden("Signature does not match".to_string());
+    assert_eq!(
+        verify(received(&signed, "PUT", "/orders/42?expand=items")),
+        mismatch
+    );
+    assert_eq!(
+        verify(received(&signed, "POST", "/orders/43?expand=items")),
+        mismatch
+    );
+    assert_eq!(
+        verify(received(&signed, "POST", "/orders/42/?expand=items")),
+        mismatch
+    );
+    assert_eq!(verify(received(&signed, "POST", "/orders/42")), mismatch);
+
+    let mut tampered = received(&signed, "POST", "/orders/42?expand=items");
+    tampered
+        .headers
+        .retain(|(name, _)| name != "Content-Digest");
+    let tampered = tampered.header("Content-Digest", "sha-256=:AAAA:");
+    assert_eq!(verify(tampered), mismatch);
+}
+
+#[test]
+fn a_signature_that_does_not_hold_is_rejected() {
+    let request =
+        |signed: &HttpSignatureHeaders| received(signed, "POST", "/orders/42?expand=items");
+    let signed = sign_order_webhook(vec![
+        HttpSignatureComponent::Method,
+        HttpSignatureComponent::Path,
+    ]);
+
+    // No signature at all — or none under the label the verifier wants.
+    let unsigned = FakeRequest::default().target("POST", "/orders/42");
+    assert!(matches!(
+        HttpSignatureVerifier::new().verify(&unsigned, webhook_key, seconds(SIGNED_AT)),
+        Err(HttpParseError::Unauthorized(_))
+    ));
+    assert_eq!(
+        HttpSignatureVerifier::new()
+            .with_label("sig2")
+            .verify(&request(&signed), webhook_key, seconds(SIGNED_AT))
+            .unwrap_err(),
+        HttpParseError::Unauthorized("expected a message signature 'sig2'".to_string())
+    );
+
+    let forbidden = |verifier: HttpSignatureVerifier, now: i64| match verifier.verify(
+        &request(&signed),
+        webhook_key,
+        seconds(now),
+    ) {
+        Err(HttpParseError::Forbidden(reason)) => reason,
+        other => panic!("{:?}", other),
+    };
+
+    assert_eq!(
+        forbidden(
+            HttpSignatureVerifier::new().require(HttpSignatureComponent::header("content-digest")),
+            SIGNED_AT
+        ),
+        "Signature does not cover 'content-digest'"
+    );
+    assert_eq!(
+        forbidden(
+            HttpSignatureVerifier::new().with_max_age_seconds(300),
+            SIGNED_AT + 301
+        ),
+        "Signature is too old"
+    );
+
+    let unknown_key = |_: Option<&str>| None;
+    assert_eq!(
+        HttpSignatureVerifier::new()
+            .verify(&request(&signed), unknown_key, seconds(SIGNED_AT))
+            .unwrap_err(),
+        HttpParseError::Forbidden("No key to verify the signature with".to_string())
+    );
+}
+// ---- `Content-Digest` (RFC 9530) of a body in memory ----------------------------------------------
+
+use my_http_utils::body::DigestAlgorithm;
+use my_http_utils::http_input::core::{check_content_digest, read_content_digest};
+
+/// The body of a payment and the `Content-Digest` the client sends it with.
+fn digested_payment(algorithm: DigestAlgorithm) -> (Vec<u8>, String) {
+    use my_http_utils::schema::client::THttpRequestBuilder;
+
+    let body = PayInput {
+        challenge_id: "c1".to_string(),
+        pci_dss_bank_cards: None,
+    }
+    .get_body::<NoRnd>()
+    .unwrap();
+
+    let digest = body.get_content_digest(algorithm).unwrap();
+    (body.into_vec(), digest.to_header_value())
+}
+
+#[test]
+fn a_body_that_matches_its_digest_passes() {
+    for algorithm in [DigestAlgorithm::Sha256, DigestAlgorithm::Sha512] {
+        let (body, digest) = digested_payment(algorithm);
+        let request = FakeRequest::default()
+            .header("Content-Digest", &digest)
+            .body("application/json", body);
+
+        check_content_digest(&request).unwrap();
+        assert_eq!(read_content_digest(&request).unwrap().algorithm, algorithm);
+        assert_eq!(PayInput::parse(&request).unwrap().challenge_id, "c1");
+    }
+}
+
+#[test]
+fn a_body_that_does_not_match_its_digest_is_rejected() {
+    let (_, digest) = digested_payment(DigestAlgorithm::Sha256);
+    let tampered = FakeRequest::default()
+        .header("Content-Digest", &digest)
+        .body("application/json", r#"{"challengeId":"c2"}"#);
+    assert_eq!(
+        check_content_digest(&tampered).unwrap_err(),
+        HttpParseError::Validation("Content-Digest does not match the body".to_string())
+    );
+
+    let missing = FakeRequest::default().body("application/json", r#"{"challengeId":"c2"}"#);
+    assert_eq!(
+        check_content_digest(&missing).unwrap_err(),
+        HttpParseError::required("Content-Digest", "Header")
+    );
+
+    let unsupported =
+        FakeRequest::default().header("Content-Digest", "md5=:HUXZLQLMuI/KZ5KDcJPcOA==:");
+    assert_eq!(
+        check_content_digest(&unsupported).unwrap_err().code(),
+        "E_CANNOT_PARSE"
+    );
+}
+
+// ---- `#[http_client_info]`: the client behind `Forwarded` / `X-Forwarded-*` ---------------------
+
+use my_http_utils::http_input::core::read_client_info_with;
+use my_http_utils::http_input::{ClientInfo, TrustedProxies};
+
+fn ip(src: &str) -> Option<std::net::IpAddr> {
+    Some(src.parse().unwrap())
+}
+
+fn proxies() -> TrustedProxies {
+    "10.0.0.0/8, 127.0.0.1".parse().unwrap()
+}
+
+#[derive(MyHttpInput)]
+struct WhoAmIInput {
+    #[http_header(name = "X-Request-Id", description = "Request id")]
+    request_id: String,
+    #[http_client_info]
+    client: ClientInfo,
+}
+
+#[test]
+fn client_info_field_reads_behind_the_registered_proxies() {
+    my_http_utils::http_input::register_trusted_proxies(proxies());
+
+    let request = FakeRequest::default()
+        .peer("10.0.0.5")
+        .header("X-Request-Id", "r1")
+        .header("Host", "internal:8080")
+        .header("X-Forwarded-For", "203.0.113.7, 10.0.0.9")
+        .header("X-Forwarded-Proto", "HTTPS")
+        .header("X-Forwarded-Host", "api.example.com");
+
+    let input = WhoAmIInput::parse(&request).unwrap();
+    assert_eq!(input.request_id, "r1");
+    assert_eq!(
+        input.client,
+        ClientInfo {
+            ip: ip("203.0.113.7"),
+            scheme: Some("https".to_string()),
+            host: Some("api.example.com".to_string()),
+            port: None,
+        }
+    );
+
+    // Not an input: nothing about it goes into the docs.
+    let params = WhoAmIInput::get_input_params();
+    assert_eq!(params.len(), 1);
+}
+
+#[test]
+fn headers_of_an_untrusted_peer_are_ignored() {
+    let request = FakeRequest::default()
+        .peer("198.51.100.1")
+        .header("Host", "api.example.com")
+        .header("X-Forwarded-For", "1.2.3.4")
+        .header("X-Forwarded-Proto", "https");
+
+    assert_eq!(
+        read_client_info_with(&request, &proxies()),
+        ClientInfo {
+            ip: ip("198.51.100.1"),
+            scheme: None,
+            host: Some("api.example.com".to_string()),
+            port: None,
+        }
+    );
+
+    // No peer address at all: only `TrustedProxies::any()` believes the headers.
+    let request = FakeRequest::default().header("X-Real-IP", "1.2.3.4");
+    assert_eq!(read_client_info_with(&request, &proxies()).ip, None);
+    assert_eq!(
+        read_client_info_with(&request, &TrustedProxies::any()).ip,
+        ip("1.2.3.4")
+    );
+}
+
+#[test]
+fn a_spoofed_hop_in_front_of_the_client_is_not_taken() {
+    // The client made up the first hop; the proxies appended the real address after it.
+    let request = FakeRequest::default()
+        .peer("127.0.0.1")
+        .header("X-Forwarded-For", "6.6.6.6, 198.51.100.20, 10.1.2.3")
+        .header("X-Forwarded-Port", "443");
+
+    let info = read_client_info_with(&request, &proxies());
+    assert_eq!(info.ip, ip("198.51.100.20"));
+    assert_eq!(info.port, Some(443));
+
+    // Every hop is a proxy: the leftmost one is all there is.
+    let request = FakeRequest::default()
+        .peer("10.0.0.1")
+        .header("X-Forwarded-For", "10.0.0.3, 10.0.0.2");
+    assert_eq!(
+        read_client_info_with(&request, &proxies()).ip,
+        ip("10.0.0.3")
+    );
+}
+
+#[test]
+fn forwarded_header_wins_and_carries_proto_and_host() {
+    let request = FakeRequest::default()
+        .peer("10.0.0.5")
+        .header("Host", "internal")
+        .header("X-Forwarded-For", "1.2.3.4")
+        .header(
+            "Forwarded",
+            r#"for=192.0.2.43, For="[2001:db8:cafe::17]:4711";proto=HTTPS;host="example.com:8443", for=10.0.0.9;proto=http"#,
+        );
+
+    assert_eq!(
+        read_client_info_with(&request, &proxies()),
+        ClientInfo {
+            ip: ip("2001:db8:cafe::17"),
+            scheme: Some("https".to_string()),
+            host: Some("example.com".to_string()),
+            port: Some(8443),
+        }
+    );
+
+    // A hidden client is not made up out of the next hop.
+    let request = FakeRequest::default()
+        .peer("10.0.0.5")
+        .header("Host", "[::1]:8080")
+        .header("Forwarded", "for=_hidden, for=unknown");
+    assert_eq!(
+        read_client_info_with(&request, &proxies()),
+        ClientInfo {
+            ip: None,
+            scheme: None,
+            host: Some("[::1]".to_string()),
+            port: Some(8080),
+        }
+    );
+}
+
+#[test]
+fn x_real_ip_when_there_is_no_list_of_hops() {
+    let request = FakeRequest::default()
+        .peer("::ffff:10.0.0.5")
+        .header("X-Real-IP", " 203.0.113.9 ")
+        .header("X-Forwarded-Proto", "http, https");
+
+    let info = read_client_info_with(&request, &proxies());
+    assert_eq!(info.ip, ip("203.0.113.9"));
+    assert_eq!(info.scheme.as_deref(), Some("https"));
+
+    let request = FakeRequest::default().peer("10.0.0.5");
+    assert_eq!(
+        read_client_info_with(&request, &proxies()).ip,
+        ip("10.0.0.5")
+    );
+}
+
+// ---- `CorsPolicy`: preflight and actual cross-origin requests -----------------------------------
+
+use my_http_utils::http_input::core::CorsPolicy;
+
+#[derive(MyHttpInput)]
+struct UpdateOrderInput {
+    #[http_path(name = "id", description = "Order id")]
+    id: String,
+    #[http_header(name = "X-Request-Id", description = "Request id")]
+    request_id: String,
+    #[http_header(name = "If-Match", description = "Version")]
+    if_match: Option<String>,
+    #[http_body(description = "Amount")]
+    amount: i64,
+}
+
+fn orders_cors() -> CorsPolicy {
+    CorsPolicy::new()
+        .with_origin("https://app.example.com")
+        .with_origin("https://*.shop.example.com")
+        .with_method("PUT")
+        .with_headers_of(&UpdateOrderInput::get_input_params())
+        .with_header("Content-Type")
+        .with_exposed_header("X-Total-Count")
+        .with_credentials()
+        .with_max_age_seconds(600)
+}
+
+#[test]
+fn preflight_allows_the_headers_of_the_model() {
+    let request = FakeRequest::default()
+        .target("OPTIONS", "/orders/1")
+        .header("Origin", "https://eu.shop.example.com")
+        .header("Access-Control-Request-Method", "PUT")
+        .header(
+            "Access-Control-Request-Headers",
+            "x-request-id, if-match, content-type",
+        );
+
+    let response = orders_cors().evaluate(&request).unwrap();
+    assert!(response.is_preflight);
+    assert_eq!(
+        response.headers,
+        vec![
+            (
+                "Access-Control-Allow-Origin",
+                "https://eu.shop.example.com".to_string()
+            ),
+            ("Vary", "Origin".to_string()),
+            ("Access-Control-Allow-Credentials", "true".to_string()),
+            ("Access-Control-Allow-Methods", "PUT".to_string()),
+            (
+                "Access-Control-Allow-Headers",
+                "x-request-id, if-match, content-type".to_string()
+            ),
+            ("Access-Control-Max-Age", "600".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn preflight_rejections_say_why() {
+    let preflight = |origin: &str, method: &str, headers: &str| {
+        FakeRequest::default()
+            .target("OPTIONS", "/orders/1")
+            .header("Origin", origin)
+            .header("Access-Control-Request-Method", method)
+            .header("Access-Control-Request-Headers", headers)
+    };
+
+    let cases = [
+        (
+            preflight("https://evil.example.com", "PUT", ""),
+            "Origin 'https://evil.example.com' is not allowed",
+        ),
+        (
+            // The suffix rule is for subdomains only, and for its own scheme.
+            preflight("https://shop.example.com", "PUT", ""),
+            "Origin 'https://shop.example.com' is not allowed",
+        ),
+        (
+            preflight("http://eu.shop.example.com", "PUT", ""),
+            "Origin 'http://eu.shop.example.com' is not allowed",
+        ),
+        (
+            preflight("https://app.example.com", "DELETE", ""),
+            "Method 'DELETE' is not allowed",
+        ),
+        (
+            preflight("https://app.example.com", "PUT", "X-Request-Id, X-Debug"),
+            "Header 'X-Debug' is not allowed",
+        ),
+    ];
+
+    for (request, reason) in cases {
+        assert_eq!(
+            orders_cors().evaluate(&request).unwrap_err(),
+            HttpParseError::Forbidden(reason.to_string())
+        );
+    }
+}
+
+#[test]
+fn subdomain_origins_match_on_any_port_and_content_type_is_not_safelisted() {
+    let cors = CorsPolicy::new().with_origin("https://*.example.com");
+
+    assert!(cors.is_origin_allowed("https://a.example.com:8443"));
+    assert!(cors.is_origin_allowed("https://a.example.com"));
+    assert!(!cors.is_origin_allowed("https://example.com:8443"));
+    assert!(!cors.is_origin_allowed("https://a.example.com.evil.io:8443"));
+
+    // A JSON body is preflighted: its `Content-Type` is allowed only when the policy says so.
+    assert!(cors.is_header_allowed("Accept-Language"));
+    assert!(!cors.is_header_allowed("Content-Type"));
+    assert!(cors.with_header("Content-Type").is_header_allowed("content-type"));
+}
+
+#[test]
+fn actual_request_gets_the_origin_and_the_exposed_headers() {
+    let request = FakeRequest::default()
+        .target("PUT", "/orders/1")
+        .header("Origin", "HTTPS://App.Example.com");
+
+    let response = orders_cors().evaluate(&request).unwrap();
+    assert!(!response.is_preflight);
+    assert_eq!(
+        response.get_header("access-control-allow-origin"),
+        Some("HTTPS://App.Example.com")
+    );
+    assert_eq!(
+        response.get_header("Access-Control-Expose-Headers"),
+        Some("X-Total-Count")
+    );
+    assert_eq!(response.get_header("Access-Control-Max-Age"), None);
+
+    // Same-origin: nothing to check, nothing to emit.
+    let request = FakeRequest::default().target("PUT", "/orders/1");
+    assert_eq!(orders_cors().evaluate(&request).unwrap().headers, vec![]);
+}
+
+#[test]
+fn any_origin_is_a_star_and_never_allowed_with_credentials() {
+    let request = FakeRequest::default().header("Origin", "https://anyone.test");
+
+    let public = CorsPolicy::new().with_origin("*");
+    let response = public.evaluate(&request).unwrap();
+    assert_eq!(
+        response.get_header("Access-Control-Allow-Origin"),
+        Some("*")
+    );
+    assert_eq!(response.get_header("Vary"), None);
+
+    let credentialed = public.with_origin("https://app.test").with_credentials();
+    assert!(matches!(
+        credentialed.evaluate(&request),
+        Err(HttpParseError::Forbidden(_))
+    ));
+
+    let request = FakeRequest::default().header("Origin", "https://app.test");
+    let response = credentialed.evaluate(&request).unwrap();
+    assert_eq!(
+        response.get_header("Access-Control-Allow-Origin"),
+        Some("https://app.test")
+    );
+    assert_eq!(response.get_header("Vary"), Some("Origin"));
+}
+
+// ---- Conditional requests: `EntityTag` / `ETagCondition` / `HttpDate` ---------------------------
+
+use my_http_utils::http_input::core::read_preconditions;
+use my_http_utils::http_input::{
+    ETagCondition, EntityTag, HttpDate, PreconditionOutcome, Preconditions,
+};
+
+#[derive(Debug, MyHttpInput)]
+struct UpdateDocumentInput {
+    #[http_path(name = "id", description = "Document id")]
+    id: String,
+    #[http_header(name = "If-Match", description = "Expected version")]
+    if_match: Option<ETagCondition>,
+    #[http_header(name = "If-Unmodified-Since", description = "Expected last change")]
+    if_unmodified_since: Option<HttpDate>,
+}
+
+#[derive(Debug, MyHttpInput)]
+struct GetDocumentInput {
+    #[http_header(name = "If-None-Match", description = "Cached versions")]
+    if_none_match: Option<ETagCondition>,
+    #[http_header(name = "If-Modified-Since", description = "Cached at")]
+    if_modified_since: Option<HttpDate>,
+}
+
+#[test]
+fn conditional_headers_client_server_round_trip() {
+    use my_http_utils::schema::client::THttpRequestBuilder;
+
+    let sent = UpdateDocumentInput {
+        id: "d1".to_string(),
+        if_match: Some(
+            ETagCondition::from_tags(vec![
+                EntityTag::strong("v1").unwrap(),
+                EntityTag::strong("v2").unwrap(),
+            ])
+            .unwrap(),
+        ),
+        if_unmodified_since: Some(HttpDate::from_unix_seconds(784_111_777)),
+    };
+
+    let mut request = FakeRequest::default().path("id", "d1");
+    sent.fill_headers(&mut request).unwrap();
+    assert_eq!(request.get_header("If-Match"), Some(r#""v1", "v2""#));
+    assert_eq!(
+        request.get_header("If-Unmodified-Since"),
+        Some("Sun, 06 Nov 1994 08:49:37 GMT")
+    );
+
+    let parsed = UpdateDocumentInput::parse(&request).unwrap();
+    assert_eq!(parsed.if_match, sent.if_match);
+    assert_eq!(parsed.if_unmodified_since, sent.if_unmodified_since);
+
+    let preconditions = Preconditions {
+        if_match: parsed.if_match,
+        if_unmodified_since: parsed.if_unmodified_since,
+        ..Default::default()
+    };
+    let current = EntityTag::strong("v2").unwrap();
+    assert_eq!(
+        preconditions.evaluate_preconditions(Some(&current), None, "PUT"),
+        PreconditionOutcome::Proceed
+    );
+    let current = EntityTag::strong("v3").unwrap();
+    assert_eq!(
+        preconditions
+            .evaluate_preconditions(Some(&current), None, "PUT")
+            .get_status_code(),
+        Some(412)
+    );
+}
+
+#[test]
+fn conditional_headers_parse_from_the_request() {
+    let request = FakeRequest::default()
+        .header("If-None-Match", r#"W/"v2", "v3""#)
+        .header("If-Modified-Since", "Sunday, 06-Nov-94 08:49:37 GMT");
+
+    let parsed = GetDocumentInput::parse(&request).unwrap();
+    assert_eq!(parsed.if_none_match.as_ref().unwrap().get_tags().len(), 2);
+    assert_eq!(
+        parsed.if_modified_since.unwrap().as_str(),
+        "Sun, 06 Nov 1994 08:49:37 GMT"
+    );
+
+    let preconditions = read_preconditions(&request).unwrap();
+    let current = EntityTag::strong("v2").unwrap();
+    assert_eq!(
+        preconditions.evaluate_preconditions(Some(&current), None, "GET"),
+        PreconditionOutcome::NotModified
+    );
+
+    let malformed = FakeRequest::default().header("If-None-Match", "v2");
+    assert_eq!(
+        GetDocumentInput::parse(&malformed).unwrap_err().code(),
+        "E_CANNOT_PARSE"
+    );
+    assert_eq!(
+        read_preconditions(&malformed).unwrap_err(),
+        HttpParseError::cannot_parse("If-None-Match", "Header", "v2")
+    );
+
+    // A date that is not an HTTP-date is ignored when the headers are read off the request.
+    let request = FakeRequest::default().header("If-Modified-Since", "yesterday");
+    assert_eq!(
+        read_preconditions(&request).unwrap(),
+        Preconditions::default()
+    );
+}
+
+// ---- Range requests: `RangeHeader` / `ContentRange` ----------------------------------------------
+
+use my_http_utils::http_input::{ByteRange, ContentRange, RangeHeader, RangeOutcome};
+
+#[derive(Debug, MyHttpInput)]
+struct DownloadBlobInput {
+    #[http_path(name = "id", description = "Blob id")]
+    id: String,
+    #[http_header(name = "Range", description = "Byte ranges to download")]
+    range: Option<RangeHeader>,
+}
+
+#[test]
+fn range_header_client_server_round_trip() {
+    use my_http_utils::schema::client::THttpRequestBuilder;
+
+    let sent = DownloadBlobInput {
+        id: "b1".to_string(),
+        range: Some(RangeHeader::single(0, 499).unwrap()),
+    };
+
+    let mut request = FakeRequest::default().path("id", "b1");
+    sent.fill_headers(&mut request).unwrap();
+    assert_eq!(request.get_header("Range"), Some("bytes=0-499"));
+
+    let parsed = DownloadBlobInput::parse(&request).unwrap();
+    assert_eq!(parsed.range, sent.range);
+
+    let RangeOutcome::Partial(ranges) = parsed.range.unwrap().resolve(300) else {
+        panic!("expected a partial answer");
+    };
+    assert_eq!(ranges, vec![ByteRange::new(0, 299).unwrap()]);
+    assert_eq!(
+        ContentRange::new(ranges[0], Some(300)).unwrap().as_str(),
+        "bytes 0-299/300"
+    );
+}
+
+#[test]
+fn range_header_parses_several_ranges_and_rejects_a_malformed_one() {
+    let request = FakeRequest::default()
+        .path("id", "b1")
+        .header("Range", "bytes=-100, 0-99");
+
+    let range = DownloadBlobInput::parse(&request).unwrap().range.unwrap();
+    assert_eq!(range.get_specs().len(), 2);
+    assert_eq!(
+        range.resolve(1000),
+        RangeOutcome::Partial(vec![
+            ByteRange::new(0, 99).unwrap(),
+            ByteRange::new(900, 999).unwrap()
+        ])
+    );
+
+    // Nothing of the representation is asked for: 416 with `bytes */length`.
+    let unsatisfiable: RangeHeader = "bytes=5000-".parse().unwrap();
+    assert_eq!(unsatisfiable.resolve(1000), RangeOutcome::NotSatisfiable);
+    assert_eq!(ContentRange::unsatisfied(1000).as_str(), "bytes */1000");
+
+    let malformed = FakeRequest::default()
+        .path("id", "b1")
+        .header("Range", "bytes=99-0");
+    assert_eq!(
+        DownloadBlobInput::parse(&malformed).unwrap_err().code(),
+        "E_CANNOT_PARSE"
+    );
+}